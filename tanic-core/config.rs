@@ -3,16 +3,21 @@
 use directories::ProjectDirs;
 use figment::{
     providers::{Env, Format, Serialized, Toml},
+    value::Value,
     Figment,
 };
 use http::Uri;
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::Result;
 
 /// Represents a named set of connection details for an Iceberg catalog
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ConnectionDetails {
     pub id: Uuid,
 
@@ -44,6 +49,259 @@ impl ConnectionDetails {
 pub struct TanicConfig {
     /// list of known connections
     pub library: Vec<ConnectionDetails>,
+
+    /// colours and styling of the UI
+    #[serde(default)]
+    pub theme: ThemeConfig,
+
+    /// where logs are written, besides the log panel
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// how long catalog metadata is reused before it is fetched again
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// limits on requests made to catalogs
+    #[serde(default)]
+    pub requests: RequestConfig,
+
+    /// thresholds for the table health checks
+    #[serde(default)]
+    pub health: HealthConfig,
+}
+
+/// The `[health]` section of the config file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// data files smaller than this many MiB count as small
+    pub small_file_mb: u64,
+
+    /// most small data files a table can have before it is flagged
+    pub max_small_files: u64,
+
+    /// most snapshots a table can retain before it is flagged
+    pub max_snapshots: usize,
+
+    /// days without a commit after which a table is flagged as stale
+    pub stale_after_days: u64,
+
+    /// most delete files a table's current snapshot can have before it is
+    /// flagged
+    pub max_delete_files: u64,
+
+    /// most entries a table's metadata log can have before it is flagged
+    pub max_metadata_log_entries: usize,
+
+    /// flag tables that don't declare a sort order
+    pub require_sort_order: bool,
+
+    /// `write.*` table properties that every table should set
+    pub required_write_properties: Vec<String>,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            small_file_mb: 32,
+            max_small_files: 100,
+            max_snapshots: 100,
+            stale_after_days: 30,
+            max_delete_files: 100,
+            max_metadata_log_entries: 100,
+            require_sort_order: true,
+            required_write_properties: vec![
+                "write.target-file-size-bytes".to_string(),
+                "write.metadata.delete-after-commit.enabled".to_string(),
+            ],
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Size below which data files count as small, in bytes
+    pub fn small_file_bytes(&self) -> u64 {
+        self.small_file_mb << 20
+    }
+}
+
+/// The `[requests]` section of the config file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestConfig {
+    /// seconds to wait for a catalog to respond before giving up
+    pub timeout_secs: u64,
+
+    /// maximum number of catalog requests to make at once
+    pub max_concurrent: usize,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            max_concurrent: 8,
+        }
+    }
+}
+
+impl RequestConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
+/// The `[cache]` section of the config file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// seconds after which cached metadata is shown while being refreshed
+    pub ttl_secs: u64,
+
+    /// keep cached metadata on disk, so that it can be shown at startup
+    pub persist: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 300,
+            persist: true,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+
+    /// Directory that cached metadata is persisted to, if persisting is
+    /// enabled and the directory can be determined
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        if !self.persist {
+            return None;
+        }
+
+        project_dirs().map(|proj_dirs| proj_dirs.cache_dir().join("metadata"))
+    }
+}
+
+/// The `[logging]` section of the config file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// also write logs to daily-rotated files in the project data dir
+    pub to_file: bool,
+
+    /// number of rotated log files to keep
+    pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            to_file: false,
+            max_files: 7,
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Directory that log files are written to, if it can be determined
+    pub fn log_dir(&self) -> Option<PathBuf> {
+        project_dirs().map(|proj_dirs| proj_dirs.data_dir().join("logs"))
+    }
+}
+
+/// The `[theme]` section of the config file. Invalid settings fall back to
+/// their defaults, since a typo in the theme shouldn't stop tanic starting.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// built-in theme to use
+    #[serde(deserialize_with = "or_default")]
+    pub preset: ThemePreset,
+
+    /// metric used to colour the tiles of the treemap
+    #[serde(deserialize_with = "or_default")]
+    pub tile_metric: TileMetric,
+
+    /// set of icons to decorate namespaces and tables with
+    #[serde(deserialize_with = "or_default")]
+    pub icons: IconSet,
+}
+
+fn or_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned + Default,
+{
+    let value = <Value as Deserialize>::deserialize(deserializer)?;
+
+    Ok(value.deserialize().unwrap_or_else(|err| {
+        tracing::warn!(%err, "invalid theme setting, using the default");
+        T::default()
+    }))
+}
+
+/// Sets of icons that the UI can use
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+    /// Requires a Nerd Font. See <https://www.nerdfonts.com>
+    #[default]
+    NerdFont,
+
+    /// Symbols available in most Unicode fonts
+    Unicode,
+
+    /// Plain ASCII, for terminals with limited fonts
+    Ascii,
+}
+
+impl FromStr for IconSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "nerd-font" => Ok(IconSet::NerdFont),
+            "unicode" => Ok(IconSet::Unicode),
+            "ascii" => Ok(IconSet::Ascii),
+            other => Err(format!(
+                "unknown icon set '{other}', expected one of: nerd-font, unicode, ascii"
+            )),
+        }
+    }
+}
+
+/// Built-in UI themes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+/// Metric that treemap tiles can be coloured by
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TileMetric {
+    /// all tiles use the same colour
+    #[default]
+    None,
+
+    /// total size of the table's data files
+    Size,
+
+    /// time since the table was last updated
+    Staleness,
+
+    /// number of data files in the table
+    FileCount,
 }
 
 impl TanicConfig {
@@ -53,7 +311,7 @@ impl TanicConfig {
     pub fn load() -> Result<TanicConfig> {
         let mut figment = Figment::from(Serialized::defaults(TanicConfig::default()));
 
-        if let Some(proj_dirs) = ProjectDirs::from("com", "Tanic", "Tanic") {
+        if let Some(proj_dirs) = project_dirs() {
             let config_dir = proj_dirs.config_dir();
 
             figment = figment.merge(Toml::file(config_dir.join("tanic.toml")))
@@ -62,3 +320,60 @@ impl TanicConfig {
         Ok(figment.merge(Env::prefixed("TANIC_")).extract()?)
     }
 }
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("com", "Tanic", "Tanic")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> TanicConfig {
+        Figment::from(Serialized::defaults(TanicConfig::default()))
+            .merge(Toml::string(toml))
+            .extract()
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_the_theme() {
+        let config = parse(
+            r#"
+            [theme]
+            preset = "high-contrast"
+            tile_metric = "file-count"
+            icons = "ascii"
+            "#,
+        );
+
+        assert_eq!(config.theme.preset, ThemePreset::HighContrast);
+        assert_eq!(config.theme.tile_metric, TileMetric::FileCount);
+        assert_eq!(config.theme.icons, IconSet::Ascii);
+    }
+
+    #[test]
+    fn invalid_theme_settings_fall_back_to_their_defaults() {
+        let config = parse(
+            r#"
+            [theme]
+            preset = "neon"
+            tile_metric = 7
+            icons = "unicode"
+            "#,
+        );
+
+        assert_eq!(config.theme.preset, ThemePreset::default());
+        assert_eq!(config.theme.tile_metric, TileMetric::default());
+        assert_eq!(config.theme.icons, IconSet::Unicode);
+    }
+
+    #[test]
+    fn a_missing_theme_is_the_default() {
+        let config = parse("");
+
+        assert_eq!(config.theme.preset, ThemePreset::default());
+        assert_eq!(config.theme.tile_metric, TileMetric::default());
+        assert_eq!(config.theme.icons, IconSet::default());
+    }
+}
@@ -3,7 +3,9 @@
 use figment::Error as FigmentError;
 use iceberg::Error as IcebergError;
 use miette::Result as MietteResult;
+use std::any::Any;
 use std::io::Error as StdIoError;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Standard Tanic `Result`.
@@ -16,7 +18,7 @@ pub type Result<T> = MietteResult<T, TanicError>;
 #[derive(Error, Debug)]
 pub enum TanicError {
     #[error("Config Parse Error")]
-    Figment(#[from] FigmentError),
+    Figment(#[from] Box<FigmentError>),
 
     #[error("IO Error")]
     IoError(#[from] StdIoError),
@@ -24,6 +26,24 @@ pub enum TanicError {
     #[error("Iceberg Error")]
     IcebergError(#[from] IcebergError),
 
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+
     #[error("Unexpected")]
     UnexpectedError(String),
 }
+
+impl From<FigmentError> for TanicError {
+    fn from(err: FigmentError) -> Self {
+        Self::Figment(Box::new(err))
+    }
+}
+
+/// The message that a panic was raised with, from its payload
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
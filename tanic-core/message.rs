@@ -1,13 +1,90 @@
-#[derive(Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NamespaceDeets {
     pub parts: Vec<String>,
     pub name: String,
     pub table_count: usize,
+
+    /// shown from the metadata cache, and not yet confirmed by the catalog
+    #[serde(default)]
+    pub stale: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TableDeets {
     pub namespace: Vec<String>,
     pub name: String,
     pub row_count: usize,
+
+    /// Stats from the table's current snapshot, once the table has been loaded
+    pub summary: Option<TableSummary>,
+
+    /// shown from the metadata cache, and not yet confirmed by the catalog
+    #[serde(default)]
+    pub stale: bool,
+}
+
+/// Summary stats for a table, taken from its metadata and current snapshot
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TableSummary {
+    /// total size of the data files in the current snapshot
+    pub total_size_bytes: Option<u64>,
+
+    /// number of data files in the current snapshot
+    pub data_file_count: Option<u64>,
+
+    /// number of records in the current snapshot
+    pub record_count: Option<u64>,
+
+    /// when the table was last updated, in ms since the epoch
+    pub last_updated_ms: i64,
+
+    /// what the health checks found when the table was loaded
+    #[serde(default)]
+    pub findings: Vec<HealthFinding>,
+}
+
+/// Something a health check found wrong with a table
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HealthFinding {
+    pub check: HealthCheck,
+    pub severity: Severity,
+
+    /// what was found, and the threshold it broke
+    pub message: String,
+}
+
+/// The checks that make up a table's health pass
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HealthCheck {
+    SmallFiles,
+    Snapshots,
+    SortOrder,
+    StaleCommit,
+    DeleteFiles,
+    MetadataLog,
+    WriteProperties,
+}
+
+impl HealthCheck {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HealthCheck::SmallFiles => "small files",
+            HealthCheck::Snapshots => "snapshots",
+            HealthCheck::SortOrder => "sort order",
+            HealthCheck::StaleCommit => "stale",
+            HealthCheck::DeleteFiles => "delete files",
+            HealthCheck::MetadataLog => "metadata log",
+            HealthCheck::WriteProperties => "write properties",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    Info,
+    Warning,
 }
@@ -0,0 +1,381 @@
+//! Metadata Cache
+//!
+//! Catalog listings and table summaries, kept per connection so that
+//! revisiting a view doesn't have to wait on the catalog. Entries older than
+//! the TTL are still shown, but are refreshed in the background.
+//!
+//! Caches are also persisted to disk, so that the last-known state of a
+//! catalog can be shown straight away at startup.
+
+use http::Uri;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use tanic_core::message::{NamespaceDeets, TableDeets};
+use tanic_core::{Result, TanicError};
+
+/// A cached value, along with when it was fetched
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Cached<T> {
+    pub(crate) value: T,
+    pub(crate) fetched_at: SystemTime,
+
+    /// fetched during this session, rather than loaded from disk. Values
+    /// from disk are always reconciled with the catalog before being trusted.
+    #[serde(skip)]
+    live: bool,
+}
+
+impl<T> Cached<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value,
+            fetched_at: SystemTime::now(),
+            live: true,
+        }
+    }
+
+    pub(crate) fn is_fresh(&self, ttl: Duration) -> bool {
+        self.live
+            && self
+                .fetched_at
+                .elapsed()
+                .map(|age| age < ttl)
+                .unwrap_or(false)
+    }
+}
+
+/// Cached metadata for a single connection
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct MetadataCache {
+    namespaces: Option<Cached<Vec<NamespaceDeets>>>,
+
+    /// tables, keyed by namespace parts. Table summaries are filled in as
+    /// tables are loaded.
+    #[serde(with = "tables_by_namespace")]
+    tables: HashMap<Vec<String>, Cached<Vec<TableDeets>>>,
+
+    /// changed since it was last persisted
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl MetadataCache {
+    pub(crate) fn namespaces(&self) -> Option<&Cached<Vec<NamespaceDeets>>> {
+        self.namespaces.as_ref()
+    }
+
+    pub(crate) fn set_namespaces(&mut self, namespaces: Vec<NamespaceDeets>) {
+        self.namespaces = Some(Cached::new(namespaces));
+        self.dirty = true;
+    }
+
+    pub(crate) fn tables(&self, namespace: &[String]) -> Option<&Cached<Vec<TableDeets>>> {
+        self.tables.get(namespace)
+    }
+
+    /// Replace the tables of a namespace, keeping the summaries of any tables
+    /// that were already known, marked as stale, until they are loaded again
+    pub(crate) fn set_tables(
+        &mut self,
+        namespace: &[String],
+        tables: Vec<TableDeets>,
+    ) -> Vec<TableDeets> {
+        let previous = self.tables.remove(namespace);
+
+        let tables = tables
+            .into_iter()
+            .map(|table| {
+                previous
+                    .iter()
+                    .flat_map(|cached| &cached.value)
+                    .find(|known| known.name == table.name)
+                    .map(|known| TableDeets {
+                        stale: known.summary.is_some(),
+                        ..known.clone()
+                    })
+                    .unwrap_or(table)
+            })
+            .collect::<Vec<_>>();
+
+        self.tables
+            .insert(namespace.to_vec(), Cached::new(tables.clone()));
+        self.dirty = true;
+        tables
+    }
+
+    /// A table's cached details, which are as fresh as the listing that
+    /// they are part of
+    pub(crate) fn table(&self, namespace: &[String], name: &str) -> Option<Cached<TableDeets>> {
+        let cached = self.tables.get(namespace)?;
+        let table = cached.value.iter().find(|table| table.name == name)?;
+
+        Some(Cached {
+            value: table.clone(),
+            fetched_at: cached.fetched_at,
+            live: cached.live,
+        })
+    }
+
+    /// Record a table's loaded summary
+    pub(crate) fn update_table(&mut self, table: &TableDeets) {
+        let Some(cached) = self.tables.get_mut(&table.namespace) else {
+            return;
+        };
+
+        if let Some(known) = cached
+            .value
+            .iter_mut()
+            .find(|known| known.name == table.name)
+        {
+            *known = table.clone();
+            self.dirty = true;
+        }
+    }
+
+    /// Whether the cache has changed since this was last called
+    pub(crate) fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+}
+
+/// Where metadata caches are persisted, one file per catalog URI.
+///
+/// Failing to read or write a cache file is logged, rather than treated as
+/// an error, since the catalog itself can always be asked again.
+#[derive(Debug)]
+pub(crate) struct CacheStore {
+    dir: Option<PathBuf>,
+}
+
+impl CacheStore {
+    pub(crate) fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    /// Load the persisted cache for a catalog, or an empty one
+    pub(crate) async fn load(&self, uri: &Uri) -> MetadataCache {
+        let Some(path) = self.path_for(uri) else {
+            return MetadataCache::default();
+        };
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return MetadataCache::default();
+        }
+
+        let loaded = Self::read(&path).await;
+
+        match loaded {
+            Ok(cache) => {
+                tracing::debug!(path = %path.display(), "loaded metadata cache");
+                cache
+            }
+            Err(err) => {
+                tracing::warn!(path = %path.display(), %err, "could not load metadata cache");
+                MetadataCache::default()
+            }
+        }
+    }
+
+    pub(crate) async fn save(&self, uri: &Uri, cache: &MetadataCache) {
+        let Some(path) = self.path_for(uri) else {
+            return;
+        };
+
+        if let Err(err) = Self::write(&path, cache).await {
+            tracing::warn!(path = %path.display(), %err, "could not save metadata cache");
+        }
+    }
+
+    async fn read(path: &PathBuf) -> Result<MetadataCache> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+
+        // a large catalog's cache takes a while to parse
+        tokio::task::spawn_blocking(move || serde_json::from_slice(&bytes))
+            .await
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))
+    }
+
+    async fn write(path: &PathBuf, cache: &MetadataCache) -> Result<()> {
+        let bytes = serde_json::to_vec(cache)
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+        }
+
+        // write then rename, so that a partially written file is never read
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, bytes)
+            .await
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+        tokio::fs::rename(&tmp_path, path)
+            .await
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))
+    }
+
+    fn path_for(&self, uri: &Uri) -> Option<PathBuf> {
+        let file_name = uri
+            .to_string()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{file_name}.json")))
+    }
+}
+
+/// JSON object keys must be strings, so tables are persisted as a list of
+/// (namespace parts, tables) pairs instead
+mod tables_by_namespace {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    type Tables = HashMap<Vec<String>, Cached<Vec<TableDeets>>>;
+
+    pub(super) fn serialize<S: Serializer>(
+        tables: &Tables,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(tables.iter())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Tables, D::Error> {
+        let entries = Vec::<(Vec<String>, Cached<Vec<TableDeets>>)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tanic_core::message::TableSummary;
+
+    fn table(name: &str) -> TableDeets {
+        TableDeets {
+            namespace: vec!["sales".to_string()],
+            name: name.to_string(),
+            row_count: 0,
+            summary: None,
+            stale: false,
+        }
+    }
+
+    fn uri() -> Uri {
+        Uri::from_static("http://localhost:8181/catalog")
+    }
+
+    fn sales() -> Vec<String> {
+        vec!["sales".to_string()]
+    }
+
+    #[test]
+    fn cached_values_round_trip_but_are_not_fresh_until_reconciled() {
+        let cached = Cached::new(vec![table("orders")]);
+        assert!(cached.is_fresh(Duration::from_secs(60)));
+
+        let json = serde_json::to_string(&cached).unwrap();
+        let loaded: Cached<Vec<TableDeets>> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.fetched_at, cached.fetched_at);
+        assert_eq!(loaded.value.len(), 1);
+        assert_eq!(loaded.value[0].name, "orders");
+        assert!(!loaded.is_fresh(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn cache_file_names_are_sanitised() {
+        let store = CacheStore::new(Some(PathBuf::from("/cache")));
+        let uri = Uri::from_static("http://host:8181/a/../../b");
+
+        let path = store.path_for(&uri).unwrap();
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+
+        assert_eq!(path.parent(), Some(std::path::Path::new("/cache")));
+        assert_eq!(file_name, "http___host_8181_a_______b.json");
+    }
+
+    #[test]
+    fn nothing_is_persisted_without_a_cache_dir() {
+        assert_eq!(CacheStore::new(None).path_for(&uri()), None);
+    }
+
+    #[test]
+    fn cached_summaries_are_stale_after_relisting_until_reloaded() {
+        let mut cache = MetadataCache::default();
+        cache.set_tables(&sales(), vec![table("orders"), table("refunds")]);
+        cache.update_table(&TableDeets {
+            row_count: 42,
+            summary: Some(TableSummary::default()),
+            ..table("orders")
+        });
+
+        let listed = cache.set_tables(&sales(), vec![table("orders"), table("refunds")]);
+        assert!(listed[0].stale);
+        assert_eq!(listed[0].row_count, 42);
+        assert!(listed[0].summary.is_some());
+        assert!(
+            !listed[1].stale,
+            "a table without a summary has nothing stale"
+        );
+        assert!(cache.table(&sales(), "orders").unwrap().value.stale);
+
+        cache.update_table(&TableDeets {
+            row_count: 43,
+            summary: Some(TableSummary::default()),
+            ..table("orders")
+        });
+        let reloaded = cache.table(&sales(), "orders").unwrap();
+        assert!(!reloaded.value.stale);
+        assert_eq!(reloaded.value.row_count, 43);
+        assert!(reloaded.is_fresh(Duration::from_secs(60)));
+    }
+
+    #[tokio::test]
+    async fn caches_round_trip_through_the_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CacheStore::new(Some(dir.path().to_path_buf()));
+
+        let mut cache = MetadataCache::default();
+        cache.set_tables(&sales(), vec![table("orders")]);
+        store.save(&uri(), &cache).await;
+
+        let loaded = store.load(&uri()).await;
+        let tables = loaded.tables(&sales()).unwrap();
+        assert_eq!(tables.value[0].name, "orders");
+        assert!(!tables.is_fresh(Duration::from_secs(60)));
+    }
+
+    #[tokio::test]
+    async fn a_missing_cache_file_loads_an_empty_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CacheStore::new(Some(dir.path().to_path_buf()));
+
+        let loaded = store.load(&uri()).await;
+
+        assert!(loaded.namespaces().is_none());
+        assert!(loaded.tables(&sales()).is_none());
+    }
+
+    #[tokio::test]
+    async fn a_corrupt_cache_file_loads_an_empty_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CacheStore::new(Some(dir.path().to_path_buf()));
+        std::fs::write(store.path_for(&uri()).unwrap(), b"{\"namespaces\": [").unwrap();
+
+        let loaded = store.load(&uri()).await;
+
+        assert!(loaded.namespaces().is_none());
+        assert!(loaded.tables(&sales()).is_none());
+    }
+}
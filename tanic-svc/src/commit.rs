@@ -0,0 +1,141 @@
+//! Table Commits
+//!
+//! Changes to a table's metadata that Iceberg's transactions can't make yet,
+//! such as removing snapshots, are committed to the catalog directly. Each
+//! commit carries requirements that the catalog checks against the table as
+//! it is at commit time, so that a commit planned against a table that has
+//! since changed is rejected rather than applied.
+
+use futures::future::BoxFuture;
+use http::Uri;
+use iceberg::{TableIdent, TableRequirement, TableUpdate};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use tanic_core::config::ConnectionDetails;
+use tanic_core::{Result, TanicError};
+
+/// Commits updates to a table's metadata
+pub trait TableCommitter: std::fmt::Debug + Send + Sync {
+    fn commit<'a>(
+        &'a self,
+        table: &'a TableIdent,
+        requirements: Vec<TableRequirement>,
+        updates: Vec<TableUpdate>,
+    ) -> BoxFuture<'a, Result<()>>;
+}
+
+/// Commits through an Iceberg REST catalog's update table endpoint
+#[derive(Debug)]
+pub struct RestTableCommitter {
+    uri: Uri,
+    client: reqwest::Client,
+}
+
+/// The part of a REST catalog's config that locates its endpoints
+#[derive(Debug, Default, Deserialize)]
+struct CatalogConfig {
+    #[serde(default)]
+    defaults: HashMap<String, String>,
+    #[serde(default)]
+    overrides: HashMap<String, String>,
+}
+
+impl CatalogConfig {
+    fn prefix(&self) -> Option<&str> {
+        self.overrides
+            .get("prefix")
+            .or_else(|| self.defaults.get("prefix"))
+            .map(String::as_str)
+    }
+}
+
+impl RestTableCommitter {
+    pub fn new(connection_details: &ConnectionDetails) -> Self {
+        Self {
+            uri: connection_details.uri.clone(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// The catalog's URL with these path segments appended
+    fn url<'a>(&self, segments: impl IntoIterator<Item = &'a str>) -> Result<reqwest::Url> {
+        let mut url = reqwest::Url::parse(&self.uri.to_string())
+            .map_err(|err| TanicError::UnexpectedError(format!("invalid catalog URI: {err}")))?;
+        url.path_segments_mut()
+            .map_err(|_| TanicError::UnexpectedError(format!("invalid catalog URI: {}", self.uri)))?
+            .pop_if_empty()
+            .extend(segments);
+
+        Ok(url)
+    }
+
+    async fn config(&self) -> Result<CatalogConfig> {
+        let response = self
+            .client
+            .get(self.url(["v1", "config"])?)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        checked(response).await?.json().await.map_err(request_error)
+    }
+
+    async fn commit_table(
+        &self,
+        table: &TableIdent,
+        requirements: Vec<TableRequirement>,
+        updates: Vec<TableUpdate>,
+    ) -> Result<()> {
+        let config = self.config().await?;
+        let namespace = table.namespace().to_url_string();
+
+        let mut segments = vec!["v1"];
+        segments.extend(config.prefix());
+        segments.extend(["namespaces", &namespace, "tables", table.name()]);
+
+        let body = serde_json::json!({
+            "identifier": table,
+            "requirements": requirements,
+            "updates": updates,
+        });
+        let response = self
+            .client
+            .post(self.url(segments)?)
+            .json(&body)
+            .send()
+            .await
+            .map_err(request_error)?;
+        checked(response).await?;
+
+        Ok(())
+    }
+}
+
+impl TableCommitter for RestTableCommitter {
+    fn commit<'a>(
+        &'a self,
+        table: &'a TableIdent,
+        requirements: Vec<TableRequirement>,
+        updates: Vec<TableUpdate>,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(self.commit_table(table, requirements, updates))
+    }
+}
+
+/// The response, if it was successful, or an error with the catalog's reason
+async fn checked(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    Err(TanicError::UnexpectedError(format!(
+        "catalog responded {status}: {body}"
+    )))
+}
+
+fn request_error(err: reqwest::Error) -> TanicError {
+    TanicError::UnexpectedError(format!("catalog request failed: {err}"))
+}
@@ -0,0 +1,441 @@
+//! Table Comparison
+//!
+//! Side-by-side comparison of the metadata of two tables, typically the
+//! same logical table in two different catalogs.
+
+use iceberg::spec::{StructType, TableMetadata, Type};
+use iceberg::table::Table;
+use serde::{Deserialize, Serialize};
+
+use tanic_core::config::ConnectionDetails;
+
+/// A table in a particular catalog
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableRef {
+    pub connection: ConnectionDetails,
+    pub namespace: Vec<String>,
+    pub name: String,
+}
+
+impl TableRef {
+    /// `<connection name>: <namespace>.<table>`
+    pub fn label(&self) -> String {
+        format!(
+            "{}: {}.{}",
+            self.connection.name,
+            self.namespace.join("."),
+            self.name
+        )
+    }
+}
+
+/// One compared value. `None` means the value is absent from that side.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiffEntry {
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl DiffEntry {
+    pub fn is_different(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+/// A titled group of compared values, such as the schema or the properties
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonSection {
+    pub title: String,
+    pub entries: Vec<DiffEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableComparison {
+    pub left: TableRef,
+    pub right: TableRef,
+
+    /// whether each table could be loaded
+    pub left_found: bool,
+    pub right_found: bool,
+
+    pub sections: Vec<ComparisonSection>,
+}
+
+impl TableComparison {
+    pub fn new(
+        left_ref: TableRef,
+        left: Option<&Table>,
+        right_ref: TableRef,
+        right: Option<&Table>,
+    ) -> Self {
+        let left_meta = left.map(Table::metadata);
+        let right_meta = right.map(Table::metadata);
+
+        let sections = [
+            (
+                "Overview",
+                overview as fn(&TableMetadata) -> Vec<(String, String)>,
+            ),
+            ("Schema", schema_fields),
+            ("Partition Spec", partition_fields),
+            ("Sort Order", sort_fields),
+            ("Properties", properties),
+        ]
+        .into_iter()
+        .map(|(title, extract)| ComparisonSection {
+            title: title.to_string(),
+            entries: diff(
+                left_meta.map(extract).unwrap_or_default(),
+                right_meta.map(extract).unwrap_or_default(),
+            ),
+        })
+        .collect();
+
+        Self {
+            left: left_ref,
+            right: right_ref,
+            left_found: left.is_some(),
+            right_found: right.is_some(),
+            sections,
+        }
+    }
+
+    pub fn difference_count(&self) -> usize {
+        self.sections
+            .iter()
+            .flat_map(|section| &section.entries)
+            .filter(|entry| entry.is_different())
+            .count()
+    }
+}
+
+/// Pair up two lists of key/value pairs by key, keeping the left's ordering
+/// and then appending keys that are only on the right
+fn diff(left: Vec<(String, String)>, right: Vec<(String, String)>) -> Vec<DiffEntry> {
+    let mut entries = left
+        .into_iter()
+        .map(|(key, value)| DiffEntry {
+            key,
+            left: Some(value),
+            right: None,
+        })
+        .collect::<Vec<_>>();
+
+    for (key, value) in right {
+        match entries.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => entry.right = Some(value),
+            None => entries.push(DiffEntry {
+                key,
+                left: None,
+                right: Some(value),
+            }),
+        }
+    }
+
+    entries
+}
+
+fn overview(metadata: &TableMetadata) -> Vec<(String, String)> {
+    let snapshot = metadata.current_snapshot();
+    let snapshot_stat = |key: &str| {
+        snapshot
+            .and_then(|snapshot| snapshot.summary().additional_properties.get(key))
+            .cloned()
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let last_commit = metadata
+        .last_updated_timestamp()
+        .map(|ts| ts.format("%F %T UTC").to_string())
+        .unwrap_or_else(|_| metadata.last_updated_ms().to_string());
+
+    vec![
+        (
+            "format version".to_string(),
+            metadata.format_version().to_string(),
+        ),
+        (
+            "current snapshot".to_string(),
+            snapshot
+                .map(|snapshot| snapshot.snapshot_id().to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ),
+        ("record count".to_string(), snapshot_stat("total-records")),
+        ("data files".to_string(), snapshot_stat("total-data-files")),
+        (
+            "total size (bytes)".to_string(),
+            snapshot_stat("total-files-size"),
+        ),
+        ("last commit".to_string(), last_commit),
+    ]
+}
+
+fn schema_fields(metadata: &TableMetadata) -> Vec<(String, String)> {
+    let mut fields = vec![];
+    flatten_struct("", metadata.current_schema().as_struct(), &mut fields);
+    fields
+}
+
+fn flatten_struct(prefix: &str, struct_type: &StructType, fields: &mut Vec<(String, String)>) {
+    for field in struct_type.fields() {
+        let name = format!("{prefix}{}", field.name);
+        let nullability = if field.required {
+            "required"
+        } else {
+            "optional"
+        };
+        fields.push((
+            name.clone(),
+            format!("{} {}", type_name(&field.field_type), nullability),
+        ));
+
+        if let Type::Struct(nested) = field.field_type.as_ref() {
+            flatten_struct(&format!("{name}."), nested, fields);
+        }
+    }
+}
+
+pub(crate) fn type_name(field_type: &Type) -> String {
+    match field_type {
+        Type::Primitive(primitive) => primitive.to_string(),
+        Type::Struct(_) => "struct".to_string(),
+        Type::List(list) => format!("list<{}>", type_name(&list.element_field.field_type)),
+        Type::Map(map) => format!(
+            "map<{}, {}>",
+            type_name(&map.key_field.field_type),
+            type_name(&map.value_field.field_type)
+        ),
+    }
+}
+
+fn partition_fields(metadata: &TableMetadata) -> Vec<(String, String)> {
+    let schema = metadata.current_schema();
+
+    metadata
+        .default_partition_spec()
+        .fields()
+        .iter()
+        .map(|field| {
+            let source = schema
+                .name_by_field_id(field.source_id)
+                .unwrap_or("<unknown column>");
+            (
+                field.name.clone(),
+                format!("{}({})", field.transform, source),
+            )
+        })
+        .collect()
+}
+
+fn sort_fields(metadata: &TableMetadata) -> Vec<(String, String)> {
+    let schema = metadata.current_schema();
+
+    metadata
+        .default_sort_order()
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let source = schema
+                .name_by_field_id(field.source_id)
+                .unwrap_or("<unknown column>");
+            (
+                format!("#{}", idx + 1),
+                format!(
+                    "{}({}) {} nulls {}",
+                    field.transform, source, field.direction, field.null_order
+                ),
+            )
+        })
+        .collect()
+}
+
+fn properties(metadata: &TableMetadata) -> Vec<(String, String)> {
+    let mut properties = metadata
+        .properties()
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<Vec<_>>();
+    properties.sort();
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixture_metadata;
+    use iceberg::io::FileIOBuilder;
+    use iceberg::spec::{NestedField, PrimitiveType, Schema, TableMetadataBuilder};
+    use iceberg::{TableCreation, TableIdent};
+
+    fn table_ref(name: &str) -> TableRef {
+        TableRef {
+            connection: ConnectionDetails::new_anon("http://localhost:8181".parse().unwrap()),
+            namespace: vec!["sales".to_string()],
+            name: name.to_string(),
+        }
+    }
+
+    fn table(metadata: TableMetadata) -> Table {
+        Table::builder()
+            .identifier(TableIdent::from_strs(["sales", "orders"]).unwrap())
+            .metadata(metadata)
+            .file_io(FileIOBuilder::new("memory").build().unwrap())
+            .readonly(true)
+            .build()
+            .unwrap()
+    }
+
+    fn table_with_columns(columns: Vec<NestedField>) -> Table {
+        let schema = Schema::builder()
+            .with_fields(columns.into_iter().map(Into::into))
+            .build()
+            .unwrap();
+        let creation = TableCreation::builder()
+            .name("orders".to_string())
+            .location("memory://warehouse/sales/orders".to_string())
+            .schema(schema)
+            .build();
+
+        table(
+            TableMetadataBuilder::from_table_creation(creation)
+                .unwrap()
+                .build()
+                .unwrap()
+                .metadata,
+        )
+    }
+
+    fn id(field_type: PrimitiveType) -> NestedField {
+        NestedField::required(1, "id", Type::Primitive(field_type))
+    }
+
+    fn name() -> NestedField {
+        NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String))
+    }
+
+    /// The schema section's entries, as (column, left, right)
+    fn schema_diff(left: &Table, right: &Table) -> Vec<(String, Option<String>, Option<String>)> {
+        let comparison = TableComparison::new(
+            table_ref("left"),
+            Some(left),
+            table_ref("right"),
+            Some(right),
+        );
+
+        comparison
+            .sections
+            .into_iter()
+            .find(|section| section.title == "Schema")
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.left, entry.right))
+            .collect()
+    }
+
+    fn some(value: &str) -> Option<String> {
+        Some(value.to_string())
+    }
+
+    #[test]
+    fn a_table_compared_with_itself_has_no_differences() {
+        let table = table(fixture_metadata(&["sales"], "orders", 3, 2).unwrap());
+
+        let comparison = TableComparison::new(
+            table_ref("orders"),
+            Some(&table),
+            table_ref("orders"),
+            Some(&table),
+        );
+
+        assert!(comparison.left_found && comparison.right_found);
+        assert_eq!(comparison.difference_count(), 0);
+        assert_eq!(schema_diff(&table, &table).len(), 2);
+    }
+
+    #[test]
+    fn added_columns_are_only_on_the_right() {
+        let left = table_with_columns(vec![id(PrimitiveType::Long)]);
+        let right = table_with_columns(vec![id(PrimitiveType::Long), name()]);
+
+        assert_eq!(
+            schema_diff(&left, &right),
+            vec![
+                (
+                    "id".to_string(),
+                    some("long required"),
+                    some("long required")
+                ),
+                ("name".to_string(), None, some("string optional")),
+            ]
+        );
+    }
+
+    #[test]
+    fn removed_columns_are_only_on_the_left() {
+        let left = table_with_columns(vec![id(PrimitiveType::Long), name()]);
+        let right = table_with_columns(vec![id(PrimitiveType::Long)]);
+
+        assert_eq!(
+            schema_diff(&left, &right),
+            vec![
+                (
+                    "id".to_string(),
+                    some("long required"),
+                    some("long required")
+                ),
+                ("name".to_string(), some("string optional"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_columns_differ() {
+        let left = table_with_columns(vec![id(PrimitiveType::Long), name()]);
+        let right = table_with_columns(vec![
+            id(PrimitiveType::Long),
+            NestedField::required(2, "name", Type::Primitive(PrimitiveType::String)),
+        ]);
+
+        assert_eq!(
+            schema_diff(&left, &right)[1],
+            (
+                "name".to_string(),
+                some("string optional"),
+                some("string required")
+            )
+        );
+    }
+
+    #[test]
+    fn type_promoted_columns_differ() {
+        let left = table_with_columns(vec![id(PrimitiveType::Int)]);
+        let right = table_with_columns(vec![id(PrimitiveType::Long)]);
+
+        assert_eq!(
+            schema_diff(&left, &right),
+            vec![(
+                "id".to_string(),
+                some("int required"),
+                some("long required")
+            )]
+        );
+    }
+
+    #[test]
+    fn a_missing_table_is_compared_as_empty() {
+        let right = table_with_columns(vec![id(PrimitiveType::Long)]);
+
+        let comparison =
+            TableComparison::new(table_ref("left"), None, table_ref("right"), Some(&right));
+
+        assert!(!comparison.left_found);
+        assert!(comparison
+            .sections
+            .iter()
+            .flat_map(|section| &section.entries)
+            .all(|entry| entry.left.is_none()));
+    }
+}
@@ -0,0 +1,247 @@
+//! Delete Files
+//!
+//! How much merge-on-read work a table's current snapshot leaves for
+//! readers. Position and equality delete files are tallied per partition
+//! from the snapshot's manifests, alongside the data files that they apply
+//! to, and partitions with enough deletes that compacting them is overdue
+//! are flagged.
+
+use iceberg::spec::{DataContentType, DataFile};
+use iceberg::table::Table;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use tanic_core::Result;
+
+use crate::comparison::TableRef;
+use crate::scan_plan::partition_label;
+
+/// Deleted records, as a fraction of a partition's data records, at which
+/// compacting the partition is overdue
+pub const COMPACTION_DELETE_RATIO: f64 = 0.1;
+
+/// Delete files in a partition at which compacting it is overdue, however
+/// few records they delete. Each one is opened by every read of the
+/// partition.
+pub const COMPACTION_DELETE_FILES: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeleteHealth {
+    pub table: TableRef,
+
+    /// the snapshot inspected. `None` if the table has no snapshots.
+    pub snapshot_id: Option<i64>,
+
+    pub data: FileTotals,
+    pub position_deletes: FileTotals,
+    pub equality_deletes: FileTotals,
+
+    /// partitions with delete files, most deleted first
+    pub partitions: Vec<PartitionDeletes>,
+}
+
+impl DeleteHealth {
+    /// Partitions that would be faster to read once compacted
+    pub fn needing_compaction(&self) -> impl Iterator<Item = &PartitionDeletes> {
+        self.partitions
+            .iter()
+            .filter(|partition| partition.needs_compaction())
+    }
+}
+
+/// Live files of one kind
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTotals {
+    pub files: usize,
+    pub records: u64,
+    pub size_bytes: u64,
+}
+
+impl FileTotals {
+    fn add(&mut self, file: &DataFile) {
+        self.files += 1;
+        self.records += file.record_count();
+        self.size_bytes += file.file_size_in_bytes();
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PartitionDeletes {
+    /// partition values, as `name=value` pairs. Empty for an unpartitioned
+    /// table.
+    pub partition: String,
+
+    pub data: FileTotals,
+    pub position_deletes: FileTotals,
+    pub equality_deletes: FileTotals,
+}
+
+impl PartitionDeletes {
+    pub fn delete_files(&self) -> usize {
+        self.position_deletes.files + self.equality_deletes.files
+    }
+
+    /// Delete records as a fraction of data records. An equality delete can
+    /// match any number of rows, so this is a measure of the work readers
+    /// do rather than of the rows deleted.
+    pub fn delete_ratio(&self) -> Option<f64> {
+        let deletes = self.position_deletes.records + self.equality_deletes.records;
+        (self.data.records > 0).then(|| deletes as f64 / self.data.records as f64)
+    }
+
+    pub fn needs_compaction(&self) -> bool {
+        self.delete_files() >= COMPACTION_DELETE_FILES
+            || self
+                .delete_ratio()
+                .is_some_and(|ratio| ratio >= COMPACTION_DELETE_RATIO)
+            // deletes with no data left to apply to
+            || (self.delete_files() > 0 && self.data.records == 0)
+    }
+}
+
+/// Tally the delete files in a table's current snapshot, reading its
+/// manifests through the table's `FileIO`
+pub async fn delete_health(table: &Table, table_ref: TableRef) -> Result<DeleteHealth> {
+    let metadata = table.metadata();
+    let mut health = DeleteHealth {
+        table: table_ref,
+        snapshot_id: None,
+        data: FileTotals::default(),
+        position_deletes: FileTotals::default(),
+        equality_deletes: FileTotals::default(),
+        partitions: vec![],
+    };
+    let Some(snapshot) = metadata.current_snapshot() else {
+        return Ok(health);
+    };
+    health.snapshot_id = Some(snapshot.snapshot_id());
+
+    let schema = snapshot.schema(metadata)?;
+    let manifest_list = snapshot
+        .load_manifest_list(table.file_io(), metadata)
+        .await?;
+
+    let mut files = vec![];
+    for manifest_file in manifest_list.entries() {
+        let manifest = manifest_file.load_manifest(table.file_io()).await?;
+        let spec = metadata.partition_spec_by_id(manifest_file.partition_spec_id);
+
+        for entry in manifest.entries().iter().filter(|entry| entry.is_alive()) {
+            let data_file = entry.data_file().clone();
+            let partition = spec
+                .map(|spec| partition_label(spec, &schema, data_file.partition()))
+                .unwrap_or_default();
+            files.push((partition, data_file));
+        }
+    }
+
+    tally(&mut health, &files);
+    Ok(health)
+}
+
+/// Add up the files, overall and per partition
+fn tally(health: &mut DeleteHealth, files: &[(String, DataFile)]) {
+    let mut partitions = BTreeMap::<&str, PartitionDeletes>::new();
+
+    for (partition, file) in files {
+        let entry = partitions
+            .entry(partition)
+            .or_insert_with(|| PartitionDeletes {
+                partition: partition.clone(),
+                ..PartitionDeletes::default()
+            });
+
+        let (total, in_partition) = match file.content_type() {
+            DataContentType::Data => (&mut health.data, &mut entry.data),
+            DataContentType::PositionDeletes => {
+                (&mut health.position_deletes, &mut entry.position_deletes)
+            }
+            DataContentType::EqualityDeletes => {
+                (&mut health.equality_deletes, &mut entry.equality_deletes)
+            }
+        };
+        total.add(file);
+        in_partition.add(file);
+    }
+
+    health.partitions = partitions
+        .into_values()
+        .filter(|partition| partition.delete_files() > 0)
+        .collect();
+    health.partitions.sort_by(|a, b| {
+        let ratio = |partition: &PartitionDeletes| partition.delete_ratio().unwrap_or(f64::MAX);
+        ratio(b).total_cmp(&ratio(a))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iceberg::spec::{DataFileBuilder, DataFileFormat, Struct};
+    use tanic_core::config::ConnectionDetails;
+
+    fn file(content: DataContentType, records: u64) -> DataFile {
+        DataFileBuilder::default()
+            .content(content)
+            .file_path("file.parquet".to_string())
+            .file_format(DataFileFormat::Parquet)
+            .partition(Struct::empty())
+            .record_count(records)
+            .file_size_in_bytes(records * 10)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn tallies_deletes_per_partition_and_flags_those_to_compact() {
+        let mut health = DeleteHealth {
+            table: TableRef {
+                connection: ConnectionDetails::new_anon("http://localhost:8181".parse().unwrap()),
+                namespace: vec!["cdc".to_string()],
+                name: "orders".to_string(),
+            },
+            snapshot_id: Some(1),
+            data: FileTotals::default(),
+            position_deletes: FileTotals::default(),
+            equality_deletes: FileTotals::default(),
+            partitions: vec![],
+        };
+        let day = |day: &str, content, records| (format!("day={day}"), file(content, records));
+        let mut files = vec![
+            day("2024-01-01", DataContentType::Data, 1000),
+            day("2024-01-01", DataContentType::PositionDeletes, 50),
+            day("2024-01-02", DataContentType::Data, 1000),
+            day("2024-01-02", DataContentType::PositionDeletes, 100),
+            day("2024-01-02", DataContentType::EqualityDeletes, 100),
+            day("2024-01-03", DataContentType::Data, 1000),
+        ];
+        files.extend(
+            (0..COMPACTION_DELETE_FILES)
+                .map(|_| day("2024-01-04", DataContentType::EqualityDeletes, 1)),
+        );
+        files.push(day("2024-01-04", DataContentType::Data, 10_000));
+
+        tally(&mut health, &files);
+
+        assert_eq!(health.data.files, 4);
+        assert_eq!(health.data.records, 13_000);
+        assert_eq!(health.position_deletes.records, 150);
+        assert_eq!(health.equality_deletes.files, COMPACTION_DELETE_FILES + 1);
+
+        let partitions = health
+            .partitions
+            .iter()
+            .map(|partition| (partition.partition.as_str(), partition.needs_compaction()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            partitions,
+            [
+                ("day=2024-01-02", true),
+                ("day=2024-01-01", false),
+                ("day=2024-01-04", true),
+            ]
+        );
+        assert_eq!(health.partitions[0].delete_ratio(), Some(0.2));
+        assert_eq!(health.needing_compaction().count(), 2);
+    }
+}
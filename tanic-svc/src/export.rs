@@ -0,0 +1,890 @@
+//! Table Export
+//!
+//! Scans a table, or a filtered or limited scan of some of its columns at a
+//! particular snapshot, and streams the resulting Arrow batches to a local
+//! CSV, JSON Lines or Parquet file.
+
+use arrow_array::{Array, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{DataType, SchemaRef};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use futures::{Stream, StreamExt};
+use iceberg::spec::{Schema, TableMetadata};
+use iceberg::table::Table;
+use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use tanic_core::{Result, TanicError};
+
+use crate::comparison::TableRef;
+use crate::filter::RowFilter;
+use crate::requests::RequestId;
+
+/// File formats that tables can be exported to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+
+    /// JSON Lines: one JSON object per row
+    Ndjson,
+
+    Parquet,
+}
+
+impl ExportFormat {
+    /// The format that a file's extension suggests, if any
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "ndjson" | "jsonl" | "json" => Some(ExportFormat::Ndjson),
+            "parquet" | "pq" => Some(ExportFormat::Parquet),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
+            "parquet" => Ok(ExportFormat::Parquet),
+            other => Err(format!(
+                "unknown export format '{other}', expected one of: csv, ndjson, parquet"
+            )),
+        }
+    }
+}
+
+/// The snapshot of a table to read from
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotSelector {
+    Id(i64),
+
+    /// a branch or tag
+    Ref(String),
+
+    /// the snapshot that was current at a time, in milliseconds since the epoch
+    AsOf(i64),
+}
+
+/// Formats that timestamps can be given in, besides RFC 3339. Times are UTC.
+const TIMESTAMP_FORMATS: [&str; 3] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"];
+
+impl SnapshotSelector {
+    /// The ID of the selected snapshot
+    pub fn resolve(&self, table: &Table) -> Result<i64> {
+        self.resolve_in(table.metadata())
+    }
+
+    fn resolve_in(&self, metadata: &TableMetadata) -> Result<i64> {
+        let snapshot_id = match self {
+            SnapshotSelector::Id(id) => metadata.snapshot_by_id(*id).map(|s| s.snapshot_id()),
+            SnapshotSelector::Ref(name) => metadata.snapshot_for_ref(name).map(|s| s.snapshot_id()),
+            SnapshotSelector::AsOf(timestamp_ms) => {
+                // the snapshot log records which snapshot was current when;
+                // tables without one fall back to when snapshots were made
+                let logged = metadata
+                    .history()
+                    .iter()
+                    .filter(|entry| entry.timestamp_ms <= *timestamp_ms)
+                    .max_by_key(|entry| entry.timestamp_ms)
+                    .map(|entry| entry.snapshot_id);
+
+                logged.or_else(|| {
+                    metadata
+                        .snapshots()
+                        .filter(|snapshot| snapshot.timestamp_ms() <= *timestamp_ms)
+                        .max_by_key(|snapshot| snapshot.timestamp_ms())
+                        .map(|snapshot| snapshot.snapshot_id())
+                })
+            }
+        };
+
+        snapshot_id.ok_or_else(|| TanicError::UnexpectedError(format!("no such snapshot: {self}")))
+    }
+}
+
+impl FromStr for SnapshotSelector {
+    type Err = String;
+
+    /// A snapshot ID if `s` is numeric, a point in time if it is a date or
+    /// timestamp, otherwise a branch or tag name
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("expected a snapshot ID, branch, tag or timestamp".to_string());
+        }
+
+        if let Ok(id) = s.parse() {
+            return Ok(SnapshotSelector::Id(id));
+        }
+
+        if let Some(timestamp) = parse_timestamp(s) {
+            return Ok(SnapshotSelector::AsOf(
+                timestamp.and_utc().timestamp_millis(),
+            ));
+        }
+
+        Ok(SnapshotSelector::Ref(s.to_string()))
+    }
+}
+
+/// Parse an RFC 3339 timestamp, a date, or a date and time, as UTC. A
+/// trailing "UTC" is allowed, so that displayed times can be entered again.
+pub(crate) fn parse_timestamp(s: &str) -> Option<NaiveDateTime> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(s) {
+        return Some(timestamp.naive_utc());
+    }
+    let s = s.strip_suffix(" UTC").unwrap_or(s);
+
+    TIMESTAMP_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+impl Display for SnapshotSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotSelector::Id(id) => write!(f, "{id}"),
+            SnapshotSelector::Ref(name) => write!(f, "{name}"),
+            SnapshotSelector::AsOf(timestamp_ms) => {
+                match DateTime::from_timestamp_millis(*timestamp_ms) {
+                    Some(timestamp) => write!(f, "{}", timestamp.format("%F %T UTC")),
+                    None => write!(f, "{timestamp_ms}"),
+                }
+            }
+        }
+    }
+}
+
+/// What to export from a table, and how
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+
+    /// columns to export. All columns are exported if empty.
+    pub columns: Vec<String>,
+
+    /// only rows matching this are exported
+    pub filter: Option<RowFilter>,
+
+    /// maximum number of rows to export
+    pub limit: Option<u64>,
+
+    /// snapshot to read from, rather than the current one
+    pub snapshot: Option<SnapshotSelector>,
+}
+
+/// A table to export, and where to
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportRequest {
+    pub table: TableRef,
+    pub path: PathBuf,
+    pub options: ExportOptions,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportStatus {
+    Running,
+    Finished,
+    Failed(String),
+}
+
+/// An export that has been started, as published to the UI
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportJob {
+    pub id: RequestId,
+    pub request: ExportRequest,
+    pub rows_written: u64,
+
+    /// how many rows the export should write, if known
+    pub rows_expected: Option<u64>,
+
+    pub status: ExportStatus,
+}
+
+impl ExportJob {
+    /// How far through the export is, from 0 to 1, if that is known
+    pub fn ratio(&self) -> Option<f64> {
+        match (&self.status, self.rows_expected) {
+            (ExportStatus::Finished, _) => Some(1.0),
+            (_, Some(0)) => None,
+            (_, Some(expected)) => Some((self.rows_written as f64 / expected as f64).min(1.0)),
+            (_, None) => None,
+        }
+    }
+}
+
+/// How many rows an export of `table` will write, according to the
+/// snapshot's summary. That isn't known for a filtered export.
+pub fn expected_rows(table: &Table, options: &ExportOptions) -> Option<u64> {
+    if options.filter.is_some() {
+        return None;
+    }
+
+    let metadata = table.metadata();
+    let snapshot = match &options.snapshot {
+        Some(selector) => metadata.snapshot_by_id(selector.resolve(table).ok()?),
+        None => metadata.current_snapshot(),
+    };
+
+    let total = snapshot?
+        .summary()
+        .additional_properties
+        .get("total-records")?
+        .parse::<u64>()
+        .ok()?;
+
+    Some(options.limit.map_or(total, |limit| limit.min(total)))
+}
+
+/// Export a table to a file, calling `on_progress` with the number of rows
+/// written so far after each batch. Returns the number of rows written.
+pub async fn export_table(
+    table: &Table,
+    path: &Path,
+    options: &ExportOptions,
+    on_progress: impl FnMut(u64),
+) -> Result<u64> {
+    let mut scan = table.scan();
+    scan = if options.columns.is_empty() {
+        scan.select_all()
+    } else {
+        scan.select(options.columns.iter())
+    };
+    let snapshot_id = match &options.snapshot {
+        Some(selector) => Some(selector.resolve(table)?),
+        None => None,
+    };
+    if let Some(snapshot_id) = snapshot_id {
+        scan = scan.snapshot_id(snapshot_id);
+    }
+
+    // the columns are those of the snapshot being read
+    let metadata = table.metadata();
+    let schema = match snapshot_id.and_then(|id| metadata.snapshot_by_id(id)) {
+        Some(snapshot) => snapshot.schema(metadata)?,
+        None => metadata.current_schema().clone(),
+    };
+    if let Some(filter) = &options.filter {
+        scan = scan.with_filter(filter.to_predicate(&schema)?);
+    }
+
+    // a table without snapshots has no rows to scan, but still has columns
+    let batches = if snapshot_id.is_none() && metadata.current_snapshot().is_none() {
+        futures::stream::empty().boxed()
+    } else {
+        scan.build()?.to_arrow().await?
+    };
+
+    write_batches(
+        batches,
+        export_schema(&schema, &options.columns)?,
+        path,
+        options.format,
+        options.limit,
+        on_progress,
+    )
+    .await
+}
+
+/// The Arrow schema of the columns that an export selects, for a file with
+/// no rows to take it from. Nested columns are left out.
+fn export_schema(schema: &Schema, columns: &[String]) -> Result<SchemaRef> {
+    let arrow_schema = iceberg::arrow::schema_to_arrow_schema(schema)?;
+    if columns.is_empty() {
+        return Ok(Arc::new(arrow_schema));
+    }
+
+    let indices = columns
+        .iter()
+        .filter_map(|column| arrow_schema.index_of(column).ok())
+        .collect::<Vec<_>>();
+    let projected = arrow_schema
+        .project(&indices)
+        .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+
+    Ok(Arc::new(projected))
+}
+
+/// Write batches to a file until they run out or `limit` rows have been
+/// written. A file that isn't finished, because of an error or because the
+/// export was cancelled, is removed, leaving any file that was already at
+/// `path` as it was. If there are no batches, the file still gets the
+/// header or schema of `schema`'s columns.
+pub(crate) async fn write_batches(
+    mut batches: impl Stream<Item = iceberg::Result<RecordBatch>> + Unpin,
+    schema: SchemaRef,
+    path: &Path,
+    format: ExportFormat,
+    limit: Option<u64>,
+    mut on_progress: impl FnMut(u64),
+) -> Result<u64> {
+    let path = path.to_path_buf();
+    let mut output = blocking(move || PartialFile::create(&path)).await?;
+    let mut writer: Option<BatchWriter> = None;
+    let mut rows_written = 0;
+
+    while let Some(batch) = batches.next().await {
+        let mut batch = batch?;
+
+        if let Some(limit) = limit {
+            let remaining = limit.saturating_sub(rows_written);
+            if remaining == 0 {
+                break;
+            }
+            if (batch.num_rows() as u64) > remaining {
+                batch = batch.slice(0, remaining as usize);
+            }
+        }
+
+        let mut batch_writer = match writer.take() {
+            Some(writer) => writer,
+            None => BatchWriter::create(&mut output, format, batch.schema()).await?,
+        };
+        let num_rows = batch.num_rows() as u64;
+        writer = Some(blocking(move || batch_writer.write(&batch).map(|()| batch_writer)).await?);
+
+        rows_written += num_rows;
+        on_progress(rows_written);
+
+        if limit.is_some_and(|limit| rows_written >= limit) {
+            break;
+        }
+    }
+
+    let writer = match writer {
+        Some(writer) => writer,
+        None => BatchWriter::create(&mut output, format, schema).await?,
+    };
+    blocking(move || {
+        writer.finish()?;
+        output.keep()
+    })
+    .await?;
+
+    Ok(rows_written)
+}
+
+/// Run blocking file writes on a thread of their own, so that they don't
+/// hold up the async runtime
+async fn blocking<T: Send + 'static>(
+    write: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(write)
+        .await
+        .map_err(|err| TanicError::UnexpectedError(err.to_string()))?
+}
+
+/// A file being exported to. It is written next to its destination and
+/// only moved there once it is kept, so that a file that is already there
+/// isn't lost if the export fails, and it is removed if dropped before then.
+struct PartialFile {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    file: Option<File>,
+    keep: bool,
+}
+
+impl PartialFile {
+    fn create(path: &Path) -> Result<Self> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Some(File::create(&tmp_path)?),
+            tmp_path,
+            keep: false,
+        })
+    }
+
+    fn take_file(&mut self) -> Result<File> {
+        self.file
+            .take()
+            .ok_or_else(|| TanicError::UnexpectedError("export file already taken".to_string()))
+    }
+
+    /// Move the finished file to its destination
+    fn keep(mut self) -> Result<()> {
+        // the file must be closed before it can be moved on some platforms
+        self.file = None;
+        std::fs::rename(&self.tmp_path, &self.path)?;
+        self.keep = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for PartialFile {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_file(&self.tmp_path);
+        }
+    }
+}
+
+enum BatchWriter {
+    Csv(Box<csv::Writer<BufWriter<File>>>),
+    Ndjson(BufWriter<File>),
+    Parquet(Box<ArrowWriter<File>>),
+}
+
+impl BatchWriter {
+    /// A writer to an export's file, for batches with the given schema
+    async fn create(
+        output: &mut PartialFile,
+        format: ExportFormat,
+        schema: SchemaRef,
+    ) -> Result<Self> {
+        let file = output.take_file()?;
+        blocking(move || Self::new(file, format, schema)).await
+    }
+
+    fn new(file: File, format: ExportFormat, schema: SchemaRef) -> Result<Self> {
+        Ok(match format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(BufWriter::new(file));
+                writer
+                    .write_record(schema.fields().iter().map(|f| f.name()))
+                    .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+                BatchWriter::Csv(Box::new(writer))
+            }
+            ExportFormat::Ndjson => BatchWriter::Ndjson(BufWriter::new(file)),
+            ExportFormat::Parquet => BatchWriter::Parquet(Box::new(
+                ArrowWriter::try_new(file, schema, None)
+                    .map_err(|err| TanicError::UnexpectedError(err.to_string()))?,
+            )),
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        match self {
+            BatchWriter::Csv(writer) => {
+                let columns = Columns::new(batch)?;
+                for row in 0..batch.num_rows() {
+                    writer
+                        .write_record(columns.texts(row))
+                        .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+                }
+            }
+            BatchWriter::Ndjson(writer) => {
+                let columns = Columns::new(batch)?;
+                for row in 0..batch.num_rows() {
+                    serde_json::to_writer(&mut *writer, &JsonRow(columns.json_values(row)))
+                        .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+            BatchWriter::Parquet(writer) => writer
+                .write(batch)
+                .map_err(|err| TanicError::UnexpectedError(err.to_string()))?,
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            BatchWriter::Csv(mut writer) => writer.flush()?,
+            BatchWriter::Ndjson(mut writer) => writer.flush()?,
+            BatchWriter::Parquet(writer) => {
+                writer
+                    .close()
+                    .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A batch's columns, formatted for text-based exports
+struct Columns<'a> {
+    names: Vec<String>,
+    arrays: Vec<&'a dyn Array>,
+    formatters: Vec<ArrayFormatter<'a>>,
+}
+
+impl<'a> Columns<'a> {
+    fn new(batch: &'a RecordBatch) -> Result<Self> {
+        let arrays = batch
+            .columns()
+            .iter()
+            .map(|array| array.as_ref())
+            .collect::<Vec<_>>();
+
+        let formatters = arrays
+            .iter()
+            .map(|array| ArrayFormatter::try_new(*array, &FormatOptions::default()))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+
+        Ok(Self {
+            names: batch
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect(),
+            arrays,
+            formatters,
+        })
+    }
+
+    /// A row's values as text, with nulls left empty
+    fn texts(&self, row: usize) -> Vec<String> {
+        self.formatters
+            .iter()
+            .map(|formatter| formatter.value(row).to_string())
+            .collect()
+    }
+
+    /// A row's values as JSON, by column name. Numbers and booleans stay as
+    /// they are, and everything else is formatted as a string. Decimals are
+    /// strings too, since JSON readers would parse them as floats and lose
+    /// digits.
+    fn json_values(&self, row: usize) -> Vec<(&str, serde_json::Value)> {
+        self.names
+            .iter()
+            .zip(self.arrays.iter().zip(&self.formatters))
+            .map(|(name, (array, formatter))| {
+                let value = if array.is_null(row) {
+                    serde_json::Value::Null
+                } else {
+                    let text = formatter.value(row).to_string();
+                    let data_type = array.data_type();
+                    let decimal = matches!(
+                        data_type,
+                        DataType::Decimal128(..) | DataType::Decimal256(..)
+                    );
+                    if (data_type.is_numeric() && !decimal) || *data_type == DataType::Boolean {
+                        serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+                    } else {
+                        serde_json::Value::String(text)
+                    }
+                };
+
+                (name.as_str(), value)
+            })
+            .collect()
+    }
+}
+
+/// A row as a JSON object, keeping its columns in order
+struct JsonRow<'a>(Vec<(&'a str, serde_json::Value)>);
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(name, value)| (name, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{fixture_metadata, FIXTURE_TIMESTAMP_MS};
+    use crate::MS_PER_DAY;
+    use arrow_array::{BooleanArray, Decimal128Array, Int64Array, StringArray};
+    use arrow_schema::{Field, Schema};
+    use iceberg::io::FileIOBuilder;
+    use iceberg::TableIdent;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn batch(ids: &[i64]) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("active", DataType::Boolean, true),
+        ]);
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(ids.to_vec())),
+                Arc::new(StringArray::from(
+                    ids.iter()
+                        .map(|id| (id % 2 == 0).then(|| format!("name {id}")))
+                        .collect::<Vec<_>>(),
+                )),
+                Arc::new(BooleanArray::from(
+                    ids.iter().map(|id| Some(id % 3 == 0)).collect::<Vec<_>>(),
+                )),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn batches(
+        batches: Vec<RecordBatch>,
+    ) -> impl Stream<Item = iceberg::Result<RecordBatch>> + Unpin {
+        futures::stream::iter(batches.into_iter().map(Ok))
+    }
+
+    async fn export(
+        format: ExportFormat,
+        limit: Option<u64>,
+    ) -> (tempfile::TempDir, PathBuf, u64, Vec<u64>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export");
+        let mut progress = vec![];
+
+        let rows = write_batches(
+            batches(vec![batch(&[1, 2, 3]), batch(&[4, 5, 6])]),
+            batch(&[]).schema(),
+            &path,
+            format,
+            limit,
+            |rows| progress.push(rows),
+        )
+        .await
+        .unwrap();
+
+        (dir, path, rows, progress)
+    }
+
+    #[tokio::test]
+    async fn exports_csv() {
+        let (_dir, path, rows, progress) = export(ExportFormat::Csv, None).await;
+
+        assert_eq!(rows, 6);
+        assert_eq!(progress, vec![3, 6]);
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "id,name,active\n\
+             1,,false\n\
+             2,name 2,false\n\
+             3,,true\n\
+             4,name 4,false\n\
+             5,,false\n\
+             6,name 6,true\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn exports_json_lines() {
+        let (_dir, path, rows, _) = export(ExportFormat::Ndjson, Some(2)).await;
+
+        assert_eq!(rows, 2);
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "{\"id\":1,\"name\":null,\"active\":false}\n\
+             {\"id\":2,\"name\":\"name 2\",\"active\":false}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn exports_decimals_as_strings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.ndjson");
+
+        let amounts = Decimal128Array::from(vec![12_345_678_901_234_567_890_123_456_i128])
+            .with_precision_and_scale(38, 6)
+            .unwrap();
+        let schema = Schema::new(vec![Field::new(
+            "amount",
+            amounts.data_type().clone(),
+            false,
+        )]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(amounts)]).unwrap();
+
+        let schema = batch.schema();
+        write_batches(
+            batches(vec![batch]),
+            schema,
+            &path,
+            ExportFormat::Ndjson,
+            None,
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "{\"amount\":\"12345678901234567890.123456\"}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn exports_parquet() {
+        let (_dir, path, rows, _) = export(ExportFormat::Parquet, None).await;
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let read_rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+
+        assert_eq!(rows, 6);
+        assert_eq!(read_rows, 6);
+    }
+
+    #[tokio::test]
+    async fn exports_the_columns_of_an_empty_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = Table::builder()
+            .identifier(TableIdent::from_strs(["sales", "orders"]).unwrap())
+            .metadata(fixture_metadata(&["sales"], "orders", 0, 0).unwrap())
+            .file_io(FileIOBuilder::new("memory").build().unwrap())
+            .readonly(true)
+            .build()
+            .unwrap();
+        let export = |format| ExportOptions {
+            format,
+            columns: vec![],
+            filter: None,
+            limit: None,
+            snapshot: None,
+        };
+
+        let csv = dir.path().join("orders.csv");
+        let rows = export_table(&table, &csv, &export(ExportFormat::Csv), |_| {})
+            .await
+            .unwrap();
+        assert_eq!(rows, 0);
+        assert_eq!(std::fs::read_to_string(csv).unwrap(), "id,name\n");
+
+        let parquet = dir.path().join("orders.parquet");
+        export_table(&table, &parquet, &export(ExportFormat::Parquet), |_| {})
+            .await
+            .unwrap();
+        let reader =
+            ParquetRecordBatchReaderBuilder::try_new(File::open(parquet).unwrap()).unwrap();
+        let columns = reader
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["id", "name"]);
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 0);
+    }
+
+    #[tokio::test]
+    async fn limit_stops_part_way_through_a_batch() {
+        let (_dir, _, rows, progress) = export(ExportFormat::Csv, Some(4)).await;
+
+        assert_eq!(rows, 4);
+        assert_eq!(progress, vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn removes_the_file_when_a_scan_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+
+        let failing = futures::stream::iter(vec![
+            Ok(batch(&[1])),
+            Err(iceberg::Error::new(
+                iceberg::ErrorKind::Unexpected,
+                "scan failed",
+            )),
+        ]);
+        let result = write_batches(
+            failing,
+            batch(&[]).schema(),
+            &path,
+            ExportFormat::Csv,
+            None,
+            |_| {},
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn leaves_an_existing_file_alone_when_a_scan_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.csv");
+        std::fs::write(&path, "precious").unwrap();
+
+        let failing = futures::stream::iter(vec![Err(iceberg::Error::new(
+            iceberg::ErrorKind::Unexpected,
+            "scan failed",
+        ))]);
+        let result = write_batches(
+            failing,
+            batch(&[]).schema(),
+            &path,
+            ExportFormat::Csv,
+            None,
+            |_| {},
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "precious");
+    }
+
+    #[test]
+    fn infers_format_from_extension() {
+        let format = |path: &str| ExportFormat::from_path(Path::new(path));
+
+        assert_eq!(format("orders.CSV"), Some(ExportFormat::Csv));
+        assert_eq!(format("orders.jsonl"), Some(ExportFormat::Ndjson));
+        assert_eq!(format("out/orders.parquet"), Some(ExportFormat::Parquet));
+        assert_eq!(format("orders"), None);
+        assert_eq!(format("orders.xlsx"), None);
+    }
+
+    #[test]
+    fn parses_snapshot_selectors() {
+        assert_eq!("42".parse(), Ok(SnapshotSelector::Id(42)));
+        assert_eq!(
+            "main".parse(),
+            Ok(SnapshotSelector::Ref("main".to_string()))
+        );
+        assert!(" ".parse::<SnapshotSelector>().is_err());
+    }
+
+    #[test]
+    fn parses_points_in_time() {
+        let noon = SnapshotSelector::AsOf(1_735_732_800_000);
+
+        assert_eq!("2025-01-01T12:00:00Z".parse(), Ok(noon.clone()));
+        assert_eq!("2025-01-01T13:00:00+01:00".parse(), Ok(noon.clone()));
+        assert_eq!("2025-01-01 12:00:00".parse(), Ok(noon.clone()));
+        assert_eq!("2025-01-01 12:00".parse(), Ok(noon.clone()));
+        assert_eq!(
+            "2025-01-01".parse(),
+            Ok(SnapshotSelector::AsOf(1_735_689_600_000))
+        );
+        assert_eq!(noon.to_string(), "2025-01-01 12:00:00 UTC");
+        assert_eq!(noon.to_string().parse(), Ok(noon));
+    }
+
+    #[test]
+    fn resolves_the_snapshot_current_at_a_time() {
+        // snapshots a day apart, the last taken at `FIXTURE_TIMESTAMP_MS`
+        let metadata = fixture_metadata(&["sales"], "orders", 0, 3).unwrap();
+        let days_ago = |days| FIXTURE_TIMESTAMP_MS - days * MS_PER_DAY;
+        let as_of = |timestamp_ms| SnapshotSelector::AsOf(timestamp_ms).resolve_in(&metadata);
+
+        assert_eq!(as_of(days_ago(1)).unwrap(), 2);
+        assert_eq!(as_of(FIXTURE_TIMESTAMP_MS - 1).unwrap(), 2);
+        assert_eq!(as_of(days_ago(-1)).unwrap(), 3);
+        assert!(as_of(days_ago(2) - 1).is_err());
+    }
+}
@@ -0,0 +1,636 @@
+//! Row Filters
+//!
+//! A small predicate language for narrowing down the rows of a scan, such as
+//! `country = 'GB' AND ts > '2024-01-01'`. Filters are parsed where they are
+//! entered, so that mistakes can be pointed out there, and converted to an
+//! Iceberg [`Predicate`] against the schema of the snapshot being read.
+//! Literals take the type of the column they are compared with, which lets
+//! the scan prune partitions and files using the table's metadata.
+
+use iceberg::expr::{Predicate, Reference};
+use iceberg::spec::{Datum, PrimitiveType, Schema, Type};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use tanic_core::{Result, TanicError};
+
+use crate::export::parse_timestamp;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RowFilter {
+    Compare {
+        column: String,
+        op: CompareOp,
+        value: String,
+    },
+    In {
+        column: String,
+        values: Vec<String>,
+        negated: bool,
+    },
+    IsNull {
+        column: String,
+        negated: bool,
+    },
+    And(Box<RowFilter>, Box<RowFilter>),
+    Or(Box<RowFilter>, Box<RowFilter>),
+    Not(Box<RowFilter>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl CompareOp {
+    /// The comparison that holds wherever this one doesn't
+    fn negate(self) -> Self {
+        match self {
+            CompareOp::Eq => CompareOp::NotEq,
+            CompareOp::NotEq => CompareOp::Eq,
+            CompareOp::Lt => CompareOp::GtEq,
+            CompareOp::LtEq => CompareOp::Gt,
+            CompareOp::Gt => CompareOp::LtEq,
+            CompareOp::GtEq => CompareOp::Lt,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "=",
+            CompareOp::NotEq => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::LtEq => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::GtEq => ">=",
+        }
+    }
+}
+
+impl RowFilter {
+    /// The filter as an Iceberg predicate on the columns of `schema`
+    pub fn to_predicate(&self, schema: &Schema) -> Result<Predicate> {
+        let predicate = match self {
+            RowFilter::Compare { column, op, value } => {
+                let datum = datum(column, &column_type(schema, column)?, value)?;
+                let reference = Reference::new(column);
+                match op {
+                    CompareOp::Eq => reference.equal_to(datum),
+                    CompareOp::NotEq => reference.not_equal_to(datum),
+                    CompareOp::Lt => reference.less_than(datum),
+                    CompareOp::LtEq => reference.less_than_or_equal_to(datum),
+                    CompareOp::Gt => reference.greater_than(datum),
+                    CompareOp::GtEq => reference.greater_than_or_equal_to(datum),
+                }
+            }
+            RowFilter::In {
+                column,
+                values,
+                negated,
+            } => {
+                let column_type = column_type(schema, column)?;
+                let datums = values
+                    .iter()
+                    .map(|value| datum(column, &column_type, value))
+                    .collect::<Result<Vec<_>>>()?;
+                let reference = Reference::new(column);
+                if *negated {
+                    reference.is_not_in(datums)
+                } else {
+                    reference.is_in(datums)
+                }
+            }
+            RowFilter::IsNull { column, negated } => {
+                column_type(schema, column)?;
+                let reference = Reference::new(column);
+                if *negated {
+                    reference.is_not_null()
+                } else {
+                    reference.is_null()
+                }
+            }
+            RowFilter::And(left, right) => {
+                left.to_predicate(schema)?.and(right.to_predicate(schema)?)
+            }
+            RowFilter::Or(left, right) => {
+                left.to_predicate(schema)?.or(right.to_predicate(schema)?)
+            }
+            RowFilter::Not(inner) => inner.to_predicate(schema)?.negate(),
+        };
+
+        Ok(predicate)
+    }
+
+    /// An equivalent filter without any `NOT`s, which are pushed down into
+    /// the conditions that they apply to
+    pub(crate) fn rewrite_not(self) -> RowFilter {
+        match self {
+            RowFilter::Not(inner) => inner.negate(),
+            RowFilter::And(left, right) => {
+                RowFilter::And(Box::new(left.rewrite_not()), Box::new(right.rewrite_not()))
+            }
+            RowFilter::Or(left, right) => {
+                RowFilter::Or(Box::new(left.rewrite_not()), Box::new(right.rewrite_not()))
+            }
+            condition => condition,
+        }
+    }
+
+    /// The filter matching the rows that this one doesn't, without any `NOT`s
+    fn negate(self) -> RowFilter {
+        match self {
+            RowFilter::Compare { column, op, value } => RowFilter::Compare {
+                column,
+                op: op.negate(),
+                value,
+            },
+            RowFilter::In {
+                column,
+                values,
+                negated,
+            } => RowFilter::In {
+                column,
+                values,
+                negated: !negated,
+            },
+            RowFilter::IsNull { column, negated } => RowFilter::IsNull {
+                column,
+                negated: !negated,
+            },
+            RowFilter::And(left, right) => {
+                RowFilter::Or(Box::new(left.negate()), Box::new(right.negate()))
+            }
+            RowFilter::Or(left, right) => {
+                RowFilter::And(Box::new(left.negate()), Box::new(right.negate()))
+            }
+            RowFilter::Not(inner) => inner.rewrite_not(),
+        }
+    }
+
+    /// Whether this filter needs parentheses to be combined with others
+    fn is_compound(&self) -> bool {
+        matches!(self, RowFilter::And(..) | RowFilter::Or(..))
+    }
+}
+
+pub(crate) fn column_type(schema: &Schema, column: &str) -> Result<PrimitiveType> {
+    let field = schema
+        .field_by_name(column)
+        .ok_or_else(|| TanicError::UnexpectedError(format!("there is no column '{column}'")))?;
+
+    match field.field_type.as_ref() {
+        Type::Primitive(primitive) => Ok(primitive.clone()),
+        other => Err(TanicError::UnexpectedError(format!(
+            "{column} is a {other}, and only primitive columns can be filtered on"
+        ))),
+    }
+}
+
+/// Convert `value` to a literal of the column's type
+pub(crate) fn datum(column: &str, column_type: &PrimitiveType, value: &str) -> Result<Datum> {
+    let datum = match column_type {
+        PrimitiveType::Boolean => value.parse::<bool>().ok().map(Datum::bool),
+        PrimitiveType::Int => value.parse::<i32>().ok().map(Datum::int),
+        PrimitiveType::Long => value.parse::<i64>().ok().map(Datum::long),
+        PrimitiveType::Float => value.parse::<f32>().ok().map(Datum::float),
+        PrimitiveType::Double => value.parse::<f64>().ok().map(Datum::double),
+        PrimitiveType::Decimal { .. } => Datum::decimal_from_str(value).ok(),
+        PrimitiveType::Date => Datum::date_from_str(value).ok(),
+        PrimitiveType::Time => Datum::time_from_str(value).ok(),
+        PrimitiveType::Timestamp => parse_timestamp(value).map(Datum::timestamp_from_datetime),
+        PrimitiveType::Timestamptz => {
+            parse_timestamp(value).map(|ts| Datum::timestamptz_from_datetime(ts.and_utc()))
+        }
+        PrimitiveType::TimestampNs => parse_timestamp(value)
+            .and_then(|ts| ts.and_utc().timestamp_nanos_opt())
+            .map(Datum::timestamp_nanos),
+        PrimitiveType::TimestamptzNs => parse_timestamp(value)
+            .and_then(|ts| ts.and_utc().timestamp_nanos_opt())
+            .map(Datum::timestamptz_nanos),
+        PrimitiveType::String => Some(Datum::string(value)),
+        PrimitiveType::Uuid => Datum::uuid_from_str(value).ok(),
+        PrimitiveType::Fixed(_) | PrimitiveType::Binary => {
+            return Err(TanicError::UnexpectedError(format!(
+                "{column} is {column_type}, which can't be filtered on"
+            )));
+        }
+    };
+
+    datum.ok_or_else(|| {
+        TanicError::UnexpectedError(format!(
+            "'{value}' is not a valid {column_type} to compare {column} with"
+        ))
+    })
+}
+
+impl FromStr for RowFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+        };
+        if parser.tokens.is_empty() {
+            return Err("expected a filter, such as country = 'GB'".to_string());
+        }
+
+        let filter = parser.or()?;
+        match parser.next() {
+            None => Ok(filter),
+            Some(token) => Err(format!("unexpected {token}")),
+        }
+    }
+}
+
+impl Display for RowFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowFilter::Compare { column, op, value } => {
+                write!(f, "{column} {} {}", op.symbol(), quote(value))
+            }
+            RowFilter::In {
+                column,
+                values,
+                negated,
+            } => {
+                let not = if *negated { "NOT " } else { "" };
+                let values = values.iter().map(|v| quote(v)).collect::<Vec<_>>();
+                write!(f, "{column} {not}IN ({})", values.join(", "))
+            }
+            RowFilter::IsNull { column, negated } => {
+                let not = if *negated { "NOT " } else { "" };
+                write!(f, "{column} IS {not}NULL")
+            }
+            RowFilter::And(left, right) => {
+                // AND binds tighter than OR, so ORs within it need bracketing
+                let side = |filter: &RowFilter| match filter {
+                    RowFilter::Or(..) => format!("({filter})"),
+                    _ => filter.to_string(),
+                };
+                write!(f, "{} AND {}", side(left), side(right))
+            }
+            RowFilter::Or(left, right) => write!(f, "{left} OR {right}"),
+            RowFilter::Not(inner) if inner.is_compound() => write!(f, "NOT ({inner})"),
+            RowFilter::Not(inner) => write!(f, "NOT {inner}"),
+        }
+    }
+}
+
+/// Numbers and booleans are shown bare, anything else in single quotes
+fn quote(value: &str) -> String {
+    if value.parse::<f64>().is_ok() || value == "true" || value == "false" {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// a column name, keyword or unquoted value
+    Word(String),
+    /// a single-quoted value
+    Text(String),
+    Op(CompareOp),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "'{word}'"),
+            Token::Text(text) => write!(f, "'{text}'"),
+            Token::Op(op) => write!(f, "'{}'", op.symbol()),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '=' => Token::Op(CompareOp::Eq),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Op(CompareOp::NotEq),
+            '<' if chars.next_if_eq(&'>').is_some() => Token::Op(CompareOp::NotEq),
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Op(CompareOp::LtEq),
+            '<' => Token::Op(CompareOp::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Op(CompareOp::GtEq),
+            '>' => Token::Op(CompareOp::Gt),
+            '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        // a doubled quote is a quote within the text
+                        Some('\'') if chars.next_if_eq(&'\'').is_some() => text.push('\''),
+                        Some('\'') => break,
+                        Some(c) => text.push(c),
+                        None => return Err(format!("'{text} is missing its closing quote")),
+                    }
+                }
+                Token::Text(text)
+            }
+            '"' => {
+                // a quoted column name
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("\"{name} is missing its closing quote")),
+                    }
+                }
+                Token::Word(name)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| is_word_char(*c)) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+            other => return Err(format!("unexpected '{other}'")),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '+' | ':')
+}
+
+/// Recursive descent over the tokens, with `NOT` binding tightest and `OR`
+/// loosest
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Consume the next token if it is the keyword `keyword`
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, expected: Token, after: &str) -> std::result::Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(format!("expected {expected} after {after}")),
+        }
+    }
+
+    fn or(&mut self) -> std::result::Result<RowFilter, String> {
+        let mut filter = self.and()?;
+        while self.keyword("OR") {
+            filter = RowFilter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    fn and(&mut self) -> std::result::Result<RowFilter, String> {
+        let mut filter = self.unary()?;
+        while self.keyword("AND") {
+            filter = RowFilter::And(Box::new(filter), Box::new(self.unary()?));
+        }
+        Ok(filter)
+    }
+
+    fn unary(&mut self) -> std::result::Result<RowFilter, String> {
+        if self.keyword("NOT") {
+            return Ok(RowFilter::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let filter = self.or()?;
+            self.expect(Token::RParen, &filter.to_string())?;
+            return Ok(filter);
+        }
+        self.condition()
+    }
+
+    fn condition(&mut self) -> std::result::Result<RowFilter, String> {
+        let column = match self.next() {
+            Some(Token::Word(word)) if !is_keyword(&word) => word,
+            Some(token) => return Err(format!("expected a column name, not {token}")),
+            None => return Err("expected a column name".to_string()),
+        };
+
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            if !self.keyword("NULL") {
+                return Err(format!("expected NULL after {column} IS"));
+            }
+            return Ok(RowFilter::IsNull { column, negated });
+        }
+
+        let negated = self.keyword("NOT");
+        if self.keyword("IN") {
+            let after = format!("{column} IN");
+            self.expect(Token::LParen, &after)?;
+            let mut values = vec![self.value(&after)?];
+            while self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+                values.push(self.value(&after)?);
+            }
+            self.expect(Token::RParen, &after)?;
+            return Ok(RowFilter::In {
+                column,
+                values,
+                negated,
+            });
+        }
+        if negated {
+            return Err(format!("expected IN after {column} NOT"));
+        }
+
+        match self.next() {
+            Some(Token::Op(op)) => {
+                let value = self.value(&format!("{column} {}", op.symbol()))?;
+                Ok(RowFilter::Compare { column, op, value })
+            }
+            _ => Err(format!(
+                "expected a comparison such as =, IN or IS NULL after {column}"
+            )),
+        }
+    }
+
+    fn value(&mut self, after: &str) -> std::result::Result<String, String> {
+        match self.next() {
+            Some(Token::Text(text)) => Ok(text),
+            Some(Token::Word(word)) if !is_keyword(&word) => Ok(word),
+            _ => Err(format!("expected a value after {after}")),
+        }
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    ["AND", "OR", "NOT", "IN", "IS", "NULL"]
+        .iter()
+        .any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iceberg::spec::NestedField;
+    use std::sync::Arc;
+
+    fn compare(column: &str, op: CompareOp, value: &str) -> RowFilter {
+        RowFilter::Compare {
+            column: column.to_string(),
+            op,
+            value: value.to_string(),
+        }
+    }
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                Arc::new(NestedField::required(
+                    1,
+                    "id",
+                    Type::Primitive(PrimitiveType::Long),
+                )),
+                Arc::new(NestedField::optional(
+                    2,
+                    "country",
+                    Type::Primitive(PrimitiveType::String),
+                )),
+                Arc::new(NestedField::optional(
+                    3,
+                    "ts",
+                    Type::Primitive(PrimitiveType::Timestamptz),
+                )),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_conditions_with_and_binding_tighter_than_or() {
+        let filter: RowFilter = "country = 'GB' and ts > '2024-01-01' OR NOT id IN (1, 2)"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            filter,
+            RowFilter::Or(
+                Box::new(RowFilter::And(
+                    Box::new(compare("country", CompareOp::Eq, "GB")),
+                    Box::new(compare("ts", CompareOp::Gt, "2024-01-01")),
+                )),
+                Box::new(RowFilter::Not(Box::new(RowFilter::In {
+                    column: "id".to_string(),
+                    values: vec!["1".to_string(), "2".to_string()],
+                    negated: false,
+                }))),
+            )
+        );
+    }
+
+    #[test]
+    fn displays_filters_so_that_they_parse_back() {
+        for text in [
+            "country = 'GB' AND ts > '2024-01-01'",
+            "(id < 10 OR id >= 20) AND country IS NOT NULL",
+            "NOT (id != 3 AND name = 'O''Brien')",
+            "country NOT IN ('GB', 'IE')",
+        ] {
+            let filter: RowFilter = text.parse().unwrap();
+
+            assert_eq!(filter.to_string(), text);
+            assert_eq!(filter.to_string().parse(), Ok(filter));
+        }
+    }
+
+    #[test]
+    fn explains_filters_that_do_not_parse() {
+        let error = |text: &str| text.parse::<RowFilter>().unwrap_err();
+
+        assert_eq!(error("country ="), "expected a value after country =");
+        assert_eq!(
+            error("country 'GB'"),
+            "expected a comparison such as =, IN or IS NULL after country"
+        );
+        assert_eq!(error("(id = 1"), "expected ')' after id = 1");
+        assert_eq!(error("id = 1 id = 2"), "unexpected 'id'");
+        assert_eq!(error("country = 'GB"), "'GB is missing its closing quote");
+    }
+
+    #[test]
+    fn pushes_nots_down_into_conditions() {
+        let filter: RowFilter = "NOT (id < 3 OR (country IN ('GB') AND NOT ts IS NULL))"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            filter.rewrite_not().to_string(),
+            "id >= 3 AND (country NOT IN ('GB') OR ts IS NULL)"
+        );
+    }
+
+    #[test]
+    fn converts_literals_to_the_column_type() {
+        let filter: RowFilter = "id >= 10 AND ts < '2024-01-01'".parse().unwrap();
+
+        let predicate = filter.to_predicate(&schema()).unwrap();
+
+        let expected = Reference::new("id")
+            .greater_than_or_equal_to(Datum::long(10))
+            .and(Reference::new("ts").less_than(Datum::timestamptz_micros(1_704_067_200_000_000)));
+        assert_eq!(predicate, expected);
+    }
+
+    #[test]
+    fn rejects_unknown_columns_and_invalid_literals() {
+        let error = |text: &str| match text.parse::<RowFilter>().unwrap().to_predicate(&schema()) {
+            Err(TanicError::UnexpectedError(message)) => message,
+            other => panic!("expected an error, got {other:?}"),
+        };
+
+        assert_eq!(error("region = 'EU'"), "there is no column 'region'");
+        assert_eq!(
+            error("id = 'GB'"),
+            "'GB' is not a valid long to compare id with"
+        );
+        assert_eq!(
+            error("ts > 'yesterday'"),
+            "'yesterday' is not a valid timestamptz to compare ts with"
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Table Health
+//!
+//! Lint rules for the upkeep that Iceberg tables need: compacting small
+//! files and deletes, expiring snapshots and old metadata, declaring a sort
+//! order and the `write.*` properties that keep files well sized. Each
+//! table is scored from its findings, and thresholds come from the
+//! `[health]` section of the config.
+//!
+//! Tables are checked as they are loaded, from their metadata alone, so
+//! that the treemap can badge them. A health pass over a table or namespace
+//! also reads the current snapshot's manifests, to count small files
+//! exactly rather than estimating from the average file size.
+
+use iceberg::spec::TableMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use tanic_core::config::HealthConfig;
+use tanic_core::message::{HealthCheck, HealthFinding, Severity};
+
+use crate::report::{ReportTarget, TableFailure};
+use crate::MS_PER_DAY;
+
+/// Points a table loses for each finding, out of 100
+const WARNING_PENALTY: u8 = 15;
+const INFO_PENALTY: u8 = 5;
+
+/// The findings of a health pass over a table or namespace
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub target: ReportTarget,
+
+    /// tables checked, least healthy first
+    pub tables: Vec<TableHealth>,
+
+    /// tables that could not be loaded, and why
+    pub failures: Vec<TableFailure>,
+}
+
+impl HealthReport {
+    pub fn new(
+        target: ReportTarget,
+        mut tables: Vec<TableHealth>,
+        failures: Vec<TableFailure>,
+    ) -> Self {
+        tables.sort_by(|left, right| {
+            left.score()
+                .cmp(&right.score())
+                .then_with(|| left.name.cmp(&right.name))
+        });
+
+        Self {
+            target,
+            tables,
+            failures,
+        }
+    }
+
+    /// Every finding, with the table it is for
+    pub fn findings(&self) -> impl Iterator<Item = (&TableHealth, &HealthFinding)> {
+        self.tables
+            .iter()
+            .flat_map(|table| table.findings.iter().map(move |finding| (table, finding)))
+    }
+
+    /// Tables without any findings
+    pub fn healthy(&self) -> impl Iterator<Item = &TableHealth> {
+        self.tables.iter().filter(|table| table.findings.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableHealth {
+    pub name: String,
+
+    /// most severe first
+    pub findings: Vec<HealthFinding>,
+
+    /// whether small files were counted from the manifests, rather than
+    /// estimated from the average file size
+    pub exact_file_sizes: bool,
+}
+
+impl TableHealth {
+    pub fn score(&self) -> u8 {
+        score(&self.findings)
+    }
+}
+
+/// A table's health, out of 100
+pub fn score(findings: &[HealthFinding]) -> u8 {
+    findings
+        .iter()
+        .map(|finding| match finding.severity {
+            Severity::Warning => WARNING_PENALTY,
+            Severity::Info => INFO_PENALTY,
+        })
+        .fold(100, u8::saturating_sub)
+}
+
+/// What the health checks look at, taken from a table's metadata
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct TableFacts {
+    pub(crate) data_files: Option<u64>,
+    pub(crate) total_size_bytes: Option<u64>,
+
+    /// sizes of the current snapshot's data files, if its manifests were read
+    pub(crate) file_sizes: Option<Vec<u64>>,
+
+    pub(crate) snapshots: usize,
+    pub(crate) sorted: bool,
+    pub(crate) last_updated_ms: i64,
+    pub(crate) delete_files: Option<u64>,
+    pub(crate) metadata_log_entries: usize,
+    pub(crate) properties: BTreeSet<String>,
+}
+
+impl TableFacts {
+    pub(crate) fn new(metadata: &TableMetadata) -> Self {
+        let snapshot_stat = |key: &str| {
+            metadata
+                .current_snapshot()
+                .and_then(|snapshot| snapshot.summary().additional_properties.get(key))
+                .and_then(|val| val.parse::<u64>().ok())
+        };
+
+        Self {
+            data_files: snapshot_stat("total-data-files"),
+            total_size_bytes: snapshot_stat("total-files-size"),
+            file_sizes: None,
+            snapshots: metadata.snapshots().len(),
+            sorted: !metadata.default_sort_order().is_unsorted(),
+            last_updated_ms: metadata.last_updated_ms(),
+            delete_files: snapshot_stat("total-delete-files"),
+            metadata_log_entries: metadata.metadata_log().len(),
+            properties: metadata.properties().keys().cloned().collect(),
+        }
+    }
+
+    pub(crate) fn with_file_sizes(self, file_sizes: Vec<u64>) -> Self {
+        Self {
+            file_sizes: Some(file_sizes),
+            ..self
+        }
+    }
+}
+
+/// Run every health check over a table, most severe findings first
+pub(crate) fn check(facts: &TableFacts, config: &HealthConfig, now_ms: i64) -> Vec<HealthFinding> {
+    let mut findings = vec![];
+    let mut found = |check, severity, message: String| {
+        findings.push(HealthFinding {
+            check,
+            severity,
+            message,
+        })
+    };
+
+    if let Some(message) = small_files(facts, config) {
+        found(HealthCheck::SmallFiles, Severity::Warning, message);
+    }
+
+    if facts.snapshots > config.max_snapshots {
+        found(
+            HealthCheck::Snapshots,
+            Severity::Warning,
+            format!(
+                "{} snapshots retained, more than {}",
+                facts.snapshots, config.max_snapshots
+            ),
+        );
+    }
+
+    if config.require_sort_order && !facts.sorted {
+        found(
+            HealthCheck::SortOrder,
+            Severity::Info,
+            "no sort order declared".to_string(),
+        );
+    }
+
+    let days_since_commit = (now_ms - facts.last_updated_ms) / MS_PER_DAY;
+    if days_since_commit > config.stale_after_days as i64 {
+        found(
+            HealthCheck::StaleCommit,
+            Severity::Info,
+            format!(
+                "last commit {days_since_commit} days ago, more than {}",
+                config.stale_after_days
+            ),
+        );
+    }
+
+    if let Some(delete_files) = facts
+        .delete_files
+        .filter(|&delete_files| delete_files > config.max_delete_files)
+    {
+        found(
+            HealthCheck::DeleteFiles,
+            Severity::Warning,
+            format!(
+                "{delete_files} delete files, more than {}",
+                config.max_delete_files
+            ),
+        );
+    }
+
+    if facts.metadata_log_entries > config.max_metadata_log_entries {
+        found(
+            HealthCheck::MetadataLog,
+            Severity::Warning,
+            format!(
+                "{} previous metadata files logged, more than {}",
+                facts.metadata_log_entries, config.max_metadata_log_entries
+            ),
+        );
+    }
+
+    let missing = config
+        .required_write_properties
+        .iter()
+        .filter(|property| !facts.properties.contains(*property))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        found(
+            HealthCheck::WriteProperties,
+            Severity::Info,
+            format!("not set: {}", missing.join(", ")),
+        );
+    }
+
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+    findings
+}
+
+/// Why a table has too many small files, if it does
+fn small_files(facts: &TableFacts, config: &HealthConfig) -> Option<String> {
+    let small_file_bytes = config.small_file_bytes();
+
+    if let Some(file_sizes) = &facts.file_sizes {
+        let small = file_sizes
+            .iter()
+            .filter(|&&size| size < small_file_bytes)
+            .count() as u64;
+
+        return (small > config.max_small_files).then(|| {
+            format!(
+                "{small} data files smaller than {} MiB, more than {}",
+                config.small_file_mb, config.max_small_files
+            )
+        });
+    }
+
+    // without the file sizes, a small enough average means most files are small
+    let data_files = facts.data_files.filter(|&files| files > 0)?;
+    let average = facts.total_size_bytes? / data_files;
+
+    (data_files > config.max_small_files && average < small_file_bytes).then(|| {
+        format!(
+            "{data_files} data files averaging {:.1} MiB, under {} MiB",
+            average as f64 / (1 << 20) as f64,
+            config.small_file_mb
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    fn healthy() -> TableFacts {
+        TableFacts {
+            data_files: Some(10),
+            total_size_bytes: Some(10 * (128 << 20)),
+            file_sizes: None,
+            snapshots: 5,
+            sorted: true,
+            last_updated_ms: NOW_MS - MS_PER_DAY,
+            delete_files: Some(0),
+            metadata_log_entries: 5,
+            properties: HealthConfig::default()
+                .required_write_properties
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn checks(findings: &[HealthFinding]) -> Vec<HealthCheck> {
+        findings.iter().map(|finding| finding.check).collect()
+    }
+
+    #[test]
+    fn finds_nothing_wrong_with_a_healthy_table() {
+        let findings = check(&healthy(), &HealthConfig::default(), NOW_MS);
+
+        assert_eq!(findings, []);
+        assert_eq!(score(&findings), 100);
+    }
+
+    #[test]
+    fn flags_every_broken_threshold_most_severe_first() {
+        let facts = TableFacts {
+            snapshots: 250,
+            sorted: false,
+            last_updated_ms: NOW_MS - 45 * MS_PER_DAY,
+            delete_files: Some(300),
+            metadata_log_entries: 101,
+            properties: BTreeSet::from(["write.target-file-size-bytes".to_string()]),
+            ..healthy()
+        };
+
+        let findings = check(&facts, &HealthConfig::default(), NOW_MS);
+
+        assert_eq!(
+            checks(&findings),
+            [
+                HealthCheck::Snapshots,
+                HealthCheck::DeleteFiles,
+                HealthCheck::MetadataLog,
+                HealthCheck::SortOrder,
+                HealthCheck::StaleCommit,
+                HealthCheck::WriteProperties,
+            ]
+        );
+        assert_eq!(findings[4].message, "last commit 45 days ago, more than 30");
+        assert_eq!(
+            findings[5].message,
+            "not set: write.metadata.delete-after-commit.enabled"
+        );
+        assert_eq!(score(&findings), 100 - 3 * 15 - 3 * 5);
+    }
+
+    #[test]
+    fn counts_small_files_exactly_when_their_sizes_are_known() {
+        let config = HealthConfig {
+            max_small_files: 2,
+            ..HealthConfig::default()
+        };
+        let facts = TableFacts {
+            data_files: Some(4),
+            total_size_bytes: Some(4 << 20),
+            ..healthy()
+        };
+
+        // four 1 MiB files on average, but only three of them are small
+        let estimated = check(&facts, &config, NOW_MS);
+        assert_eq!(
+            estimated[0].message,
+            "4 data files averaging 1.0 MiB, under 32 MiB"
+        );
+
+        let facts = facts.with_file_sizes(vec![1 << 10, 1 << 10, 1 << 10, 64 << 20]);
+        let exact = check(&facts, &config, NOW_MS);
+        assert_eq!(
+            exact[0].message,
+            "3 data files smaller than 32 MiB, more than 2"
+        );
+
+        let config = HealthConfig {
+            max_small_files: 3,
+            ..config
+        };
+        assert_eq!(check(&facts, &config, NOW_MS), []);
+    }
+}
@@ -1,30 +1,84 @@
 //! Iceberg Context
-
-use iceberg::{Catalog, NamespaceIdent};
+//!
+//! Serves catalog requests from the app state. Each request is handled by
+//! its own task, with a limit on how many run at once, and its response is
+//! sent back as actions for the tab that made it.
+
+use futures::FutureExt;
+use http::Uri;
+use iceberg::table::Table;
+use iceberg::{Catalog, NamespaceIdent, TableIdent};
 use iceberg_catalog_rest::{RestCatalog, RestCatalogConfig};
-use std::sync::Arc;
-use tokio::sync::mpsc::UnboundedSender;
-use tokio::sync::watch::Receiver;
-use tokio_stream::{wrappers::WatchStream, StreamExt};
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::Semaphore;
+use tokio::task::{AbortHandle, JoinHandle};
+
+use tanic_core::config::{ConnectionDetails, HealthConfig};
+use tanic_core::error::panic_message;
+use tanic_core::message::{NamespaceDeets, TableDeets, TableSummary};
+use tanic_core::{Result, TanicConfig, TanicError};
+
+use crate::cache::{CacheStore, MetadataCache};
+use crate::commit::{RestTableCommitter, TableCommitter};
+use crate::comparison::{TableComparison, TableRef};
+use crate::deletes;
+use crate::export;
+use crate::health::{self, HealthReport, TableFacts, TableHealth};
+use crate::maintenance::{self, MaintenanceOperation, MaintenanceOutcome, MaintenancePlan};
+use crate::preview;
+use crate::profile;
+use crate::report::{self, Report, ReportTarget, SizeDistribution, TableFailure, TableReport};
+use crate::requests::{CatalogCommand, CatalogRequest, RequestId};
+use crate::scan_plan;
+use crate::state::TanicAction;
+
+/// How often changed metadata caches are persisted
+const CACHE_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Creates the catalog client for a connection
+pub trait CatalogConnector: std::fmt::Debug + Send + Sync {
+    fn connect(&self, connection_details: &ConnectionDetails) -> Arc<dyn Catalog>;
+
+    /// Commits the table updates that the catalog client can't
+    fn committer(&self, connection_details: &ConnectionDetails) -> Arc<dyn TableCommitter>;
+}
 
-use tanic_core::config::ConnectionDetails;
-use tanic_core::message::{NamespaceDeets, TableDeets};
-use tanic_core::{Result, TanicError};
+/// Connects to Iceberg REST catalogs
+#[derive(Debug, Default)]
+pub struct RestCatalogConnector;
 
-use crate::state::{TanicAction, TanicAppState, ViewingNamespacesListState};
+impl CatalogConnector for RestCatalogConnector {
+    fn connect(&self, connection_details: &ConnectionDetails) -> Arc<dyn Catalog> {
+        let mut uri_str = connection_details.uri.to_string();
+        uri_str.pop();
 
-#[derive(Debug)]
-enum Connection {
-    Disconnected,
-    Connected(IcebergContext),
+        let config = RestCatalogConfig::builder().uri(uri_str).build();
+        Arc::new(RestCatalog::new(config))
+    }
+
+    fn committer(&self, connection_details: &ConnectionDetails) -> Arc<dyn TableCommitter> {
+        Arc::new(RestTableCommitter::new(connection_details))
+    }
 }
 
-#[derive(Debug)]
-struct IcebergContext {
+/// A client for one catalog
+#[derive(Clone, Debug)]
+pub struct IcebergContext {
     connection_details: ConnectionDetails,
 
     /// Iceberg Catalog
-    catalog: Option<Arc<dyn Catalog>>,
+    catalog: Arc<dyn Catalog>,
+
+    /// commits what the catalog client can't, such as expiring snapshots
+    committer: Arc<dyn TableCommitter>,
+
+    /// how long to wait on each catalog request
+    request_timeout: Duration,
 
     namespaces: Vec<NamespaceDeets>,
     tables: Vec<TableDeets>,
@@ -34,114 +88,579 @@ struct IcebergContext {
 #[derive(Debug)]
 pub struct IcebergContextManager {
     action_tx: UnboundedSender<TanicAction>,
+
+    /// age after which cached metadata gets refreshed
+    cache_ttl: Duration,
+
+    /// how long to wait on each catalog request
+    request_timeout: Duration,
+
+    /// how many requests can be served at once
+    max_concurrent_requests: usize,
+
+    cache_store: Arc<CacheStore>,
+
+    connector: Arc<dyn CatalogConnector>,
+
+    /// thresholds for the health checks
+    health: Arc<HealthConfig>,
 }
 
 impl IcebergContextManager {
-    pub fn new(action_tx: UnboundedSender<TanicAction>) -> Self {
-        Self { action_tx }
+    pub fn new(action_tx: UnboundedSender<TanicAction>, config: &TanicConfig) -> Self {
+        Self {
+            action_tx,
+            cache_ttl: config.cache.ttl(),
+            request_timeout: config.requests.timeout(),
+            max_concurrent_requests: config.requests.max_concurrent.max(1),
+            cache_store: Arc::new(CacheStore::new(config.cache.cache_dir())),
+            connector: Arc::new(RestCatalogConnector),
+            health: Arc::new(config.health.clone()),
+        }
     }
 
-    pub async fn event_loop(self, state_rx: Receiver<TanicAppState>) -> Result<()> {
-        let mut connection = Connection::Disconnected;
+    /// Use a different kind of catalog client, such as an in-memory catalog
+    pub fn with_connector(mut self, connector: Arc<dyn CatalogConnector>) -> Self {
+        self.connector = connector;
+        self
+    }
 
-        let mut state_stream = WatchStream::new(state_rx);
+    pub async fn event_loop(self, mut command_rx: UnboundedReceiver<CatalogCommand>) -> Result<()> {
+        // one client per catalog, shared by all requests to it
+        let clients: SharedClients = Arc::default();
 
-        while let Some(state) = state_stream.next().await {
-            match state {
-                TanicAppState::ConnectingTo(ref new_conn_details) => {
-                    match &mut connection {
-                        // initial connection
-                        Connection::Disconnected => {
-                            let mut context = IcebergContext::connect_to(new_conn_details);
+        // cached metadata, shared by all tabs connected to the same catalog
+        let caches: SharedCaches = Arc::default();
 
-                            context.populate_namespaces().await?;
+        let worker_permits = Arc::new(Semaphore::new(self.max_concurrent_requests));
+        let mut in_flight: HashMap<RequestId, AbortHandle> = HashMap::new();
 
-                            self.action_tx
-                                .send(TanicAction::RetrievedNamespaceList(
-                                    context.namespaces.clone(),
-                                ))
-                                .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+        // maintenance being applied, which is finished rather than abandoned
+        // when the context stops, so that a table isn't left half cleaned up
+        let mut applying: HashMap<RequestId, JoinHandle<()>> = HashMap::new();
 
-                            connection = Connection::Connected(context);
+        let mut cache_save_ticker = tokio::time::interval(CACHE_SAVE_INTERVAL);
+
+        loop {
+            tokio::select! {
+                command = command_rx.recv() => {
+                    let Some(command) = command else {
+                        break;
+                    };
+                    in_flight.retain(|_, handle| !handle.is_finished());
+                    applying.retain(|_, handle| !handle.is_finished());
+
+                    match command {
+                        CatalogCommand::Request(id, request) => {
+                            tracing::debug!(?id, ?request, "serving request");
+                            let applies = matches!(request, CatalogRequest::ApplyMaintenance(_));
+
+                            let worker = RequestWorker {
+                                id,
+                                action_tx: self.action_tx.clone(),
+                                clients: clients.clone(),
+                                caches: caches.clone(),
+                                cache_store: self.cache_store.clone(),
+                                connector: self.connector.clone(),
+                                cache_ttl: self.cache_ttl,
+                                request_timeout: self.request_timeout,
+                                health: self.health.clone(),
+                            };
+                            let worker_permits = worker_permits.clone();
+
+                            let handle = tokio::spawn(async move {
+                                // wait for a free worker, while still being cancellable
+                                let Ok(_permit) = worker_permits.acquire_owned().await else {
+                                    return;
+                                };
+                                worker.run(request).await;
+                            });
+                            in_flight.insert(id, handle.abort_handle());
+                            if applies {
+                                applying.insert(id, handle);
+                            }
                         }
 
-                        // already existing connection? No Op
-                        Connection::Connected(IcebergContext {
-                            connection_details, ..
-                        }) if connection_details.uri == new_conn_details.uri => {}
+                        CatalogCommand::Cancel(id) => {
+                            if let Some(handle) = in_flight.remove(&id) {
+                                tracing::debug!(?id, "cancelling request");
+                                handle.abort();
+                            }
+                        }
+                    }
+                },
 
-                        // switch connection
-                        Connection::Connected(_) => {
-                            let mut context = IcebergContext::connect_to(new_conn_details);
+                _ = cache_save_ticker.tick() => self.save_caches(&caches).await,
+            }
+        }
 
-                            context.populate_namespaces().await?;
+        for (id, handle) in &in_flight {
+            if !applying.contains_key(id) {
+                handle.abort();
+            }
+        }
+        if !applying.is_empty() {
+            tracing::info!(
+                requests = applying.len(),
+                "waiting for maintenance being applied to finish"
+            );
+        }
+        for handle in applying.into_values() {
+            let _ = handle.await;
+        }
+        self.save_caches(&caches).await;
 
-                            self.action_tx
-                                .send(TanicAction::RetrievedNamespaceList(
-                                    context.namespaces.clone(),
-                                ))
-                                .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+        Ok(())
+    }
 
-                            connection = Connection::Connected(context);
-                        }
+    /// Persist any caches that have changed since they were last saved
+    async fn save_caches(&self, caches: &SharedCaches) {
+        let changed = caches
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter_mut()
+            .filter_map(|(uri, cache)| cache.take_dirty().then(|| (uri.clone(), cache.clone())))
+            .collect::<Vec<_>>();
+
+        for (uri, cache) in changed {
+            self.cache_store.save(&uri, &cache).await;
+        }
+    }
+}
+
+type SharedClients = Arc<Mutex<HashMap<Uri, IcebergContext>>>;
+type SharedCaches = Arc<Mutex<HashMap<Uri, MetadataCache>>>;
+
+/// Serves a single request, as its own task
+struct RequestWorker {
+    id: RequestId,
+    action_tx: UnboundedSender<TanicAction>,
+
+    clients: SharedClients,
+    caches: SharedCaches,
+    cache_store: Arc<CacheStore>,
+    connector: Arc<dyn CatalogConnector>,
+    cache_ttl: Duration,
+    request_timeout: Duration,
+    health: Arc<HealthConfig>,
+}
+
+impl RequestWorker {
+    async fn run(self, request: CatalogRequest) {
+        // a request that panics fails like any other, rather than leaving the
+        // view that made it waiting for a response that never comes
+        let handled = AssertUnwindSafe(self.handle_request(request))
+            .catch_unwind()
+            .await;
+
+        let actions = match handled {
+            Ok(Ok(actions)) => actions,
+            Ok(Err(err)) => {
+                let message = describe(&err);
+                tracing::warn!(error = message, "catalog request failed");
+                vec![TanicAction::RequestFailed(message)]
+            }
+            Err(payload) => {
+                let message = format!("the request panicked: {}", panic_message(&*payload));
+                tracing::error!(error = message, "catalog request failed");
+                vec![TanicAction::RequestFailed(message)]
+            }
+        };
+
+        // the action channel only closes when the app is shutting down
+        let _ = self
+            .action_tx
+            .send(TanicAction::CatalogResponse(self.id, actions));
+    }
+
+    async fn handle_request(&self, request: CatalogRequest) -> Result<Vec<TanicAction>> {
+        match request {
+            CatalogRequest::ListNamespaces {
+                connection,
+                refresh,
+            } => {
+                let uri = &connection.uri;
+
+                // show cached namespaces straight away, marked as stale and
+                // refreshed if they are
+                let cached = self
+                    .with_cache(uri, |cache| cache.namespaces().cloned())
+                    .await;
+                if let (Some(cached), false) = (cached, refresh) {
+                    let stale = !cached.is_fresh(self.cache_ttl);
+                    let namespaces = cached
+                        .value
+                        .into_iter()
+                        .map(|namespace| NamespaceDeets { stale, ..namespace })
+                        .collect();
+
+                    let mut actions = vec![TanicAction::RetrievedNamespaceList(namespaces)];
+                    if stale {
+                        actions.push(TanicAction::Refresh);
                     }
+                    return Ok(actions);
                 }
-                TanicAppState::ViewingNamespacesList(_) => {}
-                TanicAppState::RetrievingTableList(ViewingNamespacesListState {
-                    namespaces,
-                    selected_idx,
-                }) => {
-                    let Some(selected_idx) = selected_idx else {
-                        continue;
-                    };
-                    let namespace = &namespaces[selected_idx];
-                    if let Connection::Connected(ref mut iceberg_ctx) = &mut connection {
-                        iceberg_ctx.populate_table_list(&namespace.parts).await?;
-
-                        self.action_tx
-                            .send(TanicAction::RetrievedTableList(
-                                namespace.clone(),
-                                iceberg_ctx.tables.clone(),
-                            ))
-                            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+
+                if refresh {
+                    tracing::debug!(connection = connection.name, "refreshing namespaces");
+                }
+
+                let mut iceberg_ctx = self.client(&connection);
+                iceberg_ctx.populate_namespaces().await?;
+                let namespaces = iceberg_ctx.namespaces;
+                self.with_cache(uri, |cache| cache.set_namespaces(namespaces.clone()))
+                    .await;
+
+                Ok(vec![if refresh {
+                    TanicAction::RefreshedNamespaceList(namespaces)
+                } else {
+                    TanicAction::RetrievedNamespaceList(namespaces)
+                }])
+            }
+
+            CatalogRequest::ListTables {
+                connection,
+                namespace,
+                refresh,
+            } => {
+                let uri = &connection.uri;
+
+                // show cached tables straight away, marked as stale and
+                // refreshed if they are
+                let cached = self
+                    .with_cache(uri, |cache| cache.tables(&namespace.parts).cloned())
+                    .await;
+                if let (Some(cached), false) = (cached, refresh) {
+                    let listing_stale = !cached.is_fresh(self.cache_ttl);
+                    let tables = cached
+                        .value
+                        .into_iter()
+                        .map(|table| TableDeets {
+                            stale: table.stale || listing_stale,
+                            ..table
+                        })
+                        .collect();
+
+                    let mut actions = vec![TanicAction::RetrievedTableList(namespace, tables)];
+                    if listing_stale {
+                        actions.push(TanicAction::Refresh);
                     }
+                    return Ok(actions);
                 }
-                TanicAppState::Exiting => {
-                    break;
+
+                if refresh {
+                    tracing::debug!(namespace = namespace.name, "refreshing tables");
+                }
+
+                let mut iceberg_ctx = self.client(&connection);
+                iceberg_ctx.populate_table_list(&namespace.parts).await?;
+                let tables = self
+                    .with_cache(uri, |cache| {
+                        cache.set_tables(&namespace.parts, iceberg_ctx.tables)
+                    })
+                    .await;
+
+                Ok(vec![if refresh {
+                    TanicAction::RefreshedTableList(namespace, tables)
+                } else {
+                    TanicAction::RetrievedTableList(namespace, tables)
+                }])
+            }
+
+            CatalogRequest::LoadTable {
+                connection,
+                namespace,
+                name,
+                refresh,
+            } => {
+                let uri = &connection.uri;
+
+                let cached = self
+                    .with_cache(uri, |cache| cache.table(&namespace, &name))
+                    .await;
+                if let (Some(cached), false) = (cached, refresh) {
+                    let summarised = cached.value.summary.is_some() && !cached.value.stale;
+                    if summarised && cached.is_fresh(self.cache_ttl) {
+                        return Ok(vec![TanicAction::EnrichedTableDetails(cached.value)]);
+                    }
                 }
-                _ => {}
+
+                let table = TableDeets {
+                    namespace,
+                    name,
+                    row_count: 1,
+                    summary: None,
+                    stale: false,
+                };
+
+                // a table that can't be loaded just goes without its summary,
+                // rather than failing the view that it's in
+                let table = match self
+                    .client(&connection)
+                    .enrich_table(table, &self.health)
+                    .await
+                {
+                    Ok(table) => table,
+                    Err(err) => {
+                        tracing::warn!(error = describe(&err), "could not load table");
+                        return Ok(vec![]);
+                    }
+                };
+                self.with_cache(uri, |cache| cache.update_table(&table))
+                    .await;
+
+                Ok(vec![TanicAction::EnrichedTableDetails(table)])
+            }
+
+            CatalogRequest::CompareTables(left, right) => {
+                let left_table = self.load_table_ref(&left).await;
+                let right_table = self.load_table_ref(&right).await;
+
+                let comparison =
+                    TableComparison::new(*left, left_table.as_ref(), *right, right_table.as_ref());
+
+                Ok(vec![TanicAction::RetrievedTableComparison(Box::new(
+                    comparison,
+                ))])
+            }
+
+            CatalogRequest::PreviewTable(request) => {
+                let table = self
+                    .client(&request.table.connection)
+                    .load_table(&request.table.namespace, &request.table.name)
+                    .await?;
+                let preview =
+                    preview::preview_table(&table, *request, self.request_timeout).await?;
+
+                Ok(vec![TanicAction::RetrievedTablePreview(Box::new(preview))])
+            }
+
+            CatalogRequest::PlanScan(request) => {
+                let table = self
+                    .client(&request.table.connection)
+                    .load_table(&request.table.namespace, &request.table.name)
+                    .await?;
+                let plan =
+                    timed(self.request_timeout, scan_plan::plan_scan(&table, *request)).await?;
+
+                Ok(vec![TanicAction::RetrievedScanPlan(Box::new(plan))])
+            }
+
+            CatalogRequest::ProfileTable(request) => {
+                let table = self
+                    .client(&request.table.connection)
+                    .load_table(&request.table.namespace, &request.table.name)
+                    .await?;
+                let profile =
+                    profile::profile_table(&table, *request, self.request_timeout).await?;
+
+                Ok(vec![TanicAction::RetrievedTableProfile(Box::new(profile))])
+            }
+
+            CatalogRequest::InspectDeletes(table_ref) => {
+                let table = self
+                    .client(&table_ref.connection)
+                    .load_table(&table_ref.namespace, &table_ref.name)
+                    .await?;
+                let health = timed(
+                    self.request_timeout,
+                    deletes::delete_health(&table, *table_ref),
+                )
+                .await?;
+
+                Ok(vec![TanicAction::RetrievedDeleteHealth(Box::new(health))])
+            }
+
+            CatalogRequest::CheckHealth(target) => {
+                let report = self
+                    .client(target.connection())
+                    .health(*target, &self.health)
+                    .await?;
+
+                Ok(vec![TanicAction::RetrievedHealthReport(Box::new(report))])
+            }
+
+            CatalogRequest::PlanMaintenance(request) => {
+                let table = self
+                    .client(&request.table.connection)
+                    .load_table(&request.table.namespace, &request.table.name)
+                    .await?;
+                let plan =
+                    maintenance::plan_maintenance(&table, *request, now_ms(), self.request_timeout)
+                        .await?;
+                tracing::info!(
+                    table = plan.request.table.label(),
+                    operation = %plan.request.operation,
+                    removals = plan.removals.len(),
+                    kept = plan.kept,
+                    "planned maintenance, as a dry run"
+                );
+
+                Ok(vec![TanicAction::RetrievedMaintenancePlan(Box::new(plan))])
+            }
+
+            CatalogRequest::ApplyMaintenance(plan) => {
+                let outcome = self
+                    .client(&plan.request.table.connection)
+                    .apply_maintenance(&plan)
+                    .await?;
+
+                Ok(vec![TanicAction::AppliedMaintenance(Box::new(outcome))])
+            }
+
+            CatalogRequest::ExportTable(request) => {
+                let table = self
+                    .client(&request.table.connection)
+                    .load_table(&request.table.namespace, &request.table.name)
+                    .await?;
+
+                let rows_expected = export::expected_rows(&table, &request.options);
+                let report_progress = |rows_written| {
+                    let _ = self.action_tx.send(TanicAction::ExportProgressed {
+                        id: self.id,
+                        rows_written,
+                        rows_expected,
+                    });
+                };
+                report_progress(0);
+
+                let rows_written =
+                    export::export_table(&table, &request.path, &request.options, report_progress)
+                        .await?;
+                tracing::info!(
+                    table = request.table.label(),
+                    path = %request.path.display(),
+                    rows_written,
+                    "exported table"
+                );
+
+                Ok(vec![TanicAction::ExportFinished(rows_written)])
+            }
+
+            CatalogRequest::WriteReport(request) => {
+                let iceberg_ctx = self.client(request.target.connection());
+                let report = iceberg_ctx.report(&request.target).await?;
+                report.write(&request.path, request.format)?;
+                tracing::info!(
+                    target = request.target.label(),
+                    path = %request.path.display(),
+                    "wrote metadata report"
+                );
+
+                Ok(vec![])
             }
         }
+    }
 
-        Ok(())
+    /// Load a table for comparison. A table that can't be loaded is reported
+    /// as missing rather than failing the comparison.
+    async fn load_table_ref(&self, table_ref: &TableRef) -> Option<Table> {
+        let iceberg_ctx = self.client(&table_ref.connection);
+
+        match iceberg_ctx
+            .load_table(&table_ref.namespace, &table_ref.name)
+            .await
+        {
+            Ok(table) => Some(table),
+            Err(err) => {
+                tracing::warn!(
+                    table = table_ref.label(),
+                    error = describe(&err),
+                    "could not load table for comparison"
+                );
+                None
+            }
+        }
+    }
+
+    /// The client for a catalog, connecting to it the first time
+    fn client(&self, connection: &ConnectionDetails) -> IcebergContext {
+        self.clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(connection.uri.clone())
+            .or_insert_with(|| {
+                IcebergContext::connect_to(connection, &*self.connector, self.request_timeout)
+            })
+            .clone()
+    }
+
+    /// Access the cache for a catalog, loading it from disk the first time.
+    /// The cache is loaded before taking the lock, so that other workers
+    /// aren't held up by the read.
+    async fn with_cache<T>(&self, uri: &Uri, f: impl FnOnce(&mut MetadataCache) -> T) -> T {
+        let loaded = self.caches().contains_key(uri);
+        if !loaded {
+            let cache = self.cache_store.load(uri).await;
+            // another worker may have loaded it in the meantime
+            self.caches().entry(uri.clone()).or_insert(cache);
+        }
+
+        f(self.caches().entry(uri.clone()).or_default())
+    }
+
+    fn caches(&self) -> MutexGuard<'_, HashMap<Uri, MetadataCache>> {
+        self.caches.lock().unwrap_or_else(PoisonError::into_inner)
     }
 }
 
-impl IcebergContext {
-    /// Create a new Iceberg Context from a Uri
-    pub fn connect_to(connection_details: &ConnectionDetails) -> Self {
-        let connection_details = connection_details.clone();
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
 
-        let mut uri_str = connection_details.uri.to_string();
-        uri_str.pop();
+/// Wait on a request to a catalog or its file store, giving up after `timeout`
+pub(crate) async fn timed<T, E>(
+    timeout: Duration,
+    request: impl Future<Output = std::result::Result<T, E>>,
+) -> Result<T>
+where
+    TanicError: From<E>,
+{
+    match tokio::time::timeout(timeout, request).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(TanicError::Timeout(timeout)),
+    }
+}
 
-        let config = RestCatalogConfig::builder().uri(uri_str).build();
-        let rest_catalog = RestCatalog::new(config);
+/// An error's message, followed by the messages of its sources
+pub(crate) fn describe(err: &TanicError) -> String {
+    if let TanicError::UnexpectedError(message) = err {
+        return message.clone();
+    }
+
+    let mut message = err.to_string();
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        message.push_str(&format!(": {err}"));
+        source = err.source();
+    }
+    message
+}
 
+impl IcebergContext {
+    /// Create a new Iceberg Context for a connection
+    pub fn connect_to(
+        connection_details: &ConnectionDetails,
+        connector: &dyn CatalogConnector,
+        request_timeout: Duration,
+    ) -> Self {
         Self {
-            connection_details,
+            connection_details: connection_details.clone(),
             namespaces: vec![],
             tables: vec![],
-            catalog: Some(Arc::new(rest_catalog)),
+            catalog: connector.connect(connection_details),
+            committer: connector.committer(connection_details),
+            request_timeout,
         }
     }
 
-    pub async fn populate_namespaces(&mut self) -> Result<()> {
-        let Some(ref catalog) = self.catalog else {
-            panic!();
-        };
+    /// Wait on a catalog request, giving up after the request timeout
+    async fn timed<T>(&self, request: impl Future<Output = iceberg::Result<T>>) -> Result<T> {
+        timed(self.request_timeout, request).await
+    }
 
-        let root_namespaces = catalog.list_namespaces(None).await?;
+    pub async fn populate_namespaces(&mut self) -> Result<()> {
+        let root_namespaces = self.timed(self.catalog.list_namespaces(None)).await?;
 
         let namespaces = root_namespaces
             .into_iter()
@@ -152,6 +671,7 @@ impl IcebergContext {
                     parts,
                     name,
                     table_count: 0,
+                    stale: false,
                 }
             })
             .collect::<Vec<_>>();
@@ -162,12 +682,9 @@ impl IcebergContext {
     }
 
     pub async fn populate_table_list(&mut self, namespace_parts: &Vec<String>) -> Result<()> {
-        let Some(ref catalog) = self.catalog else {
-            panic!();
-        };
-
-        let tables = catalog
-            .list_tables(&NamespaceIdent::from_strs(namespace_parts)?)
+        let namespace_ident = NamespaceIdent::from_strs(namespace_parts)?;
+        let tables = self
+            .timed(self.catalog.list_tables(&namespace_ident))
             .await?;
 
         let table_names = tables
@@ -176,6 +693,8 @@ impl IcebergContext {
                 namespace: namespace_parts.clone(),
                 name: ti.name().to_string(),
                 row_count: 1,
+                summary: None,
+                stale: false,
             })
             .collect::<Vec<_>>();
 
@@ -183,4 +702,231 @@ impl IcebergContext {
 
         Ok(())
     }
+
+    pub async fn load_table(&self, namespace: &[String], name: &str) -> Result<Table> {
+        let table_ident =
+            TableIdent::from_strs(namespace.iter().map(String::as_str).chain([name]))?;
+        tracing::debug!(
+            connection = self.connection_details.name,
+            table = name,
+            "loading table"
+        );
+
+        self.timed(self.catalog.load_table(&table_ident)).await
+    }
+
+    /// Load a table's metadata and fill in its summary stats, along with
+    /// what the health checks find from the metadata alone
+    pub async fn enrich_table(
+        &self,
+        mut table: TableDeets,
+        health: &HealthConfig,
+    ) -> Result<TableDeets> {
+        let loaded = self.load_table(&table.namespace, &table.name).await?;
+        let metadata = loaded.metadata();
+
+        let snapshot_stat = |key: &str| {
+            metadata
+                .current_snapshot()
+                .and_then(|snapshot| snapshot.summary().additional_properties.get(key))
+                .and_then(|val| val.parse::<u64>().ok())
+        };
+
+        let summary = TableSummary {
+            total_size_bytes: snapshot_stat("total-files-size"),
+            data_file_count: snapshot_stat("total-data-files"),
+            record_count: snapshot_stat("total-records"),
+            last_updated_ms: metadata.last_updated_ms(),
+            findings: health::check(&TableFacts::new(metadata), health, now_ms()),
+        };
+
+        if let Some(record_count) = summary.record_count {
+            table.row_count = record_count as usize;
+        }
+        table.summary = Some(summary);
+
+        Ok(table)
+    }
+
+    /// Gather a report on a table, or on every table in a namespace
+    pub async fn report(&self, target: &ReportTarget) -> Result<Report> {
+        match target {
+            ReportTarget::Table(table) => Ok(Report {
+                title: target.label(),
+                tables: vec![self.table_report(&table.namespace, &table.name).await?],
+                failures: vec![],
+            }),
+            ReportTarget::Namespace { namespace, .. } => {
+                self.namespace_report(target.label(), namespace).await
+            }
+        }
+    }
+
+    /// Report on a table's metadata, along with the sizes of its data files
+    pub async fn table_report(&self, namespace: &[String], name: &str) -> Result<TableReport> {
+        let table = self.load_table(namespace, name).await?;
+
+        // a table whose manifests can't be read is still worth reporting on
+        let file_sizes = match self.timed(report::data_file_sizes(&table)).await {
+            Ok(sizes) => Some(SizeDistribution::new(sizes)),
+            Err(err) => {
+                tracing::warn!(
+                    table = name,
+                    error = describe(&err),
+                    "could not read manifests"
+                );
+                None
+            }
+        };
+
+        Ok(TableReport::new(&table, file_sizes))
+    }
+
+    /// Run the health checks over a table, or every table in a namespace.
+    /// Tables in a namespace that can't be loaded are listed in the report
+    /// rather than failing it.
+    pub async fn health(
+        &self,
+        target: ReportTarget,
+        config: &HealthConfig,
+    ) -> Result<HealthReport> {
+        let (tables, failures) = match &target {
+            ReportTarget::Table(table) => (
+                vec![
+                    self.table_health(&table.namespace, &table.name, config)
+                        .await?,
+                ],
+                vec![],
+            ),
+            ReportTarget::Namespace { namespace, .. } => {
+                let mut tables = vec![];
+                let mut failures = vec![];
+                for name in self.table_names(namespace).await? {
+                    match self.table_health(namespace, &name, config).await {
+                        Ok(table) => tables.push(table),
+                        Err(err) => failures.push(TableFailure {
+                            table: name,
+                            error: describe(&err),
+                        }),
+                    }
+                }
+                (tables, failures)
+            }
+        };
+
+        Ok(HealthReport::new(target, tables, failures))
+    }
+
+    /// Check a table's health, counting its small files from its manifests
+    /// where they can be read
+    async fn table_health(
+        &self,
+        namespace: &[String],
+        name: &str,
+        config: &HealthConfig,
+    ) -> Result<TableHealth> {
+        let table = self.load_table(namespace, name).await?;
+        let facts = TableFacts::new(table.metadata());
+
+        let facts = match self.timed(report::data_file_sizes(&table)).await {
+            Ok(sizes) => facts.with_file_sizes(sizes),
+            Err(err) => {
+                tracing::warn!(
+                    table = name,
+                    error = describe(&err),
+                    "could not read manifests, estimating small files"
+                );
+                facts
+            }
+        };
+
+        Ok(TableHealth {
+            name: name.to_string(),
+            exact_file_sizes: facts.file_sizes.is_some(),
+            findings: health::check(&facts, config, now_ms()),
+        })
+    }
+
+    /// Remove exactly what a maintenance plan lists, provided that the table
+    /// and its current snapshot are still the ones that it was planned against
+    pub async fn apply_maintenance(&self, plan: &MaintenancePlan) -> Result<MaintenanceOutcome> {
+        let table_ref = &plan.request.table;
+        let table = self
+            .load_table(&table_ref.namespace, &table_ref.name)
+            .await?;
+
+        let metadata = table.metadata();
+        if metadata.uuid().to_string() != plan.table_uuid
+            || metadata.current_snapshot_id() != plan.snapshot_id
+        {
+            return Err(TanicError::UnexpectedError(format!(
+                "{} has changed since the dry run, so nothing was removed. Run it again.",
+                table_ref.label()
+            )));
+        }
+
+        let outcome = match &plan.request.operation {
+            MaintenanceOperation::ExpireSnapshots { .. } => {
+                let commit = maintenance::expire_snapshots(&*self.committer, &table, plan);
+                timed(self.request_timeout, commit).await?
+            }
+            MaintenanceOperation::RemoveOldMetadata { .. } => {
+                let commit = maintenance::trim_metadata_log(&*self.committer, &table, plan);
+                timed(self.request_timeout, commit).await?;
+
+                // only files that the trimmed log no longer lists are deleted
+                let table = self
+                    .load_table(&table_ref.namespace, &table_ref.name)
+                    .await?;
+                maintenance::delete_files(&table, plan).await
+            }
+            MaintenanceOperation::DeleteOrphanFiles { .. } => {
+                maintenance::delete_files(&table, plan).await
+            }
+        };
+        tracing::info!(
+            table = table_ref.label(),
+            operation = %plan.request.operation,
+            removed = outcome.removed,
+            failed = outcome.failures.len(),
+            "applied maintenance"
+        );
+
+        Ok(outcome)
+    }
+
+    /// The names of the tables in a namespace, in order
+    async fn table_names(&self, namespace: &[String]) -> Result<Vec<String>> {
+        let namespace_ident = NamespaceIdent::from_strs(namespace)?;
+        let mut names = self
+            .timed(self.catalog.list_tables(&namespace_ident))
+            .await?
+            .into_iter()
+            .map(|ident| ident.name().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Report on every table in a namespace. Tables that can't be loaded are
+    /// listed in the report rather than failing it.
+    async fn namespace_report(&self, title: String, namespace: &[String]) -> Result<Report> {
+        let mut report = Report {
+            title,
+            tables: vec![],
+            failures: vec![],
+        };
+        for name in self.table_names(namespace).await? {
+            match self.table_report(namespace, &name).await {
+                Ok(table) => report.tables.push(table),
+                Err(err) => report.failures.push(TableFailure {
+                    table: name,
+                    error: describe(&err),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
 }
@@ -3,61 +3,98 @@ use tanic_core::{Result, TanicError};
 use tokio::sync::mpsc::{UnboundedReceiver as MpscReceiver, UnboundedSender as MpscSender};
 use tokio::sync::watch::{Receiver as WatchReceiver, Sender as WatchSender};
 
+use crate::requests::CatalogCommand;
+use crate::tabs::Tabs;
+
+mod cache;
+pub mod commit;
+pub mod comparison;
+pub mod deletes;
+pub mod export;
+pub mod filter;
+pub mod health;
 pub mod iceberg_context;
+pub mod maintenance;
+mod navigation;
+pub mod preview;
+pub mod profile;
+pub mod report;
+pub mod requests;
+pub mod scan_plan;
 pub mod state;
+pub mod tabs;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub use state::{TanicAction, TanicAppState};
+pub use tabs::{TabId, TanicTab, TanicTabsState};
+
+/// Milliseconds in a day, to convert ages in days to and from timestamps
+pub(crate) const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
 
 pub struct AppStateManager {
     action_rx: MpscReceiver<TanicAction>,
 
     #[allow(unused)]
     action_tx: MpscSender<TanicAction>,
-    state_tx: WatchSender<TanicAppState>,
+    state_tx: WatchSender<TanicTabsState>,
+    command_tx: MpscSender<CatalogCommand>,
 
-    state: TanicAppState,
+    tabs: Tabs,
 }
 
 impl AppStateManager {
     pub fn new(
         _config: TanicConfig,
-    ) -> (Self, MpscSender<TanicAction>, WatchReceiver<TanicAppState>) {
-        let state = TanicAppState::default();
+    ) -> (
+        Self,
+        MpscSender<TanicAction>,
+        WatchReceiver<TanicTabsState>,
+        MpscReceiver<CatalogCommand>,
+    ) {
+        let tabs = Tabs::default();
 
         let (action_tx, action_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (state_tx, state_rx) = tokio::sync::watch::channel(state.clone());
+        let (state_tx, state_rx) = tokio::sync::watch::channel(tabs.snapshot());
+        let (command_tx, command_rx) = tokio::sync::mpsc::unbounded_channel();
 
         (
             Self {
                 action_rx,
                 action_tx: action_tx.clone(),
                 state_tx,
-                state,
+                command_tx,
+                tabs,
             },
             action_tx,
             state_rx,
+            command_rx,
         )
     }
 
     pub async fn event_loop(self) -> Result<()> {
         let Self {
-            mut state,
+            mut tabs,
             state_tx,
+            command_tx,
             mut action_rx,
             ..
         } = self;
 
-        while !matches!(state, TanicAppState::Exiting) {
+        while !tabs.is_exiting() {
             let Some(action) = action_rx.recv().await else {
                 break;
             };
             tracing::info!(?action, "AppState received an action");
 
-            let next_state = state.reduce(action);
+            for command in tabs.reduce(action) {
+                command_tx
+                    .send(command)
+                    .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+            }
 
-            state = next_state;
             state_tx
-                .send(state.clone())
+                .send(tabs.snapshot())
                 .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
         }
 
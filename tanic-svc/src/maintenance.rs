@@ -0,0 +1,926 @@
+//! Table Maintenance
+//!
+//! Routine housekeeping that would otherwise need a Spark job: expiring old
+//! snapshots, removing old metadata files, and deleting orphan files that no
+//! snapshot references. Every operation is planned first, as a dry run that
+//! lists exactly what would be removed, and only that list is acted on once
+//! the user has confirmed it by typing the table's name.
+//!
+//! Expiring snapshots only removes them from the table's metadata. Files
+//! that are no longer referenced by any snapshot are left in place, to be
+//! found by a later orphan file pass.
+//!
+//! Old metadata files are removed from the metadata log before they are
+//! deleted, by briefly setting the `write.metadata.previous-versions-max`
+//! that the catalog trims the log to. The property is restored straight
+//! after, so the table's configuration is left as it was.
+//!
+//! Orphan files can only be found in table locations on the local
+//! filesystem, since Iceberg's file IO can't list object stores.
+
+use iceberg::spec::{ManifestList, TableMetadata, PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX};
+use iceberg::table::Table;
+use iceberg::{TableRequirement, TableUpdate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use tanic_core::{Result, TanicError};
+
+use crate::commit::TableCommitter;
+use crate::comparison::TableRef;
+use crate::iceberg_context::timed;
+use crate::MS_PER_DAY;
+
+const MAIN_BRANCH: &str = "main";
+
+/// A maintenance operation, and the table to run it on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceRequest {
+    pub table: TableRef,
+    pub operation: MaintenanceOperation,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MaintenanceOperation {
+    /// remove snapshots committed more than `older_than_days` ago, or
+    /// beyond the `retain_last` most recent. Snapshots that a branch or tag
+    /// points at are always kept.
+    ExpireSnapshots {
+        older_than_days: Option<u64>,
+        retain_last: Option<usize>,
+    },
+
+    /// delete the metadata files in the table's metadata log, beyond the
+    /// `retain_last` most recent
+    RemoveOldMetadata { retain_last: usize },
+
+    /// delete files under the table's location that no snapshot or metadata
+    /// file references, and that were last modified more than
+    /// `older_than_days` ago
+    DeleteOrphanFiles { older_than_days: u64 },
+}
+
+impl MaintenanceOperation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaintenanceOperation::ExpireSnapshots { .. } => "Expire snapshots",
+            MaintenanceOperation::RemoveOldMetadata { .. } => "Remove old metadata",
+            MaintenanceOperation::DeleteOrphanFiles { .. } => "Delete orphan files",
+        }
+    }
+}
+
+impl Display for MaintenanceOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaintenanceOperation::ExpireSnapshots {
+                older_than_days,
+                retain_last,
+            } => {
+                let criteria = [
+                    older_than_days.map(|days| format!("older than {days} days")),
+                    retain_last.map(|last| format!("beyond the last {last}")),
+                ];
+                let criteria = criteria.into_iter().flatten().collect::<Vec<_>>();
+                write!(f, "expire snapshots {}", criteria.join(" or "))
+            }
+            MaintenanceOperation::RemoveOldMetadata { retain_last } => {
+                write!(f, "remove metadata files beyond the last {retain_last}")
+            }
+            MaintenanceOperation::DeleteOrphanFiles { older_than_days } => {
+                write!(f, "delete orphan files older than {older_than_days} days")
+            }
+        }
+    }
+}
+
+/// Something that a maintenance operation removes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Removal {
+    Snapshot {
+        id: i64,
+        committed_at: String,
+        operation: String,
+    },
+    File {
+        path: String,
+
+        /// `None` if the file's size isn't known without reading it
+        size_bytes: Option<u64>,
+    },
+}
+
+impl Removal {
+    /// The snapshot ID or file path, for logging
+    pub fn target(&self) -> String {
+        match self {
+            Removal::Snapshot { id, .. } => id.to_string(),
+            Removal::File { path, .. } => path.clone(),
+        }
+    }
+}
+
+/// A dry run of a maintenance operation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MaintenancePlan {
+    pub request: MaintenanceRequest,
+
+    /// the table's current snapshot when the plan was made. Snapshots are
+    /// only expired if it hasn't changed since.
+    pub snapshot_id: Option<i64>,
+    pub table_uuid: String,
+
+    /// exactly what applying the plan removes, oldest first
+    pub removals: Vec<Removal>,
+
+    /// snapshots, metadata files or files under the table location that
+    /// are kept
+    pub kept: usize,
+}
+
+impl MaintenancePlan {
+    /// Total size of the files to remove, where it is known
+    pub fn bytes_to_remove(&self) -> u64 {
+        self.removals
+            .iter()
+            .filter_map(|removal| match removal {
+                Removal::File { size_bytes, .. } => *size_bytes,
+                Removal::Snapshot { .. } => None,
+            })
+            .sum()
+    }
+}
+
+/// What applying a plan removed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceOutcome {
+    pub removed: usize,
+    pub failures: Vec<RemovalFailure>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RemovalFailure {
+    pub target: String,
+    pub error: String,
+}
+
+/// Plan a maintenance operation, without changing anything. Reading the
+/// table's manifests gives up after `timeout`.
+pub async fn plan_maintenance(
+    table: &Table,
+    request: MaintenanceRequest,
+    now_ms: i64,
+    timeout: Duration,
+) -> Result<MaintenancePlan> {
+    let metadata = table.metadata();
+
+    let (removals, kept) = match &request.operation {
+        MaintenanceOperation::ExpireSnapshots {
+            older_than_days,
+            retain_last,
+        } => expired_snapshots(metadata, *older_than_days, *retain_last, now_ms)?,
+        MaintenanceOperation::RemoveOldMetadata { retain_last } => {
+            old_metadata_files(metadata, *retain_last)
+        }
+        MaintenanceOperation::DeleteOrphanFiles { older_than_days } => {
+            // files younger than a day may belong to a commit in progress
+            if *older_than_days == 0 {
+                return Err(TanicError::UnexpectedError(
+                    "orphan files must be at least a day old to be deleted".to_string(),
+                ));
+            }
+            let cutoff_ms = days_before(now_ms, *older_than_days)?;
+
+            let location = local_path(metadata.location()).ok_or_else(|| {
+                TanicError::UnexpectedError(format!(
+                    "orphan files can only be found in local table locations, not {}",
+                    metadata.location()
+                ))
+            })?;
+
+            let referenced = timed(timeout, referenced_files(table)).await?;
+            let listed = tokio::task::spawn_blocking(move || list_files(&location))
+                .await
+                .map_err(|err| TanicError::UnexpectedError(err.to_string()))??;
+
+            orphan_files(metadata.location(), listed, &referenced, cutoff_ms)
+        }
+    };
+
+    Ok(MaintenancePlan {
+        request,
+        snapshot_id: metadata.current_snapshot_id(),
+        table_uuid: metadata.uuid().to_string(),
+        removals,
+        kept,
+    })
+}
+
+/// Snapshots older than the cutoff, or beyond the most recent
+/// `retain_last`, unless a branch or tag points at them
+pub(crate) fn expired_snapshots(
+    metadata: &TableMetadata,
+    older_than_days: Option<u64>,
+    retain_last: Option<usize>,
+    now_ms: i64,
+) -> Result<(Vec<Removal>, usize)> {
+    let protected = ref_heads(metadata)
+        .into_values()
+        .chain(metadata.current_snapshot_id())
+        .collect::<HashSet<_>>();
+    let cutoff_ms = older_than_days
+        .map(|days| days_before(now_ms, days))
+        .transpose()?;
+
+    let mut snapshots = metadata.snapshots().collect::<Vec<_>>();
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.timestamp_ms()));
+
+    let mut removals = vec![];
+    for (idx, snapshot) in snapshots.iter().enumerate() {
+        let too_old = cutoff_ms.is_some_and(|cutoff_ms| snapshot.timestamp_ms() < cutoff_ms);
+        let beyond_last = retain_last.is_some_and(|retain_last| idx >= retain_last);
+
+        if (too_old || beyond_last) && !protected.contains(&snapshot.snapshot_id()) {
+            removals.push(Removal::Snapshot {
+                id: snapshot.snapshot_id(),
+                committed_at: snapshot
+                    .timestamp()
+                    .map(|ts| ts.format("%F %T UTC").to_string())
+                    .unwrap_or_else(|_| snapshot.timestamp_ms().to_string()),
+                operation: format!("{:?}", snapshot.summary().operation).to_lowercase(),
+            });
+        }
+    }
+    removals.reverse();
+
+    let kept = snapshots.len() - removals.len();
+    Ok((removals, kept))
+}
+
+/// The time `days` days before `now_ms`, unless that is out of range
+fn days_before(now_ms: i64, days: u64) -> Result<i64> {
+    i64::try_from(days)
+        .ok()
+        .and_then(|days| days.checked_mul(MS_PER_DAY))
+        .and_then(|ms| now_ms.checked_sub(ms))
+        .ok_or_else(|| TanicError::UnexpectedError(format!("{days} days ago is out of range")))
+}
+
+/// The snapshot that each branch and tag points at. Iceberg doesn't expose
+/// a table's refs other than by name, so they are read from the metadata's
+/// serialized form.
+fn ref_heads(metadata: &TableMetadata) -> BTreeMap<String, i64> {
+    let refs = serde_json::to_value(metadata)
+        .ok()
+        .and_then(|metadata| metadata.get("refs").cloned())
+        .and_then(|refs| refs.as_object().cloned())
+        .unwrap_or_default();
+
+    refs.into_iter()
+        .filter_map(|(name, reference)| Some((name, reference.get("snapshot-id")?.as_i64()?)))
+        .collect()
+}
+
+/// Metadata files that trimming the metadata log to `retain_last` entries
+/// drops from it. The commit that trims the log adds the current metadata
+/// file to it, so that file is always kept, and counts towards `retain_last`.
+pub(crate) fn old_metadata_files(
+    metadata: &TableMetadata,
+    retain_last: usize,
+) -> (Vec<Removal>, usize) {
+    let mut log = metadata.metadata_log().to_vec();
+    log.sort_by_key(|entry| entry.timestamp_ms);
+
+    // catalogs keep at least one previous metadata file
+    let removed = (log.len() + 1)
+        .saturating_sub(retain_last.max(1))
+        .min(log.len());
+    let removals = log[..removed]
+        .iter()
+        .map(|entry| Removal::File {
+            path: entry.metadata_file.clone(),
+            size_bytes: None,
+        })
+        .collect();
+
+    (removals, log.len() - removed)
+}
+
+/// Every file that the table's metadata references, directly or through
+/// its manifests, as local paths. If any manifest can't be read, what is
+/// referenced isn't known, so this fails rather than risk calling
+/// referenced files orphans.
+async fn referenced_files(table: &Table) -> Result<HashSet<PathBuf>> {
+    let metadata = table.metadata();
+    let file_io = table.file_io();
+
+    let mut referenced = vec![];
+    referenced.extend(table.metadata_location().map(str::to_string));
+    referenced.extend(
+        metadata
+            .metadata_log()
+            .iter()
+            .map(|entry| entry.metadata_file.clone()),
+    );
+    referenced.extend(
+        metadata
+            .statistics_iter()
+            .map(|statistics| statistics.statistics_path.clone()),
+    );
+    referenced.extend(
+        metadata
+            .partition_statistics_iter()
+            .map(|statistics| statistics.statistics_path.clone()),
+    );
+
+    for snapshot in metadata.snapshots() {
+        referenced.push(snapshot.manifest_list().to_string());
+
+        let manifest_list: ManifestList = snapshot.load_manifest_list(file_io, metadata).await?;
+        for manifest_file in manifest_list.entries() {
+            referenced.push(manifest_file.manifest_path.clone());
+
+            let manifest = manifest_file.load_manifest(file_io).await?;
+            referenced.extend(
+                manifest
+                    .entries()
+                    .iter()
+                    .map(|entry| entry.data_file().file_path().to_string()),
+            );
+        }
+    }
+
+    Ok(referenced
+        .iter()
+        .filter_map(|path| local_path(path))
+        .collect())
+}
+
+/// A file found under a table's location
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ListedFile {
+    pub(crate) path: PathBuf,
+    pub(crate) size_bytes: u64,
+    pub(crate) modified_ms: i64,
+}
+
+/// Every file under a directory, recursively
+fn list_files(dir: &Path) -> Result<Vec<ListedFile>> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_metadata = entry.metadata()?;
+
+            if file_metadata.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                let modified_ms = file_metadata
+                    .modified()?
+                    .duration_since(UNIX_EPOCH)
+                    .map(|since_epoch| since_epoch.as_millis() as i64)
+                    .unwrap_or_default();
+                files.push(ListedFile {
+                    path: entry.path(),
+                    size_bytes: file_metadata.len(),
+                    modified_ms,
+                });
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Listed files that nothing references, and that are old enough not to be
+/// part of a commit in progress. The version hint that some catalogs write
+/// is never an orphan.
+pub(crate) fn orphan_files(
+    location: &str,
+    listed: Vec<ListedFile>,
+    referenced: &HashSet<PathBuf>,
+    cutoff_ms: i64,
+) -> (Vec<Removal>, usize) {
+    let total = listed.len();
+
+    let mut orphans = listed
+        .into_iter()
+        .filter(|file| {
+            !referenced.contains(&file.path)
+                && file.modified_ms < cutoff_ms
+                && file.path.file_name() != Some("version-hint.text".as_ref())
+        })
+        .collect::<Vec<_>>();
+    orphans.sort_by(|left, right| {
+        left.modified_ms
+            .cmp(&right.modified_ms)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+
+    // paths are given in the same form as the table's location
+    let scheme = if location.starts_with("file:") {
+        "file://"
+    } else {
+        ""
+    };
+    let removals = orphans
+        .into_iter()
+        .map(|file| Removal::File {
+            path: format!("{scheme}{}", file.path.display()),
+            size_bytes: Some(file.size_bytes),
+        })
+        .collect::<Vec<_>>();
+
+    let kept = total - removals.len();
+    (removals, kept)
+}
+
+/// The local filesystem path of a location, if it is a local one
+pub(crate) fn local_path(location: &str) -> Option<PathBuf> {
+    let path = match location.strip_prefix("file:") {
+        Some(path) => path.trim_start_matches("//"),
+        None => location,
+    };
+
+    path.starts_with('/').then(|| PathBuf::from(path))
+}
+
+/// Remove the snapshots that a plan lists from the table's metadata, as a
+/// single commit. The catalog rejects the commit if the table has been
+/// replaced, or its main branch has moved, since the plan was made.
+pub async fn expire_snapshots(
+    committer: &dyn TableCommitter,
+    table: &Table,
+    plan: &MaintenancePlan,
+) -> Result<MaintenanceOutcome> {
+    let snapshot_ids = plan
+        .removals
+        .iter()
+        .filter_map(|removal| match removal {
+            Removal::Snapshot { id, .. } => Some(*id),
+            Removal::File { .. } => None,
+        })
+        .collect::<Vec<_>>();
+
+    let updates = vec![TableUpdate::RemoveSnapshots {
+        snapshot_ids: snapshot_ids.clone(),
+    }];
+
+    committer
+        .commit(table.identifier(), requirements(plan)?, updates)
+        .await?;
+    for id in &snapshot_ids {
+        tracing::info!(
+            table = plan.request.table.label(),
+            snapshot_id = id,
+            "expired snapshot"
+        );
+    }
+
+    Ok(MaintenanceOutcome {
+        removed: snapshot_ids.len(),
+        failures: vec![],
+    })
+}
+
+/// Trim the table's metadata log to the number of files that a plan to
+/// remove old metadata retains, so that the files it lists can be deleted.
+/// The catalog rejects the commit if the table has been replaced, or its
+/// main branch has moved, since the plan was made.
+///
+/// The log is trimmed by committing a `write.metadata.previous-versions-max`
+/// of the number retained, then the property is put back as it was, so the
+/// table's configuration is left unchanged.
+pub async fn trim_metadata_log(
+    committer: &dyn TableCommitter,
+    table: &Table,
+    plan: &MaintenancePlan,
+) -> Result<()> {
+    let MaintenanceOperation::RemoveOldMetadata { retain_last } = plan.request.operation else {
+        return Err(TanicError::UnexpectedError(format!(
+            "{} does not trim the metadata log",
+            plan.request.operation
+        )));
+    };
+
+    let trim = vec![TableUpdate::SetProperties {
+        updates: HashMap::from([(
+            PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX.to_string(),
+            retain_last.max(1).to_string(),
+        )]),
+    }];
+    committer
+        .commit(table.identifier(), requirements(plan)?, trim)
+        .await?;
+
+    let restore = match table
+        .metadata()
+        .properties()
+        .get(PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX)
+    {
+        Some(previous) => TableUpdate::SetProperties {
+            updates: HashMap::from([(
+                PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX.to_string(),
+                previous.clone(),
+            )]),
+        },
+        None => TableUpdate::RemoveProperties {
+            removals: vec![PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX.to_string()],
+        },
+    };
+    // whatever has been committed to the table since, the property is restored
+    committer
+        .commit(table.identifier(), vec![uuid_match(plan)?], vec![restore])
+        .await
+        .map_err(|err| {
+            TanicError::UnexpectedError(format!(
+                "the metadata log was trimmed, but {PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX} \
+                 could not be restored: {}",
+                crate::iceberg_context::describe(&err)
+            ))
+        })
+}
+
+/// Requirements that a commit made for a plan only applies to the table, and
+/// main branch, that the plan was made against
+fn requirements(plan: &MaintenancePlan) -> Result<Vec<TableRequirement>> {
+    Ok(vec![
+        uuid_match(plan)?,
+        TableRequirement::RefSnapshotIdMatch {
+            r#ref: MAIN_BRANCH.to_string(),
+            snapshot_id: plan.snapshot_id,
+        },
+    ])
+}
+
+/// A requirement that a commit made for a plan only applies to the table
+/// that the plan was made against
+fn uuid_match(plan: &MaintenancePlan) -> Result<TableRequirement> {
+    let uuid = plan
+        .table_uuid
+        .parse()
+        .map_err(|err| TanicError::UnexpectedError(format!("invalid table UUID: {err}")))?;
+
+    Ok(TableRequirement::UuidMatch { uuid })
+}
+
+/// Delete the files that a plan lists, carrying on past any that can't be
+/// deleted. Metadata files that the table's metadata still refers to are
+/// never deleted. Orphan files are deleted from the local filesystem that they
+/// were listed from; metadata files through the table's file IO.
+pub async fn delete_files(table: &Table, plan: &MaintenancePlan) -> MaintenanceOutcome {
+    let mut outcome = MaintenanceOutcome {
+        removed: 0,
+        failures: vec![],
+    };
+
+    let metadata = table.metadata();
+    let still_referenced = metadata
+        .metadata_log()
+        .iter()
+        .map(|entry| entry.metadata_file.as_str())
+        .chain(table.metadata_location())
+        .collect::<HashSet<_>>();
+
+    for removal in &plan.removals {
+        let Removal::File { path, .. } = removal else {
+            continue;
+        };
+
+        let deleted = match &plan.request.operation {
+            _ if still_referenced.contains(path.as_str()) => Err(TanicError::UnexpectedError(
+                "still in the table's metadata log, so kept".to_string(),
+            )),
+            MaintenanceOperation::DeleteOrphanFiles { .. } => match local_path(path) {
+                Some(local) => tokio::fs::remove_file(local)
+                    .await
+                    .map_err(TanicError::from),
+                None => Err(TanicError::UnexpectedError(format!(
+                    "{path} is not a local file"
+                ))),
+            },
+            _ => table.file_io().delete(path).await.map_err(TanicError::from),
+        };
+
+        match deleted {
+            Ok(()) => {
+                tracing::info!(table = plan.request.table.label(), path, "deleted file");
+                outcome.removed += 1;
+            }
+            Err(err) => {
+                let error = crate::iceberg_context::describe(&err);
+                tracing::warn!(
+                    table = plan.request.table.label(),
+                    path,
+                    error,
+                    "could not delete file"
+                );
+                outcome.failures.push(RemovalFailure {
+                    target: path.clone(),
+                    error,
+                });
+            }
+        }
+    }
+
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{fixture_metadata, MemoryCatalog, FIXTURE_TIMESTAMP_MS as NOW_MS};
+    use iceberg::io::FileIOBuilder;
+    use iceberg::spec::{
+        NestedField, PartitionStatisticsFile, PrimitiveType, Schema, SnapshotReference,
+        SnapshotRetention, TableMetadataBuilder, Type,
+    };
+    use iceberg::{Catalog, TableCreation, TableIdent};
+    use std::time::SystemTime;
+    use tanic_core::config::ConnectionDetails;
+
+    /// A table with a snapshot a day for the last `days` days, the latest
+    /// current, and a tag on the oldest
+    fn metadata_with_daily_snapshots(days: i64) -> TableMetadata {
+        let metadata = fixture_metadata(&["sales"], "orders", 0, days).unwrap();
+
+        // tagged in a later commit, as tagging a snapshot that is added in the
+        // same commit dates the commit to the snapshot
+        TableMetadataBuilder::new_from_metadata(metadata, None)
+            .set_ref(
+                "audited",
+                SnapshotReference {
+                    snapshot_id: 1,
+                    retention: SnapshotRetention::Tag {
+                        max_ref_age_ms: None,
+                    },
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata
+    }
+
+    fn snapshot_ids(removals: &[Removal]) -> Vec<String> {
+        removals.iter().map(Removal::target).collect()
+    }
+
+    #[test]
+    fn expires_old_snapshots_but_keeps_those_with_refs() {
+        let metadata = metadata_with_daily_snapshots(10);
+
+        // snapshot 1 is tagged, and snapshot 4 is exactly 6 days old
+        let (removals, kept) = expired_snapshots(&metadata, Some(6), None, NOW_MS).unwrap();
+        assert_eq!(snapshot_ids(&removals), ["2", "3"]);
+        assert_eq!(kept, 8);
+
+        let (removals, _) = expired_snapshots(&metadata, None, Some(8), NOW_MS).unwrap();
+        assert_eq!(snapshot_ids(&removals), ["2"]);
+
+        let (removals, _) = expired_snapshots(&metadata, Some(6), Some(3), NOW_MS).unwrap();
+        assert_eq!(snapshot_ids(&removals), ["2", "3", "4", "5", "6", "7"]);
+
+        // the current snapshot is never expired
+        let (removals, _) = expired_snapshots(&metadata, Some(0), Some(0), NOW_MS + 1).unwrap();
+        assert_eq!(
+            snapshot_ids(&removals),
+            ["2", "3", "4", "5", "6", "7", "8", "9"]
+        );
+
+        // ages too long ago to be a timestamp are rejected, not wrapped
+        assert!(expired_snapshots(&metadata, Some(u64::MAX), None, NOW_MS).is_err());
+        assert!(expired_snapshots(&metadata, Some(1 << 50), None, NOW_MS).is_err());
+    }
+
+    #[test]
+    fn removes_the_metadata_files_that_trimming_the_log_drops() {
+        let location = |version| format!("memory://warehouse/orders/v{version}.metadata.json");
+        let mut metadata = fixture_metadata(&["sales"], "orders", 0, 1).unwrap();
+        for version in 1..=4 {
+            metadata = TableMetadataBuilder::new_from_metadata(metadata, Some(location(version)))
+                .build()
+                .unwrap()
+                .metadata;
+        }
+
+        let (removals, kept) = old_metadata_files(&metadata, 2);
+        let removed = removals.iter().map(Removal::target).collect::<Vec<_>>();
+        assert_eq!(removed, [location(1), location(2), location(3)]);
+        assert_eq!(kept, 1);
+
+        // as the catalog trims the log, when committing the retained count
+        let trimmed = TableMetadataBuilder::new_from_metadata(metadata, Some(location(5)))
+            .set_properties(HashMap::from([(
+                PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX.to_string(),
+                "2".to_string(),
+            )]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+        let log = trimmed
+            .metadata_log()
+            .iter()
+            .map(|entry| entry.metadata_file.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(log, [location(4), location(5)]);
+    }
+
+    #[tokio::test]
+    async fn trims_the_metadata_log_without_changing_the_table_properties() {
+        let ident = TableIdent::from_strs(["sales", "orders"]).unwrap();
+        let previous_versions_max = |table: &Table| {
+            table
+                .metadata()
+                .properties()
+                .get(PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX)
+                .cloned()
+        };
+
+        // a table that doesn't set the property, and one that does
+        for previous in [None, Some("50".to_string())] {
+            let catalog = MemoryCatalog::new().with_table(&["sales"], "orders", 0);
+            if let Some(previous) = &previous {
+                let updates = vec![TableUpdate::SetProperties {
+                    updates: HashMap::from([(
+                        PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX.to_string(),
+                        previous.clone(),
+                    )]),
+                }];
+                catalog.commit(&ident, vec![], updates).await.unwrap();
+            }
+            let table = catalog.load_table(&ident).await.unwrap();
+
+            let request = MaintenanceRequest {
+                table: TableRef {
+                    connection: ConnectionDetails::new_anon("http://prod:8181".parse().unwrap()),
+                    namespace: vec!["sales".to_string()],
+                    name: "orders".to_string(),
+                },
+                operation: MaintenanceOperation::RemoveOldMetadata { retain_last: 2 },
+            };
+            let plan = plan_maintenance(&table, request, NOW_MS, Duration::from_secs(5))
+                .await
+                .unwrap();
+            trim_metadata_log(&catalog, &table, &plan).await.unwrap();
+
+            let table = catalog.load_table(&ident).await.unwrap();
+            assert_eq!(previous_versions_max(&table), previous);
+        }
+    }
+
+    #[test]
+    fn finds_orphans_that_are_old_enough() {
+        let file = |path: &str, modified_days_ago: i64| ListedFile {
+            path: PathBuf::from(path),
+            size_bytes: 100,
+            modified_ms: NOW_MS - modified_days_ago * MS_PER_DAY,
+        };
+        let listed = vec![
+            file("/warehouse/orders/data/referenced.parquet", 10),
+            file("/warehouse/orders/data/orphan.parquet", 10),
+            file("/warehouse/orders/data/in-progress.parquet", 0),
+            file("/warehouse/orders/metadata/version-hint.text", 10),
+        ];
+        let referenced =
+            HashSet::from([
+                local_path("file:///warehouse/orders/data/referenced.parquet").unwrap(),
+            ]);
+
+        let (removals, kept) = orphan_files(
+            "file:///warehouse/orders",
+            listed,
+            &referenced,
+            NOW_MS - 3 * MS_PER_DAY,
+        );
+
+        assert_eq!(
+            removals,
+            [Removal::File {
+                path: "file:///warehouse/orders/data/orphan.parquet".to_string(),
+                size_bytes: Some(100),
+            }]
+        );
+        assert_eq!(kept, 3);
+    }
+
+    #[tokio::test]
+    async fn keeps_partition_statistics_files_when_deleting_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let location = format!("file://{}", dir.path().display());
+        std::fs::create_dir_all(dir.path().join("metadata")).unwrap();
+
+        let partition_stats = dir.path().join("metadata/partition-stats-1.parquet");
+        let orphan = dir.path().join("data/orphan.parquet");
+        std::fs::create_dir_all(dir.path().join("data")).unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(10 * 24 * 60 * 60);
+        for path in [&partition_stats, &orphan] {
+            std::fs::File::create(path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+
+        let schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Long),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        let creation = TableCreation::builder()
+            .name("orders".to_string())
+            .location(location.clone())
+            .schema(schema)
+            .build();
+        let metadata = TableMetadataBuilder::from_table_creation(creation)
+            .unwrap()
+            .set_partition_statistics(PartitionStatisticsFile {
+                snapshot_id: 1,
+                statistics_path: format!("file://{}", partition_stats.display()),
+                file_size_in_bytes: 0,
+            })
+            .build()
+            .unwrap()
+            .metadata;
+        let table = Table::builder()
+            .identifier(TableIdent::from_strs(["sales", "orders"]).unwrap())
+            .metadata(metadata)
+            .file_io(FileIOBuilder::new_fs_io().build().unwrap())
+            .readonly(true)
+            .build()
+            .unwrap();
+
+        let request = MaintenanceRequest {
+            table: TableRef {
+                connection: ConnectionDetails::new_anon("http://prod:8181".parse().unwrap()),
+                namespace: vec!["sales".to_string()],
+                name: "orders".to_string(),
+            },
+            operation: MaintenanceOperation::DeleteOrphanFiles { older_than_days: 3 },
+        };
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let plan = plan_maintenance(&table, request, now_ms, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let removed = plan
+            .removals
+            .iter()
+            .map(Removal::target)
+            .collect::<Vec<_>>();
+        assert_eq!(removed, [format!("file://{}", orphan.display())]);
+        assert_eq!(plan.kept, 1);
+    }
+
+    #[test]
+    fn only_lists_local_locations() {
+        assert_eq!(
+            local_path("file:///tmp/orders"),
+            Some(PathBuf::from("/tmp/orders"))
+        );
+        assert_eq!(
+            local_path("file:/tmp/orders"),
+            Some(PathBuf::from("/tmp/orders"))
+        );
+        assert_eq!(
+            local_path("/tmp/orders"),
+            Some(PathBuf::from("/tmp/orders"))
+        );
+        assert_eq!(local_path("s3://bucket/orders"), None);
+    }
+
+    #[test]
+    fn lists_files_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("data/day=1")).unwrap();
+        std::fs::write(dir.path().join("data/day=1/a.parquet"), b"abc").unwrap();
+        std::fs::write(dir.path().join("v1.metadata.json"), b"{}").unwrap();
+
+        let mut listed = list_files(dir.path()).unwrap();
+        listed.sort_by(|left, right| left.path.cmp(&right.path));
+
+        let paths = listed
+            .iter()
+            .map(|file| (file.path.strip_prefix(dir.path()).unwrap(), file.size_bytes))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                (Path::new("data/day=1/a.parquet"), 3),
+                (Path::new("v1.metadata.json"), 2)
+            ]
+        );
+    }
+}
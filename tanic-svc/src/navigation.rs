@@ -0,0 +1,54 @@
+//! Navigation History
+//!
+//! Back and forward stacks of the views that the user has visited. Each
+//! entry is the full state of the view when it was left, so that returning
+//! to it restores its selection.
+
+use crate::state::TanicAppState;
+
+#[derive(Debug, Default)]
+pub(crate) struct NavigationHistory {
+    back: Vec<TanicAppState>,
+    forward: Vec<TanicAppState>,
+}
+
+impl NavigationHistory {
+    /// Record that `current` was left to open a new view.
+    ///
+    /// Opening a new view discards the forward history.
+    pub(crate) fn push(&mut self, current: TanicAppState) {
+        if current.is_navigable() {
+            self.back.push(current);
+        }
+        self.forward.clear();
+    }
+
+    /// Return to the previous view, if there is one
+    pub(crate) fn back(&mut self, current: TanicAppState) -> TanicAppState {
+        let Some(previous) = self.back.pop() else {
+            return current;
+        };
+
+        if current.is_navigable() {
+            self.forward.push(current);
+        }
+        previous
+    }
+
+    /// Revisit the view that was last navigated back from, if there is one
+    pub(crate) fn forward(&mut self, current: TanicAppState) -> TanicAppState {
+        let Some(next) = self.forward.pop() else {
+            return current;
+        };
+
+        if current.is_navigable() {
+            self.back.push(current);
+        }
+        next
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.back.clear();
+        self.forward.clear();
+    }
+}
@@ -0,0 +1,300 @@
+//! Table Preview
+//!
+//! The first rows of a table along with its schema, either as the table is
+//! now or as it was at an earlier snapshot. Reading a table as of a
+//! snapshot uses the schema that the snapshot was written with.
+//!
+//! Previews can be narrowed to some of the table's columns and to the rows
+//! matching a filter. The filter is pushed down into the scan, and the
+//! number of data files that it pruned is reported alongside the rows.
+
+use arrow_array::RecordBatch;
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use futures::{Stream, StreamExt, TryStreamExt};
+use iceberg::spec::Schema;
+use iceberg::table::Table;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use tanic_core::{Result, TanicError};
+
+use crate::comparison::TableRef;
+use crate::export::SnapshotSelector;
+use crate::filter::RowFilter;
+use crate::iceberg_context::{describe, timed};
+use crate::report::{schema_fields, SchemaField};
+
+/// Most rows read into a preview
+pub const PREVIEW_ROW_LIMIT: usize = 100;
+
+/// A table to preview, and the point in its history to preview it at
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PreviewRequest {
+    pub table: TableRef,
+
+    /// the snapshot to read. The current snapshot is read if `None`.
+    pub as_of: Option<SnapshotSelector>,
+
+    /// columns to read. All columns are read if empty.
+    pub columns: Vec<String>,
+
+    /// only rows matching this are read
+    pub filter: Option<RowFilter>,
+}
+
+impl PreviewRequest {
+    /// A preview of the whole of the table as it is now
+    pub fn current(table: TableRef) -> Self {
+        Self {
+            table,
+            as_of: None,
+            columns: vec![],
+            filter: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TablePreview {
+    pub request: PreviewRequest,
+
+    /// the snapshot that was read. `None` if the table has no snapshots.
+    pub snapshot: Option<PreviewSnapshot>,
+
+    pub schema_id: i32,
+    pub schema: Vec<SchemaField>,
+
+    /// the names of the columns that were read
+    pub columns: Vec<String>,
+
+    /// each row's values, formatted for display
+    pub rows: Vec<Vec<String>>,
+
+    /// why the rows could not be read, if they couldn't
+    pub rows_error: Option<String>,
+
+    /// the data files planned for the scan. `None` if it wasn't planned.
+    pub files: Option<ScannedFiles>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScannedFiles {
+    /// data files that the scan reads
+    pub scanned: usize,
+
+    /// data files in the snapshot
+    pub total: usize,
+}
+
+impl ScannedFiles {
+    /// Data files skipped because the filter ruled them out
+    pub fn pruned(&self) -> usize {
+        self.total.saturating_sub(self.scanned)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PreviewSnapshot {
+    pub id: i64,
+    pub committed_at: String,
+}
+
+impl TablePreview {
+    /// How the point in time that was read is described in view titles
+    pub fn point_in_time(&self) -> String {
+        match (&self.request.as_of, &self.snapshot) {
+            (_, None) => "no snapshots".to_string(),
+            (None, Some(snapshot)) => format!("current, snapshot {}", snapshot.id),
+            (Some(SnapshotSelector::Id(_)), Some(snapshot)) => {
+                format!("as of snapshot {}, {}", snapshot.id, snapshot.committed_at)
+            }
+            (Some(selector), Some(snapshot)) => {
+                format!("as of {selector}, snapshot {}", snapshot.id)
+            }
+        }
+    }
+}
+
+/// Read a table's schema and first rows at the requested snapshot. A
+/// snapshot that can't be found fails the preview, but rows that can't be
+/// read within `timeout` are reported in the preview instead.
+pub async fn preview_table(
+    table: &Table,
+    request: PreviewRequest,
+    timeout: Duration,
+) -> Result<TablePreview> {
+    let metadata = table.metadata();
+    let snapshot = match &request.as_of {
+        Some(selector) => metadata.snapshot_by_id(selector.resolve(table)?),
+        None => metadata.current_snapshot(),
+    };
+
+    let schema = match snapshot {
+        Some(snapshot) => snapshot.schema(metadata)?,
+        None => metadata.current_schema().clone(),
+    };
+    let columns = if request.columns.is_empty() {
+        schema
+            .as_struct()
+            .fields()
+            .iter()
+            .map(|field| field.name.clone())
+            .collect()
+    } else {
+        request.columns.clone()
+    };
+
+    let scanned = match snapshot {
+        Some(snapshot) => {
+            let rows = read_rows(table, snapshot.snapshot_id(), &schema, &request);
+            timed(timeout, rows).await
+        }
+        None => Ok((vec![], None)),
+    };
+    let (rows, files, rows_error) = match scanned {
+        Ok((rows, files)) => (rows, files, None),
+        Err(err) => {
+            let message = describe(&err);
+            tracing::warn!(
+                table = request.table.label(),
+                error = message,
+                "could not read rows"
+            );
+            (vec![], None, Some(message))
+        }
+    };
+
+    Ok(TablePreview {
+        snapshot: snapshot.map(|snapshot| PreviewSnapshot {
+            id: snapshot.snapshot_id(),
+            committed_at: snapshot
+                .timestamp()
+                .map(|ts| ts.format("%F %T UTC").to_string())
+                .unwrap_or_else(|_| snapshot.timestamp_ms().to_string()),
+        }),
+        schema_id: schema.schema_id(),
+        schema: schema_fields(&schema),
+        columns,
+        rows,
+        rows_error,
+        files,
+        request,
+    })
+}
+
+async fn read_rows(
+    table: &Table,
+    snapshot_id: i64,
+    schema: &Schema,
+    request: &PreviewRequest,
+) -> Result<(Vec<Vec<String>>, Option<ScannedFiles>)> {
+    let mut scan = table.scan().snapshot_id(snapshot_id);
+    scan = if request.columns.is_empty() {
+        scan.select_all()
+    } else {
+        scan.select(request.columns.iter())
+    };
+    if let Some(filter) = &request.filter {
+        scan = scan.with_filter(filter.to_predicate(schema)?);
+    }
+    let scan = scan.build()?;
+
+    let scanned = scan
+        .plan_files()
+        .await?
+        .try_fold(0, |n, _| async move { Ok(n + 1) })
+        .await?;
+    let total = match request.filter {
+        Some(_) => {
+            let unfiltered = table.scan().snapshot_id(snapshot_id).build()?;
+            unfiltered
+                .plan_files()
+                .await?
+                .try_fold(0, |n, _| async move { Ok(n + 1) })
+                .await?
+        }
+        None => scanned,
+    };
+
+    let rows = collect_rows(scan.to_arrow().await?, PREVIEW_ROW_LIMIT).await?;
+
+    Ok((rows, Some(ScannedFiles { scanned, total })))
+}
+
+/// Format rows from the batches until they run out or `limit` rows have
+/// been read
+pub(crate) async fn collect_rows(
+    mut batches: impl Stream<Item = iceberg::Result<RecordBatch>> + Unpin,
+    limit: usize,
+) -> Result<Vec<Vec<String>>> {
+    let options = FormatOptions::default().with_null("");
+    let mut rows = vec![];
+
+    while rows.len() < limit {
+        let Some(batch) = batches.next().await else {
+            break;
+        };
+        let batch = batch?;
+
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+
+        let remaining = limit - rows.len();
+        rows.extend((0..batch.num_rows().min(remaining)).map(|row| {
+            formatters
+                .iter()
+                .map(|formatter| formatter.value(row).to_string())
+                .collect()
+        }));
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    fn batch(ids: &[i64]) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(ids.to_vec())),
+                Arc::new(StringArray::from(
+                    ids.iter()
+                        .map(|id| (id % 2 == 0).then(|| format!("name {id}")))
+                        .collect::<Vec<_>>(),
+                )),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn collects_rows_up_to_the_limit() {
+        let batches = futures::stream::iter([Ok(batch(&[1, 2])), Ok(batch(&[3, 4]))]);
+
+        let rows = collect_rows(batches, 3).await.unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec!["1".to_string(), String::new()],
+                vec!["2".to_string(), "name 2".to_string()],
+                vec!["3".to_string(), String::new()],
+            ]
+        );
+    }
+}
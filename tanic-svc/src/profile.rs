@@ -0,0 +1,592 @@
+//! Column Profiles
+//!
+//! Per-column statistics for a table's current snapshot, aggregated from the
+//! column metrics that writers record for each data file in its manifests:
+//! value, null and NaN counts, and lower and upper bounds. No data is read
+//! for these.
+//!
+//! A histogram of one column can be added to a profile. It is sampled from
+//! the first rows of a bounded scan, so describes those rows rather than
+//! the whole table.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::DataType;
+use futures::{Stream, StreamExt};
+use iceberg::spec::{
+    DataContentType, DataFile, Datum, ManifestContentType, PrimitiveLiteral, Schema, Snapshot,
+    StructType, Type,
+};
+use iceberg::table::Table;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tanic_core::{Result, TanicError};
+
+use crate::comparison::{type_name, TableRef};
+use crate::iceberg_context::{describe, timed};
+
+/// Most rows read to sample a histogram
+pub const HISTOGRAM_SAMPLE_ROWS: usize = 10_000;
+
+/// Buckets that a numeric column's values are divided into
+const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Most frequent values listed for a non-numeric column. The rest are
+/// counted together.
+const HISTOGRAM_TOP_VALUES: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileRequest {
+    pub table: TableRef,
+
+    /// the column to sample a histogram of, if any
+    pub histogram: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableProfile {
+    pub request: ProfileRequest,
+
+    /// the snapshot profiled. `None` if the table has no snapshots.
+    pub snapshot_id: Option<i64>,
+
+    /// live data files in the snapshot
+    pub data_files: usize,
+
+    /// records in the live data files
+    pub records: u64,
+
+    /// each primitive column, including those nested in structs
+    pub columns: Vec<ColumnProfile>,
+
+    pub histogram: Option<Histogram>,
+
+    /// why the histogram could not be sampled, if it couldn't
+    pub histogram_error: Option<String>,
+}
+
+impl TableProfile {
+    /// Columns that no data file has metrics for
+    pub fn columns_without_stats(&self) -> impl Iterator<Item = &ColumnProfile> {
+        self.columns.iter().filter(|column| !column.has_stats())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    pub field_id: i32,
+
+    /// the column's name, with any parent structs' names, dot separated
+    pub name: String,
+    pub field_type: String,
+
+    /// data files with value and null counts for the column
+    pub files_with_counts: usize,
+
+    /// data files with lower or upper bounds for the column
+    pub files_with_bounds: usize,
+
+    /// values, including nulls, in the files with counts
+    pub values: u64,
+    pub nulls: u64,
+
+    /// NaN values, for floating point columns whose files count them
+    pub nans: Option<u64>,
+
+    /// the lowest lower bound and highest upper bound across the files.
+    /// Writers may truncate bounds, so these can be wider than the values.
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+impl ColumnProfile {
+    pub fn has_stats(&self) -> bool {
+        self.files_with_counts > 0 || self.files_with_bounds > 0
+    }
+
+    /// The fraction of the counted values that are null
+    pub fn null_ratio(&self) -> Option<f64> {
+        (self.values > 0).then(|| self.nulls as f64 / self.values as f64)
+    }
+}
+
+/// The distribution of a column's values in a sample of its rows
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub column: String,
+
+    /// rows sampled, including those where the column is null
+    pub sampled_rows: usize,
+    pub nulls: usize,
+
+    /// value ranges for numeric columns, or the most frequent values for
+    /// others
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub label: String,
+    pub count: usize,
+}
+
+/// Profile a table's columns as of its current snapshot, giving up on
+/// reads that take longer than `timeout`. A histogram that can't be sampled
+/// is reported in the profile rather than failing it.
+pub async fn profile_table(
+    table: &Table,
+    request: ProfileRequest,
+    timeout: Duration,
+) -> Result<TableProfile> {
+    let metadata = table.metadata();
+    let snapshot = metadata.current_snapshot();
+    let schema = match snapshot {
+        Some(snapshot) => snapshot.schema(metadata)?,
+        None => metadata.current_schema().clone(),
+    };
+
+    let mut stats = ColumnStats::for_schema(&schema);
+    let (data_files, records) = match snapshot {
+        Some(snapshot) => timed(timeout, add_file_metrics(table, snapshot, &mut stats)).await?,
+        None => (0, 0),
+    };
+
+    let sampled = match (snapshot, &request.histogram) {
+        (Some(snapshot), Some(column)) => {
+            let sample = sample_histogram(table, snapshot.snapshot_id(), column);
+            Some(timed(timeout, sample).await)
+        }
+        _ => None,
+    };
+    let (histogram, histogram_error) = match sampled {
+        Some(Ok(histogram)) => (Some(histogram), None),
+        Some(Err(err)) => {
+            let message = describe(&err);
+            tracing::warn!(
+                table = request.table.label(),
+                error = message,
+                "could not sample a histogram"
+            );
+            (None, Some(message))
+        }
+        None => (None, None),
+    };
+
+    Ok(TableProfile {
+        snapshot_id: snapshot.map(|snapshot| snapshot.snapshot_id()),
+        data_files,
+        records,
+        columns: stats.into_iter().map(ColumnStats::finish).collect(),
+        histogram,
+        histogram_error,
+        request,
+    })
+}
+
+/// Add the metrics of each of the snapshot's live data files to `stats`,
+/// returning how many files and records there are
+async fn add_file_metrics(
+    table: &Table,
+    snapshot: &Snapshot,
+    stats: &mut [ColumnStats],
+) -> Result<(usize, u64)> {
+    let (mut data_files, mut records) = (0, 0);
+    let manifest_list = snapshot
+        .load_manifest_list(table.file_io(), table.metadata())
+        .await?;
+
+    for manifest_file in manifest_list.entries() {
+        if manifest_file.content != ManifestContentType::Data {
+            continue;
+        }
+
+        let manifest = manifest_file.load_manifest(table.file_io()).await?;
+        for entry in manifest.entries() {
+            if !entry.is_alive() || entry.content_type() != DataContentType::Data {
+                continue;
+            }
+
+            data_files += 1;
+            records += entry.data_file().record_count();
+            for column in stats.iter_mut() {
+                column.add(entry.data_file());
+            }
+        }
+    }
+
+    Ok((data_files, records))
+}
+
+/// A column's metrics, aggregated over the files added so far
+struct ColumnStats {
+    profile: ColumnProfile,
+    min: Option<Datum>,
+    max: Option<Datum>,
+}
+
+impl ColumnStats {
+    /// Empty stats for each of the schema's primitive columns
+    fn for_schema(schema: &Schema) -> Vec<Self> {
+        let mut stats = vec![];
+        Self::for_struct("", schema.as_struct(), &mut stats);
+        stats
+    }
+
+    fn for_struct(prefix: &str, struct_type: &StructType, stats: &mut Vec<Self>) {
+        for field in struct_type.fields() {
+            let name = format!("{prefix}{}", field.name);
+
+            match field.field_type.as_ref() {
+                Type::Primitive(_) => stats.push(Self {
+                    profile: ColumnProfile {
+                        field_id: field.id,
+                        name,
+                        field_type: type_name(&field.field_type),
+                        files_with_counts: 0,
+                        files_with_bounds: 0,
+                        values: 0,
+                        nulls: 0,
+                        nans: None,
+                        min: None,
+                        max: None,
+                    },
+                    min: None,
+                    max: None,
+                }),
+                Type::Struct(nested) => Self::for_struct(&format!("{name}."), nested, stats),
+                // the elements of lists and maps aren't profiled
+                Type::List(_) | Type::Map(_) => {}
+            }
+        }
+    }
+
+    fn add(&mut self, file: &DataFile) {
+        let field_id = self.profile.field_id;
+        let profile = &mut self.profile;
+
+        if let Some(values) = file.value_counts().get(&field_id) {
+            profile.files_with_counts += 1;
+            profile.values += values;
+            profile.nulls += file.null_value_counts().get(&field_id).unwrap_or(&0);
+        }
+        if let Some(nans) = file.nan_value_counts().get(&field_id) {
+            *profile.nans.get_or_insert(0) += nans;
+        }
+
+        let lower = file.lower_bounds().get(&field_id);
+        let upper = file.upper_bounds().get(&field_id);
+        if lower.is_some() || upper.is_some() {
+            profile.files_with_bounds += 1;
+        }
+        if let Some(lower) = lower {
+            if self.min.as_ref().is_none_or(|min| lower < min) {
+                self.min = Some(lower.clone());
+            }
+        }
+        if let Some(upper) = upper {
+            if self.max.as_ref().is_none_or(|max| upper > max) {
+                self.max = Some(upper.clone());
+            }
+        }
+    }
+
+    fn finish(self) -> ColumnProfile {
+        ColumnProfile {
+            min: self.min.as_ref().map(bound_label),
+            max: self.max.as_ref().map(bound_label),
+            ..self.profile
+        }
+    }
+}
+
+/// A bound as it is shown. Strings are shown without quotes.
+fn bound_label(datum: &Datum) -> String {
+    match datum.literal() {
+        PrimitiveLiteral::String(value) => value.clone(),
+        _ => datum.to_string(),
+    }
+}
+
+async fn sample_histogram(table: &Table, snapshot_id: i64, column: &str) -> Result<Histogram> {
+    let scan = table
+        .scan()
+        .snapshot_id(snapshot_id)
+        .select([column])
+        .build()?;
+
+    histogram(column, scan.to_arrow().await?, HISTOGRAM_SAMPLE_ROWS).await
+}
+
+/// The distribution of the first column's values in the batches, up to
+/// `limit` rows of them
+pub(crate) async fn histogram(
+    column: &str,
+    mut batches: impl Stream<Item = iceberg::Result<RecordBatch>> + Unpin,
+    limit: usize,
+) -> Result<Histogram> {
+    let mut arrays: Vec<ArrayRef> = vec![];
+    let mut sampled_rows = 0;
+
+    while sampled_rows < limit {
+        let Some(batch) = batches.next().await else {
+            break;
+        };
+        let batch = batch?;
+        let Some(array) = batch.columns().first() else {
+            break;
+        };
+
+        let rows = array.len().min(limit - sampled_rows);
+        arrays.push(array.slice(0, rows));
+        sampled_rows += rows;
+    }
+
+    let nulls = arrays.iter().map(|array| array.null_count()).sum();
+    let numeric = arrays
+        .first()
+        .is_some_and(|array| array.data_type().is_numeric());
+    let buckets = if numeric {
+        value_ranges(&arrays)?
+    } else {
+        top_values(&arrays)?
+    };
+
+    Ok(Histogram {
+        column: column.to_string(),
+        sampled_rows,
+        nulls,
+        buckets,
+    })
+}
+
+/// Equal width buckets spanning a numeric column's values
+fn value_ranges(arrays: &[ArrayRef]) -> Result<Vec<HistogramBucket>> {
+    let mut values = vec![];
+    for array in arrays {
+        let array = arrow_cast::cast(array, &DataType::Float64)
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+        values.extend(
+            array
+                .as_primitive::<Float64Type>()
+                .iter()
+                .flatten()
+                .filter(|value| !value.is_nan()),
+        );
+    }
+
+    let Some((min, max)) = values.iter().fold(None, |range, &value| match range {
+        None => Some((value, value)),
+        Some((min, max)) => Some((f64::min(min, value), f64::max(max, value))),
+    }) else {
+        return Ok(vec![]);
+    };
+    if min == max {
+        return Ok(vec![HistogramBucket {
+            label: number_label(min),
+            count: values.len(),
+        }]);
+    }
+
+    let width = (max - min) / HISTOGRAM_BUCKETS as f64;
+    let mut counts = [0; HISTOGRAM_BUCKETS];
+    for value in values {
+        let idx = ((value - min) / width) as usize;
+        counts[idx.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+
+    Ok(counts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, count)| {
+            let low = min + width * idx as f64;
+            let high = min + width * (idx + 1) as f64;
+            HistogramBucket {
+                label: format!("{} – {}", number_label(low), number_label(high)),
+                count,
+            }
+        })
+        .collect())
+}
+
+fn number_label(value: f64) -> String {
+    let label = format!("{value:.2}");
+    label
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// The most frequent of a column's values, most frequent first
+fn top_values(arrays: &[ArrayRef]) -> Result<Vec<HistogramBucket>> {
+    let options = FormatOptions::default();
+    let mut counts = HashMap::<String, usize>::new();
+
+    for array in arrays {
+        let formatter = ArrayFormatter::try_new(array.as_ref(), &options)
+            .map_err(|err| TanicError::UnexpectedError(err.to_string()))?;
+        for row in (0..array.len()).filter(|&row| array.is_valid(row)) {
+            *counts.entry(formatter.value(row).to_string()).or_default() += 1;
+        }
+    }
+
+    let mut buckets = counts
+        .into_iter()
+        .map(|(label, count)| HistogramBucket { label, count })
+        .collect::<Vec<_>>();
+    buckets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+
+    if buckets.len() > HISTOGRAM_TOP_VALUES {
+        let others = buckets
+            .split_off(HISTOGRAM_TOP_VALUES)
+            .iter()
+            .map(|bucket| bucket.count)
+            .sum();
+        buckets.push(HistogramBucket {
+            label: "(others)".to_string(),
+            count: others,
+        });
+    }
+
+    Ok(buckets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int64Array, StringArray};
+    use iceberg::spec::{DataFileBuilder, DataFileFormat, NestedField, PrimitiveType, Struct};
+    use std::sync::Arc;
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(2, "score", Type::Primitive(PrimitiveType::Double)).into(),
+                NestedField::optional(
+                    3,
+                    "address",
+                    Type::Struct(StructType::new(vec![NestedField::optional(
+                        4,
+                        "city",
+                        Type::Primitive(PrimitiveType::String),
+                    )
+                    .into()])),
+                )
+                .into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    fn data_file(ids: (i64, i64), null_scores: u64, cities: Option<(&str, &str)>) -> DataFile {
+        let mut lower_bounds = HashMap::from([(1, Datum::long(ids.0))]);
+        let mut upper_bounds = HashMap::from([(1, Datum::long(ids.1))]);
+        if let Some((low, high)) = cities {
+            lower_bounds.insert(4, Datum::string(low));
+            upper_bounds.insert(4, Datum::string(high));
+        }
+
+        DataFileBuilder::default()
+            .content(DataContentType::Data)
+            .file_path("data.parquet".to_string())
+            .file_format(DataFileFormat::Parquet)
+            .partition(Struct::empty())
+            .record_count(10)
+            .file_size_in_bytes(1024)
+            .value_counts(HashMap::from([(1, 10), (2, 10)]))
+            .null_value_counts(HashMap::from([(1, 0), (2, null_scores)]))
+            .nan_value_counts(HashMap::from([(2, 1)]))
+            .lower_bounds(lower_bounds)
+            .upper_bounds(upper_bounds)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn aggregates_file_metrics_per_column() {
+        let mut stats = ColumnStats::for_schema(&schema());
+        for file in [
+            data_file((5, 20), 2, Some(("Leeds", "York"))),
+            data_file((1, 9), 3, None),
+        ] {
+            for column in &mut stats {
+                column.add(&file);
+            }
+        }
+        let columns = stats
+            .into_iter()
+            .map(ColumnStats::finish)
+            .collect::<Vec<_>>();
+
+        let names = columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["id", "score", "address.city"]);
+
+        let id = &columns[0];
+        assert_eq!((id.values, id.nulls), (20, 0));
+        assert_eq!(
+            (id.min.as_deref(), id.max.as_deref()),
+            (Some("1"), Some("20"))
+        );
+        assert_eq!(id.nans, None);
+
+        let score = &columns[1];
+        assert_eq!(score.null_ratio(), Some(0.25));
+        assert_eq!(score.nans, Some(2));
+        assert_eq!(score.files_with_bounds, 0);
+
+        let city = &columns[2];
+        assert!(city.has_stats());
+        assert_eq!(city.files_with_counts, 0);
+        assert_eq!(city.files_with_bounds, 1);
+        assert_eq!(city.null_ratio(), None);
+        assert_eq!(city.min.as_deref(), Some("Leeds"));
+    }
+
+    fn batch(array: ArrayRef) -> iceberg::Result<RecordBatch> {
+        Ok(RecordBatch::try_from_iter([("column", array)]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn divides_numeric_values_into_ranges() {
+        let batches = futures::stream::iter([
+            batch(Arc::new(Int64Array::from(vec![Some(0), None, Some(10)]))),
+            batch(Arc::new(Int64Array::from((0..100).collect::<Vec<_>>()))),
+        ]);
+
+        let histogram = histogram("id", batches, 50).await.unwrap();
+
+        assert_eq!(histogram.sampled_rows, 50);
+        assert_eq!(histogram.nulls, 1);
+        assert_eq!(histogram.buckets.len(), HISTOGRAM_BUCKETS);
+        assert_eq!(histogram.buckets[0].label, "0 – 4.6");
+        assert_eq!(histogram.buckets[9].label, "41.4 – 46");
+        let counted = histogram.buckets.iter().map(|b| b.count).sum::<usize>();
+        assert_eq!(counted, 49);
+    }
+
+    #[tokio::test]
+    async fn counts_the_most_frequent_values() {
+        let mut cities = vec![Some("Ely"), Some("York"), Some("Ely"), None];
+        let towns = (0..HISTOGRAM_TOP_VALUES)
+            .map(|n| format!("town {n:02}"))
+            .collect::<Vec<_>>();
+        cities.extend(towns.iter().map(|town| Some(town.as_str())));
+        let batches = futures::stream::iter([batch(Arc::new(StringArray::from(cities)))]);
+
+        let histogram = histogram("city", batches, 100).await.unwrap();
+
+        let buckets = histogram
+            .buckets
+            .iter()
+            .map(|bucket| (bucket.label.as_str(), bucket.count))
+            .collect::<Vec<_>>();
+        assert_eq!(buckets.len(), HISTOGRAM_TOP_VALUES + 1);
+        assert_eq!(buckets[..3], [("Ely", 2), ("York", 1), ("town 00", 1)]);
+        assert_eq!(buckets.last(), Some(&("(others)", 2)));
+        assert_eq!(histogram.nulls, 1);
+    }
+}
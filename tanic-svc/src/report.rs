@@ -0,0 +1,805 @@
+//! Metadata Reports
+//!
+//! A written summary of a table's metadata - its schema, partitioning, sort
+//! order, properties, snapshots and data files - or of every table in a
+//! namespace. Reports are rendered as Markdown, for attaching to reviews and
+//! tickets, or as JSON, for tooling.
+
+use iceberg::spec::{DataContentType, ManifestContentType, Schema, StructType, TableMetadata};
+use iceberg::table::Table;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tanic_core::config::ConnectionDetails;
+use tanic_core::{Result, TanicError};
+
+use crate::comparison::{type_name, TableRef};
+
+/// Upper bounds of the data file size buckets, in bytes. Files larger than
+/// the last bound go in a final, unbounded bucket.
+const SIZE_BUCKETS: [u64; 6] = [1 << 20, 16 << 20, 64 << 20, 128 << 20, 256 << 20, 512 << 20];
+
+/// File formats that reports can be written in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportFormat {
+    Markdown,
+    Json,
+}
+
+impl ReportFormat {
+    /// The format that a file's extension suggests, if any
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "json" => Some(ReportFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "json" => Ok(ReportFormat::Json),
+            other => Err(format!(
+                "unknown report format '{other}', expected one of: markdown, json"
+            )),
+        }
+    }
+}
+
+/// What a report covers
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReportTarget {
+    Table(TableRef),
+
+    /// every table in a namespace
+    Namespace {
+        connection: ConnectionDetails,
+        namespace: Vec<String>,
+    },
+}
+
+impl ReportTarget {
+    pub fn connection(&self) -> &ConnectionDetails {
+        match self {
+            ReportTarget::Table(table) => &table.connection,
+            ReportTarget::Namespace { connection, .. } => connection,
+        }
+    }
+
+    /// `<connection name>: <namespace>[.<table>]`
+    pub fn label(&self) -> String {
+        match self {
+            ReportTarget::Table(table) => table.label(),
+            ReportTarget::Namespace {
+                connection,
+                namespace,
+            } => format!("{}: {}", connection.name, namespace.join(".")),
+        }
+    }
+
+    /// File name for the report, relative to the working directory
+    pub fn default_path(&self, format: ReportFormat) -> PathBuf {
+        let name = match self {
+            ReportTarget::Table(table) => {
+                format!("{}.{}", table.namespace.join("."), table.name)
+            }
+            ReportTarget::Namespace { namespace, .. } => namespace.join("."),
+        };
+
+        PathBuf::from(format!("{name}.report.{}", format.extension()))
+    }
+}
+
+/// A report to write, and where to write it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReportRequest {
+    pub target: ReportTarget,
+    pub path: PathBuf,
+    pub format: ReportFormat,
+}
+
+/// The metadata of one or more tables
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub title: String,
+    pub tables: Vec<TableReport>,
+
+    /// tables that could not be loaded, and why
+    pub failures: Vec<TableFailure>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableFailure {
+    pub table: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableReport {
+    pub namespace: Vec<String>,
+    pub name: String,
+    pub location: String,
+    pub uuid: String,
+    pub format_version: String,
+    pub last_updated: String,
+
+    pub schema: Vec<SchemaField>,
+    pub partition_spec: Vec<PartitionField>,
+    pub sort_order: Vec<SortField>,
+    pub properties: BTreeMap<String, String>,
+    pub snapshots: SnapshotReport,
+    pub files: FileCounts,
+
+    /// sizes of the current snapshot's data files. `None` if its manifests
+    /// could not be read.
+    pub file_sizes: Option<SizeDistribution>,
+}
+
+/// A column, with nested columns flattened to dotted names
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SchemaField {
+    pub id: i32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub required: bool,
+    pub doc: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PartitionField {
+    pub name: String,
+    pub transform: String,
+    pub source_column: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SortField {
+    pub source_column: String,
+    pub transform: String,
+    pub direction: String,
+    pub null_order: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SnapshotReport {
+    pub count: usize,
+    pub current: Option<CurrentSnapshot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CurrentSnapshot {
+    pub id: i64,
+    pub committed_at: String,
+    pub operation: String,
+    pub summary: BTreeMap<String, String>,
+}
+
+/// Totals from the current snapshot's summary, where it records them
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct FileCounts {
+    pub data_files: Option<u64>,
+    pub delete_files: Option<u64>,
+    pub position_deletes: Option<u64>,
+    pub equality_deletes: Option<u64>,
+    pub records: Option<u64>,
+    pub total_size_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SizeDistribution {
+    pub count: u64,
+    pub total_bytes: u64,
+    pub min_bytes: u64,
+    pub median_bytes: u64,
+    pub mean_bytes: u64,
+    pub max_bytes: u64,
+    pub buckets: Vec<SizeBucket>,
+}
+
+/// The number of files no larger than `max_bytes`, and larger than the
+/// previous bucket's. The last bucket has no upper bound.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SizeBucket {
+    pub max_bytes: Option<u64>,
+    pub files: u64,
+}
+
+impl SizeDistribution {
+    pub fn new(mut sizes: Vec<u64>) -> Self {
+        sizes.sort_unstable();
+
+        let count = sizes.len() as u64;
+        let total_bytes = sizes.iter().sum();
+
+        let mut buckets = SIZE_BUCKETS
+            .iter()
+            .map(|max| SizeBucket {
+                max_bytes: Some(*max),
+                files: 0,
+            })
+            .chain([SizeBucket {
+                max_bytes: None,
+                files: 0,
+            }])
+            .collect::<Vec<_>>();
+        for size in &sizes {
+            let idx = SIZE_BUCKETS.partition_point(|max| max < size);
+            buckets[idx].files += 1;
+        }
+
+        Self {
+            count,
+            total_bytes,
+            min_bytes: sizes.first().copied().unwrap_or_default(),
+            median_bytes: sizes.get(sizes.len() / 2).copied().unwrap_or_default(),
+            mean_bytes: total_bytes.checked_div(count).unwrap_or_default(),
+            max_bytes: sizes.last().copied().unwrap_or_default(),
+            buckets,
+        }
+    }
+}
+
+impl TableReport {
+    pub fn new(table: &Table, file_sizes: Option<SizeDistribution>) -> Self {
+        let metadata = table.metadata();
+        let ident = table.identifier();
+
+        Self {
+            namespace: ident.namespace().clone().inner(),
+            name: ident.name().to_string(),
+            location: metadata.location().to_string(),
+            uuid: metadata.uuid().to_string(),
+            format_version: metadata.format_version().to_string(),
+            last_updated: metadata
+                .last_updated_timestamp()
+                .map(|ts| ts.format("%F %T UTC").to_string())
+                .unwrap_or_else(|_| metadata.last_updated_ms().to_string()),
+            schema: schema_fields(metadata.current_schema()),
+            partition_spec: partition_fields(metadata),
+            sort_order: sort_fields(metadata),
+            properties: metadata
+                .properties()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            snapshots: snapshots(metadata),
+            files: file_counts(metadata),
+            file_sizes,
+        }
+    }
+}
+
+pub(crate) fn schema_fields(schema: &Schema) -> Vec<SchemaField> {
+    let mut fields = vec![];
+    flatten_struct("", schema.as_struct(), &mut fields);
+    fields
+}
+
+fn flatten_struct(prefix: &str, struct_type: &StructType, fields: &mut Vec<SchemaField>) {
+    for field in struct_type.fields() {
+        let name = format!("{prefix}{}", field.name);
+        fields.push(SchemaField {
+            id: field.id,
+            name: name.clone(),
+            field_type: type_name(&field.field_type),
+            required: field.required,
+            doc: field.doc.clone(),
+        });
+
+        if let iceberg::spec::Type::Struct(nested) = field.field_type.as_ref() {
+            flatten_struct(&format!("{name}."), nested, fields);
+        }
+    }
+}
+
+fn column_name(metadata: &TableMetadata, field_id: i32) -> String {
+    metadata
+        .current_schema()
+        .name_by_field_id(field_id)
+        .unwrap_or("<unknown column>")
+        .to_string()
+}
+
+fn partition_fields(metadata: &TableMetadata) -> Vec<PartitionField> {
+    metadata
+        .default_partition_spec()
+        .fields()
+        .iter()
+        .map(|field| PartitionField {
+            name: field.name.clone(),
+            transform: field.transform.to_string(),
+            source_column: column_name(metadata, field.source_id),
+        })
+        .collect()
+}
+
+fn sort_fields(metadata: &TableMetadata) -> Vec<SortField> {
+    metadata
+        .default_sort_order()
+        .fields
+        .iter()
+        .map(|field| SortField {
+            source_column: column_name(metadata, field.source_id),
+            transform: field.transform.to_string(),
+            direction: field.direction.to_string(),
+            null_order: field.null_order.to_string(),
+        })
+        .collect()
+}
+
+fn snapshots(metadata: &TableMetadata) -> SnapshotReport {
+    let current = metadata.current_snapshot().map(|snapshot| CurrentSnapshot {
+        id: snapshot.snapshot_id(),
+        committed_at: snapshot
+            .timestamp()
+            .map(|ts| ts.format("%F %T UTC").to_string())
+            .unwrap_or_else(|_| snapshot.timestamp_ms().to_string()),
+        operation: format!("{:?}", snapshot.summary().operation).to_lowercase(),
+        summary: snapshot
+            .summary()
+            .additional_properties
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    });
+
+    SnapshotReport {
+        count: metadata.snapshots().len(),
+        current,
+    }
+}
+
+fn file_counts(metadata: &TableMetadata) -> FileCounts {
+    let snapshot_stat = |key: &str| {
+        metadata
+            .current_snapshot()
+            .and_then(|snapshot| snapshot.summary().additional_properties.get(key))
+            .and_then(|val| val.parse::<u64>().ok())
+    };
+
+    FileCounts {
+        data_files: snapshot_stat("total-data-files"),
+        delete_files: snapshot_stat("total-delete-files"),
+        position_deletes: snapshot_stat("total-position-deletes"),
+        equality_deletes: snapshot_stat("total-equality-deletes"),
+        records: snapshot_stat("total-records"),
+        total_size_bytes: snapshot_stat("total-files-size"),
+    }
+}
+
+/// Sizes of the live data files in a table's current snapshot, read from
+/// its manifests
+pub async fn data_file_sizes(table: &Table) -> iceberg::Result<Vec<u64>> {
+    let metadata = table.metadata();
+    let Some(snapshot) = metadata.current_snapshot() else {
+        return Ok(vec![]);
+    };
+
+    let manifest_list = snapshot
+        .load_manifest_list(table.file_io(), metadata)
+        .await?;
+
+    let mut sizes = vec![];
+    for manifest_file in manifest_list.entries() {
+        if manifest_file.content != ManifestContentType::Data {
+            continue;
+        }
+
+        let manifest = manifest_file.load_manifest(table.file_io()).await?;
+        sizes.extend(
+            manifest
+                .entries()
+                .iter()
+                .filter(|entry| entry.is_alive() && entry.content_type() == DataContentType::Data)
+                .map(|entry| entry.file_size_in_bytes()),
+        );
+    }
+
+    Ok(sizes)
+}
+
+impl Report {
+    pub fn render(&self, format: ReportFormat) -> Result<String> {
+        match format {
+            ReportFormat::Markdown => Ok(self.to_markdown()),
+            ReportFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|err| TanicError::UnexpectedError(err.to_string())),
+        }
+    }
+
+    /// Write the report to a file
+    pub fn write(&self, path: &Path, format: ReportFormat) -> Result<()> {
+        std::fs::write(path, self.render(format)?)?;
+        Ok(())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.title);
+
+        for table in &self.tables {
+            table.write_markdown(&mut out);
+        }
+
+        if !self.failures.is_empty() {
+            out.push_str("\n## Tables that could not be loaded\n\n");
+            markdown_table(
+                &mut out,
+                &["Table", "Error"],
+                self.failures
+                    .iter()
+                    .map(|failure| vec![failure.table.clone(), failure.error.clone()]),
+            );
+        }
+
+        out
+    }
+}
+
+impl TableReport {
+    fn write_markdown(&self, out: &mut String) {
+        let optional = |value: Option<u64>| value.map_or("-".to_string(), |v| v.to_string());
+
+        let _ = writeln!(out, "\n## {}.{}\n", self.namespace.join("."), self.name);
+        markdown_table(
+            out,
+            &["", ""],
+            [
+                ("Location", self.location.clone()),
+                ("UUID", self.uuid.clone()),
+                ("Format version", self.format_version.clone()),
+                ("Last updated", self.last_updated.clone()),
+            ]
+            .map(|(key, value)| vec![key.to_string(), value]),
+        );
+
+        out.push_str("\n### Schema\n\n");
+        markdown_table(
+            out,
+            &["ID", "Column", "Type", "Required", "Doc"],
+            self.schema.iter().map(|field| {
+                vec![
+                    field.id.to_string(),
+                    field.name.clone(),
+                    field.field_type.clone(),
+                    if field.required { "yes" } else { "no" }.to_string(),
+                    field.doc.clone().unwrap_or_default(),
+                ]
+            }),
+        );
+
+        out.push_str("\n### Partition Spec\n\n");
+        if self.partition_spec.is_empty() {
+            out.push_str("Unpartitioned\n");
+        } else {
+            markdown_table(
+                out,
+                &["Field", "Transform", "Source Column"],
+                self.partition_spec.iter().map(|field| {
+                    vec![
+                        field.name.clone(),
+                        field.transform.clone(),
+                        field.source_column.clone(),
+                    ]
+                }),
+            );
+        }
+
+        out.push_str("\n### Sort Order\n\n");
+        if self.sort_order.is_empty() {
+            out.push_str("Unsorted\n");
+        } else {
+            markdown_table(
+                out,
+                &["Column", "Transform", "Direction", "Nulls"],
+                self.sort_order.iter().map(|field| {
+                    vec![
+                        field.source_column.clone(),
+                        field.transform.clone(),
+                        field.direction.clone(),
+                        field.null_order.clone(),
+                    ]
+                }),
+            );
+        }
+
+        out.push_str("\n### Properties\n\n");
+        if self.properties.is_empty() {
+            out.push_str("None\n");
+        } else {
+            markdown_table(
+                out,
+                &["Property", "Value"],
+                self.properties
+                    .iter()
+                    .map(|(key, value)| vec![key.clone(), value.clone()]),
+            );
+        }
+
+        out.push_str("\n### Snapshots\n\n");
+        match &self.snapshots.current {
+            None => {
+                let _ = writeln!(out, "{} snapshots, none current", self.snapshots.count);
+            }
+            Some(current) => {
+                markdown_table(
+                    out,
+                    &["", ""],
+                    [
+                        ("Snapshots", self.snapshots.count.to_string()),
+                        ("Current snapshot", current.id.to_string()),
+                        ("Operation", current.operation.clone()),
+                        ("Committed at", current.committed_at.clone()),
+                    ]
+                    .map(|(key, value)| vec![key.to_string(), value]),
+                );
+                out.push('\n');
+                markdown_table(
+                    out,
+                    &["Summary", "Value"],
+                    current
+                        .summary
+                        .iter()
+                        .map(|(key, value)| vec![key.clone(), value.clone()]),
+                );
+            }
+        }
+
+        out.push_str("\n### Files\n\n");
+        markdown_table(
+            out,
+            &["", ""],
+            [
+                ("Data files", optional(self.files.data_files)),
+                ("Delete files", optional(self.files.delete_files)),
+                ("Position deletes", optional(self.files.position_deletes)),
+                ("Equality deletes", optional(self.files.equality_deletes)),
+                ("Records", optional(self.files.records)),
+                (
+                    "Total size",
+                    self.files
+                        .total_size_bytes
+                        .map_or("-".to_string(), format_bytes),
+                ),
+            ]
+            .map(|(key, value)| vec![key.to_string(), value]),
+        );
+
+        out.push_str("\n### Data File Sizes\n\n");
+        match &self.file_sizes {
+            None => out.push_str("The manifests could not be read\n"),
+            Some(sizes) if sizes.count == 0 => out.push_str("No data files\n"),
+            Some(sizes) => {
+                markdown_table(
+                    out,
+                    &["Files", "Min", "Median", "Mean", "Max"],
+                    [vec![
+                        sizes.count.to_string(),
+                        format_bytes(sizes.min_bytes),
+                        format_bytes(sizes.median_bytes),
+                        format_bytes(sizes.mean_bytes),
+                        format_bytes(sizes.max_bytes),
+                    ]],
+                );
+                out.push('\n');
+
+                let mut lower = 0;
+                markdown_table(
+                    out,
+                    &["Size", "Files"],
+                    sizes.buckets.iter().map(|bucket| {
+                        let range = match bucket.max_bytes {
+                            Some(max) if lower == 0 => format!("up to {}", format_bytes(max)),
+                            Some(max) => {
+                                format!("{} to {}", format_bytes(lower), format_bytes(max))
+                            }
+                            None => format!("over {}", format_bytes(lower)),
+                        };
+                        lower = bucket.max_bytes.unwrap_or(lower);
+                        vec![range, bucket.files.to_string()]
+                    }),
+                );
+            }
+        }
+    }
+}
+
+fn markdown_table(out: &mut String, headers: &[&str], rows: impl IntoIterator<Item = Vec<String>>) {
+    let _ = writeln!(out, "| {} |", headers.join(" | "));
+    let _ = writeln!(out, "|{}", "---|".repeat(headers.len()));
+
+    for row in rows {
+        let cells = row
+            .iter()
+            .map(|cell| cell.replace('|', "\\|").replace('\n', " "))
+            .collect::<Vec<_>>();
+        let _ = writeln!(out, "| {} |", cells.join(" | "));
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1 << 20;
+
+    fn table_report() -> TableReport {
+        TableReport {
+            namespace: vec!["sales".to_string()],
+            name: "orders".to_string(),
+            location: "s3://warehouse/sales/orders".to_string(),
+            uuid: "9c12d441-03fe-4693-9a96-a0705ddf69c1".to_string(),
+            format_version: "v2".to_string(),
+            last_updated: "2025-01-01 00:00:00 UTC".to_string(),
+            schema: vec![
+                SchemaField {
+                    id: 1,
+                    name: "id".to_string(),
+                    field_type: "long".to_string(),
+                    required: true,
+                    doc: None,
+                },
+                SchemaField {
+                    id: 2,
+                    name: "note".to_string(),
+                    field_type: "string".to_string(),
+                    required: false,
+                    doc: Some("free text | may be blank".to_string()),
+                },
+            ],
+            partition_spec: vec![PartitionField {
+                name: "id_bucket".to_string(),
+                transform: "bucket[16]".to_string(),
+                source_column: "id".to_string(),
+            }],
+            sort_order: vec![],
+            properties: BTreeMap::from([(
+                "write.format.default".to_string(),
+                "parquet".to_string(),
+            )]),
+            snapshots: SnapshotReport {
+                count: 3,
+                current: Some(CurrentSnapshot {
+                    id: 42,
+                    committed_at: "2025-01-01 00:00:00 UTC".to_string(),
+                    operation: "append".to_string(),
+                    summary: BTreeMap::from([("total-records".to_string(), "10".to_string())]),
+                }),
+            },
+            files: FileCounts {
+                data_files: Some(3),
+                records: Some(10),
+                total_size_bytes: Some(3 * MIB),
+                ..FileCounts::default()
+            },
+            file_sizes: Some(SizeDistribution::new(vec![MIB / 2, 2 * MIB, MIB / 2])),
+        }
+    }
+
+    #[test]
+    fn distributes_file_sizes_into_buckets() {
+        let sizes = SizeDistribution::new(vec![600 * MIB, MIB, 10 * MIB, 20 * MIB, 0]);
+
+        assert_eq!(sizes.count, 5);
+        assert_eq!(sizes.min_bytes, 0);
+        assert_eq!(sizes.median_bytes, 10 * MIB);
+        assert_eq!(sizes.mean_bytes, 631 * MIB / 5);
+        assert_eq!(sizes.max_bytes, 600 * MIB);
+        assert_eq!(
+            sizes
+                .buckets
+                .iter()
+                .map(|bucket| bucket.files)
+                .collect::<Vec<_>>(),
+            vec![2, 1, 1, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn describes_no_files() {
+        let sizes = SizeDistribution::new(vec![]);
+
+        assert_eq!(sizes.count, 0);
+        assert_eq!(sizes.mean_bytes, 0);
+        assert!(sizes.buckets.iter().all(|bucket| bucket.files == 0));
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let report = Report {
+            title: "prod: sales".to_string(),
+            tables: vec![table_report()],
+            failures: vec![TableFailure {
+                table: "refunds".to_string(),
+                error: "timed out".to_string(),
+            }],
+        };
+
+        let markdown = report.to_markdown();
+
+        assert!(markdown.starts_with("# prod: sales\n\n## sales.orders\n"));
+        assert!(markdown.contains("| 2 | note | string | no | free text \\| may be blank |"));
+        assert!(markdown.contains("| id_bucket | bucket[16] | id |"));
+        assert!(markdown.contains("### Sort Order\n\nUnsorted\n"));
+        assert!(markdown.contains("| Snapshots | 3 |\n| Current snapshot | 42 |"));
+        assert!(markdown.contains("| Total size | 3.0 MiB |"));
+        assert!(markdown.contains("| 3 | 512.0 KiB | 512.0 KiB | 1.0 MiB | 2.0 MiB |"));
+        assert!(markdown.contains("| up to 1.0 MiB | 2 |\n| 1.0 MiB to 16.0 MiB | 1 |"));
+        assert!(markdown.contains("| refunds | timed out |"));
+    }
+
+    #[test]
+    fn renders_json() {
+        let report = Report {
+            title: "prod: sales.orders".to_string(),
+            tables: vec![table_report()],
+            failures: vec![],
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(ReportFormat::Json).unwrap()).unwrap();
+
+        let table = &json["tables"][0];
+        assert_eq!(table["schema"][1]["type"], "string");
+        assert_eq!(table["files"]["data_files"], 3);
+        assert_eq!(table["files"]["delete_files"], serde_json::Value::Null);
+        assert_eq!(table["file_sizes"]["median_bytes"], 512 * 1024);
+    }
+
+    #[test]
+    fn names_reports_after_their_target() {
+        let connection = ConnectionDetails::new_anon("http://localhost:8181".parse().unwrap());
+        let namespace = vec!["sales".to_string(), "eu".to_string()];
+
+        let table = ReportTarget::Table(TableRef {
+            connection: connection.clone(),
+            namespace: namespace.clone(),
+            name: "orders".to_string(),
+        });
+        let namespace = ReportTarget::Namespace {
+            connection,
+            namespace,
+        };
+
+        assert_eq!(
+            table.default_path(ReportFormat::Markdown),
+            PathBuf::from("sales.eu.orders.report.md")
+        );
+        assert_eq!(
+            namespace.default_path(ReportFormat::Json),
+            PathBuf::from("sales.eu.report.json")
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Catalog Requests
+//!
+//! Typed requests that the app state sends to the `IcebergContextManager`.
+//! Each request has an ID, and its response comes back as a
+//! `TanicAction::CatalogResponse` carrying the same ID, so that responses to
+//! requests that have since been cancelled or superseded can be ignored.
+
+use serde::{Deserialize, Serialize};
+
+use tanic_core::config::ConnectionDetails;
+use tanic_core::message::NamespaceDeets;
+
+use crate::comparison::TableRef;
+use crate::export::ExportRequest;
+use crate::maintenance::{MaintenancePlan, MaintenanceRequest};
+use crate::preview::PreviewRequest;
+use crate::profile::ProfileRequest;
+use crate::report::{ReportRequest, ReportTarget};
+use crate::state::{TanicAppState, ViewingNamespacesListState, ViewingTablesListState};
+
+/// Identifies a catalog request, so that its response can be matched to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CatalogRequest {
+    /// list a catalog's root namespaces. With `refresh`, any cached listing
+    /// is bypassed.
+    ListNamespaces {
+        connection: ConnectionDetails,
+        refresh: bool,
+    },
+
+    /// list the tables in a namespace. With `refresh`, any cached listing is
+    /// bypassed.
+    ListTables {
+        connection: ConnectionDetails,
+        namespace: NamespaceDeets,
+        refresh: bool,
+    },
+
+    /// load a table's metadata, to fill in its summary stats. With
+    /// `refresh`, any cached summary is bypassed.
+    LoadTable {
+        connection: ConnectionDetails,
+        namespace: Vec<String>,
+        name: String,
+        refresh: bool,
+    },
+
+    /// load two tables, possibly from different catalogs, and compare them
+    CompareTables(Box<TableRef>, Box<TableRef>),
+
+    /// read a table's schema and first rows, as of a snapshot
+    PreviewTable(Box<PreviewRequest>),
+
+    /// plan a filtered scan of a table, as of a snapshot, to explain which
+    /// files it reads
+    PlanScan(Box<PreviewRequest>),
+
+    /// aggregate a table's column metrics, and sample a histogram of a
+    /// column if one is asked for
+    ProfileTable(Box<ProfileRequest>),
+
+    /// tally the delete files in a table's current snapshot
+    InspectDeletes(Box<TableRef>),
+
+    /// run the health checks over a table, or every table in a namespace
+    CheckHealth(Box<ReportTarget>),
+
+    /// plan a maintenance operation on a table as a dry run, listing what it
+    /// would remove
+    PlanMaintenance(Box<MaintenanceRequest>),
+
+    /// remove exactly what a confirmed maintenance plan lists
+    ApplyMaintenance(Box<MaintenancePlan>),
+
+    /// export a table's data to a local file, reporting progress as it goes
+    ExportTable(Box<ExportRequest>),
+
+    /// write a metadata report on a table or namespace to a local file
+    WriteReport(Box<ReportRequest>),
+}
+
+impl CatalogRequest {
+    /// The request that a view in this state is waiting on, if any
+    pub(crate) fn for_state(
+        connection: Option<&ConnectionDetails>,
+        state: &TanicAppState,
+    ) -> Option<Self> {
+        match (state, connection) {
+            (TanicAppState::ConnectingTo(conn_details), _) => {
+                Some(CatalogRequest::ListNamespaces {
+                    connection: conn_details.clone(),
+                    refresh: false,
+                })
+            }
+            (
+                TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+                    refreshing: true,
+                    ..
+                }),
+                Some(connection),
+            ) => Some(CatalogRequest::ListNamespaces {
+                connection: connection.clone(),
+                refresh: true,
+            }),
+            (TanicAppState::RetrievingTableList(namespace), Some(connection)) => {
+                Some(CatalogRequest::ListTables {
+                    connection: connection.clone(),
+                    namespace: namespace.clone(),
+                    refresh: false,
+                })
+            }
+            (
+                TanicAppState::ViewingTablesList(ViewingTablesListState {
+                    namespace,
+                    refreshing: true,
+                    ..
+                }),
+                Some(connection),
+            ) => Some(CatalogRequest::ListTables {
+                connection: connection.clone(),
+                namespace: namespace.clone(),
+                refresh: true,
+            }),
+            (TanicAppState::RetrievingTableComparison(left, right), _) => Some(
+                CatalogRequest::CompareTables(Box::new(left.clone()), Box::new(right.clone())),
+            ),
+            (TanicAppState::RetrievingTablePreview(request, _), _) => {
+                Some(CatalogRequest::PreviewTable(Box::new(request.clone())))
+            }
+            (TanicAppState::RetrievingScanPlan(request), _) => {
+                Some(CatalogRequest::PlanScan(Box::new(request.clone())))
+            }
+            (TanicAppState::RetrievingTableProfile(request), _) => {
+                Some(CatalogRequest::ProfileTable(Box::new(request.clone())))
+            }
+            (TanicAppState::RetrievingDeleteHealth(table), _) => {
+                Some(CatalogRequest::InspectDeletes(Box::new(table.clone())))
+            }
+            (TanicAppState::RetrievingHealthReport(target), _) => {
+                Some(CatalogRequest::CheckHealth(Box::new(target.clone())))
+            }
+            (TanicAppState::RetrievingMaintenancePlan(request), _) => {
+                Some(CatalogRequest::PlanMaintenance(Box::new(request.clone())))
+            }
+            (TanicAppState::ApplyingMaintenance(plan), _) => {
+                Some(CatalogRequest::ApplyMaintenance(Box::new(plan.clone())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a tab in this state still has a use for the request's response
+    pub(crate) fn is_wanted_by(
+        &self,
+        connection: Option<&ConnectionDetails>,
+        state: &TanicAppState,
+    ) -> bool {
+        match (self, state) {
+            (
+                CatalogRequest::LoadTable {
+                    namespace, name, ..
+                },
+                TanicAppState::ViewingTablesList(view_state),
+            ) => {
+                *namespace == view_state.namespace.parts
+                    && view_state.tables.iter().any(|table| table.name == *name)
+            }
+            (request, state) => Self::for_state(connection, state).as_ref() == Some(request),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum CatalogCommand {
+    Request(RequestId, CatalogRequest),
+
+    /// abandon a request, if it is still in flight
+    Cancel(RequestId),
+}
+
+/// Hands out request IDs, and collects the commands to send
+#[derive(Debug, Default)]
+pub(crate) struct CommandQueue {
+    next_id: u64,
+    commands: Vec<CatalogCommand>,
+}
+
+impl CommandQueue {
+    pub(crate) fn request(&mut self, request: CatalogRequest) -> RequestId {
+        let id = RequestId(self.next_id);
+        self.next_id += 1;
+
+        self.commands.push(CatalogCommand::Request(id, request));
+        id
+    }
+
+    pub(crate) fn cancel(&mut self, id: RequestId) {
+        self.commands.push(CatalogCommand::Cancel(id));
+    }
+
+    /// Take the commands queued so far
+    pub(crate) fn drain(&mut self) -> Vec<CatalogCommand> {
+        std::mem::take(&mut self.commands)
+    }
+}
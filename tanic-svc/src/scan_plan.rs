@@ -0,0 +1,602 @@
+//! Scan Planning
+//!
+//! Explains which files a filtered scan of a table would read. The data
+//! files selected come from Iceberg's own planner, `TableScan::plan_files`,
+//! and each file that it leaves out is attributed to pruning on its
+//! partition or on its column metrics by checking the filter against the
+//! file's partition values and column bounds.
+//!
+//! Iceberg's planner doesn't yet support tables with delete files. Plans for
+//! those tables are worked out by the same checks alone, and are marked as
+//! estimated.
+
+use futures::TryStreamExt;
+use iceberg::spec::{
+    DataContentType, DataFile, Datum, Literal, ManifestContentType, PartitionSpec,
+    PrimitiveLiteral, PrimitiveType, Schema, Struct, Transform, Type,
+};
+use iceberg::table::Table;
+use iceberg::transform::create_transform_function;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use tanic_core::Result;
+
+use crate::filter::{self, CompareOp, RowFilter};
+use crate::preview::PreviewRequest;
+
+/// Most files listed under each manifest. Files beyond these are counted
+/// but not listed.
+pub const MAX_LISTED_FILES: usize = 100;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScanPlan {
+    /// the table, point in time and filter that the scan is planned for
+    pub request: PreviewRequest,
+
+    /// the snapshot scanned. `None` if the table has no snapshots.
+    pub snapshot_id: Option<i64>,
+
+    /// the plan was worked out without Iceberg's planner, which doesn't
+    /// support delete files yet
+    pub estimated: bool,
+
+    pub data_files: FileCounts,
+    pub position_deletes: FileCounts,
+    pub equality_deletes: FileCounts,
+
+    pub manifests: Vec<ManifestPlan>,
+}
+
+impl ScanPlan {
+    /// Bytes of data and delete files that the scan reads
+    pub fn bytes_to_read(&self) -> u64 {
+        self.data_files.selected_bytes
+            + self.position_deletes.selected_bytes
+            + self.equality_deletes.selected_bytes
+    }
+
+    /// Manifests with files that the scan reads
+    pub fn matching_manifests(&self) -> usize {
+        self.manifests
+            .iter()
+            .filter(|manifest| manifest.files.selected > 0)
+            .count()
+    }
+}
+
+/// What happened to the files of one kind in a scan plan
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileCounts {
+    pub total: usize,
+    pub selected: usize,
+    pub pruned_by_partition: usize,
+    pub pruned_by_metrics: usize,
+
+    /// size of the selected files
+    pub selected_bytes: u64,
+
+    /// records in the selected files
+    pub selected_records: u64,
+}
+
+impl FileCounts {
+    fn add(&mut self, file: &PlannedFile) {
+        self.total += 1;
+        match file.outcome {
+            FileOutcome::Selected => {
+                self.selected += 1;
+                self.selected_bytes += file.size_bytes;
+                self.selected_records += file.records;
+            }
+            FileOutcome::PrunedByPartition => self.pruned_by_partition += 1,
+            FileOutcome::PrunedByMetrics => self.pruned_by_metrics += 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ManifestPlan {
+    pub path: String,
+
+    /// whether the manifest lists delete files rather than data files
+    pub deletes: bool,
+
+    pub files: FileCounts,
+
+    /// the manifest's first `MAX_LISTED_FILES` live files
+    pub listed: Vec<PlannedFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub content: FileContent,
+
+    /// partition values, as `name=value` pairs
+    pub partition: String,
+
+    pub records: u64,
+    pub size_bytes: u64,
+    pub outcome: FileOutcome,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileContent {
+    Data,
+    PositionDeletes,
+    EqualityDeletes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileOutcome {
+    Selected,
+    PrunedByPartition,
+    PrunedByMetrics,
+}
+
+/// Plan a scan of `table` at the requested point in time with the
+/// requested filter
+pub async fn plan_scan(table: &Table, request: PreviewRequest) -> Result<ScanPlan> {
+    let metadata = table.metadata();
+    let snapshot = match &request.as_of {
+        Some(selector) => metadata.snapshot_by_id(selector.resolve(table)?),
+        None => metadata.current_snapshot(),
+    };
+
+    let mut plan = ScanPlan {
+        snapshot_id: snapshot.map(|snapshot| snapshot.snapshot_id()),
+        estimated: false,
+        data_files: FileCounts::default(),
+        position_deletes: FileCounts::default(),
+        equality_deletes: FileCounts::default(),
+        manifests: vec![],
+        request,
+    };
+    let Some(snapshot) = snapshot else {
+        return Ok(plan);
+    };
+
+    let schema = snapshot.schema(metadata)?;
+    let check = match &plan.request.filter {
+        Some(filter) => Some(Check::new(filter.clone().rewrite_not(), &schema)?),
+        None => None,
+    };
+
+    let manifest_list = snapshot
+        .load_manifest_list(table.file_io(), metadata)
+        .await?;
+    plan.estimated = manifest_list
+        .entries()
+        .iter()
+        .any(|manifest_file| manifest_file.content != ManifestContentType::Data);
+
+    // the data files that Iceberg's planner selects
+    let planned = match (&plan.request.filter, plan.estimated) {
+        (Some(filter), false) => {
+            let scan = table
+                .scan()
+                .snapshot_id(snapshot.snapshot_id())
+                .with_filter(filter.to_predicate(&schema)?)
+                .build()?;
+            let paths = scan
+                .plan_files()
+                .await?
+                .map_ok(|task| task.data_file_path)
+                .try_collect::<HashSet<_>>()
+                .await?;
+            Some(paths)
+        }
+        _ => None,
+    };
+
+    for manifest_file in manifest_list.entries() {
+        let manifest = manifest_file.load_manifest(table.file_io()).await?;
+        let spec = metadata.partition_spec_by_id(manifest_file.partition_spec_id);
+
+        let mut manifest_plan = ManifestPlan {
+            path: manifest_file.manifest_path.clone(),
+            deletes: manifest_file.content != ManifestContentType::Data,
+            files: FileCounts::default(),
+            listed: vec![],
+        };
+
+        for entry in manifest.entries().iter().filter(|entry| entry.is_alive()) {
+            let data_file = entry.data_file();
+            let partition_matches = match (&check, spec) {
+                (Some(check), Some(spec)) => check.partition_matches(spec, &schema, data_file),
+                _ => true,
+            };
+            let metrics_match = match &check {
+                Some(check) => check.metrics_match(data_file),
+                None => true,
+            };
+
+            let content = match data_file.content_type() {
+                DataContentType::Data => FileContent::Data,
+                DataContentType::PositionDeletes => FileContent::PositionDeletes,
+                DataContentType::EqualityDeletes => FileContent::EqualityDeletes,
+            };
+            let outcome = match (content, &planned) {
+                (FileContent::Data, Some(planned)) if planned.contains(data_file.file_path()) => {
+                    FileOutcome::Selected
+                }
+                _ if !partition_matches => FileOutcome::PrunedByPartition,
+                // delete files' metrics describe the deletes, not the rows
+                // that they delete
+                (FileContent::PositionDeletes | FileContent::EqualityDeletes, _) => {
+                    FileOutcome::Selected
+                }
+                (FileContent::Data, Some(_)) => FileOutcome::PrunedByMetrics,
+                (FileContent::Data, None) if !metrics_match => FileOutcome::PrunedByMetrics,
+                (FileContent::Data, None) => FileOutcome::Selected,
+            };
+
+            let file = PlannedFile {
+                path: data_file.file_path().to_string(),
+                content,
+                partition: spec
+                    .map(|spec| partition_label(spec, &schema, data_file.partition()))
+                    .unwrap_or_default(),
+                records: data_file.record_count(),
+                size_bytes: data_file.file_size_in_bytes(),
+                outcome,
+            };
+
+            manifest_plan.files.add(&file);
+            match content {
+                FileContent::Data => plan.data_files.add(&file),
+                FileContent::PositionDeletes => plan.position_deletes.add(&file),
+                FileContent::EqualityDeletes => plan.equality_deletes.add(&file),
+            }
+            if manifest_plan.listed.len() < MAX_LISTED_FILES {
+                manifest_plan.listed.push(file);
+            }
+        }
+
+        plan.manifests.push(manifest_plan);
+    }
+
+    Ok(plan)
+}
+
+/// A filter, with `NOT`s pushed down and its columns and literals resolved
+/// against a schema, to check against files' metadata
+#[derive(Debug)]
+enum Check {
+    Condition { field_id: i32, condition: Condition },
+    And(Box<Check>, Box<Check>),
+    Or(Box<Check>, Box<Check>),
+}
+
+#[derive(Debug)]
+enum Condition {
+    Compare(CompareOp, Datum),
+    In(Vec<Datum>),
+    NotIn,
+    IsNull,
+    IsNotNull,
+}
+
+impl Check {
+    fn new(filter: RowFilter, schema: &Schema) -> Result<Self> {
+        let field_id = |column: &str| -> Result<(i32, PrimitiveType)> {
+            let column_type = filter::column_type(schema, column)?;
+            let field = schema.field_by_name(column).expect("the column exists");
+            Ok((field.id, column_type))
+        };
+
+        let check = match filter {
+            RowFilter::Compare { column, op, value } => {
+                let (field_id, column_type) = field_id(&column)?;
+                let datum = filter::datum(&column, &column_type, &value)?;
+                Check::Condition {
+                    field_id,
+                    condition: Condition::Compare(op, datum),
+                }
+            }
+            RowFilter::In {
+                column,
+                values,
+                negated,
+            } => {
+                let (field_id, column_type) = field_id(&column)?;
+                let condition = if negated {
+                    Condition::NotIn
+                } else {
+                    Condition::In(
+                        values
+                            .iter()
+                            .map(|value| filter::datum(&column, &column_type, value))
+                            .collect::<Result<_>>()?,
+                    )
+                };
+                Check::Condition {
+                    field_id,
+                    condition,
+                }
+            }
+            RowFilter::IsNull { column, negated } => Check::Condition {
+                field_id: field_id(&column)?.0,
+                condition: if negated {
+                    Condition::IsNotNull
+                } else {
+                    Condition::IsNull
+                },
+            },
+            RowFilter::And(left, right) => Check::And(
+                Box::new(Check::new(*left, schema)?),
+                Box::new(Check::new(*right, schema)?),
+            ),
+            RowFilter::Or(left, right) => Check::Or(
+                Box::new(Check::new(*left, schema)?),
+                Box::new(Check::new(*right, schema)?),
+            ),
+            RowFilter::Not(_) => unreachable!("NOTs are rewritten before checking"),
+        };
+
+        Ok(check)
+    }
+
+    /// Whether rows matching a condition could be in the file
+    fn might_match(&self, test: &impl Fn(i32, &Condition) -> bool) -> bool {
+        match self {
+            Check::Condition {
+                field_id,
+                condition,
+            } => test(*field_id, condition),
+            Check::And(left, right) => left.might_match(test) && right.might_match(test),
+            Check::Or(left, right) => left.might_match(test) || right.might_match(test),
+        }
+    }
+
+    /// Whether the file's partition could hold matching rows
+    fn partition_matches(&self, spec: &PartitionSpec, schema: &Schema, file: &DataFile) -> bool {
+        let values = file.partition().iter().collect::<Vec<_>>();
+
+        self.might_match(&|field_id, condition| {
+            spec.fields()
+                .iter()
+                .zip(&values)
+                .filter(|(partition_field, _)| partition_field.source_id == field_id)
+                .all(|(partition_field, value)| {
+                    let Some(source_type) = schema.field_by_id(field_id) else {
+                        return true;
+                    };
+                    let value = value.and_then(|value| {
+                        partition_datum(value, &partition_field.transform, &source_type.field_type)
+                    });
+                    partition_might_match(&partition_field.transform, value, condition)
+                })
+        })
+    }
+
+    /// Whether the file's column bounds and counts allow matching rows
+    fn metrics_match(&self, file: &DataFile) -> bool {
+        self.might_match(&|field_id, condition| {
+            let values = file.value_counts().get(&field_id);
+            let nulls = file.null_value_counts().get(&field_id);
+            let all_null =
+                matches!((values, nulls), (Some(values), Some(nulls)) if values == nulls);
+            let lower = file.lower_bounds().get(&field_id);
+            let upper = file.upper_bounds().get(&field_id);
+
+            match condition {
+                Condition::IsNull => nulls != Some(&0),
+                Condition::IsNotNull | Condition::NotIn => !all_null,
+                Condition::In(datums) => {
+                    !all_null
+                        && datums
+                            .iter()
+                            .any(|datum| bounds_allow(CompareOp::Eq, datum, lower, upper))
+                }
+                Condition::Compare(op, datum) => {
+                    !all_null && bounds_allow(*op, datum, lower, upper)
+                }
+            }
+        })
+    }
+}
+
+/// Whether a column with these bounds could have values that compare with
+/// `datum` as `op` asks. Missing bounds, or bounds of another type, allow
+/// anything.
+fn bounds_allow(
+    op: CompareOp,
+    datum: &Datum,
+    lower: Option<&Datum>,
+    upper: Option<&Datum>,
+) -> bool {
+    let at_least = |bound: Option<&Datum>, ok: fn(std::cmp::Ordering) -> bool| {
+        bound
+            .and_then(|bound| bound.partial_cmp(datum))
+            .is_none_or(ok)
+    };
+
+    match op {
+        CompareOp::Eq => at_least(lower, |o| o.is_le()) && at_least(upper, |o| o.is_ge()),
+        CompareOp::NotEq => !(lower == Some(datum) && upper == Some(datum)),
+        CompareOp::Lt => at_least(lower, |o| o.is_lt()),
+        CompareOp::LtEq => at_least(lower, |o| o.is_le()),
+        CompareOp::Gt => at_least(upper, |o| o.is_gt()),
+        CompareOp::GtEq => at_least(upper, |o| o.is_ge()),
+    }
+}
+
+/// Whether a partition with the transformed value `value` could hold rows
+/// matching `condition`
+fn partition_might_match(
+    transform: &Transform,
+    value: Option<Datum>,
+    condition: &Condition,
+) -> bool {
+    let transformed = |datum: &Datum| {
+        create_transform_function(transform)
+            .and_then(|function| function.transform_literal(datum))
+            .ok()
+            .flatten()
+    };
+
+    match (transform, condition) {
+        (Transform::Void | Transform::Unknown, _) => true,
+        (_, Condition::IsNull) => value.is_none(),
+        (_, Condition::IsNotNull) => value.is_some(),
+        (_, Condition::NotIn) => value.is_some(),
+        (_, Condition::In(datums)) => datums.iter().any(|datum| {
+            partition_might_match(
+                transform,
+                value.clone(),
+                &Condition::Compare(CompareOp::Eq, datum.clone()),
+            )
+        }),
+        (_, Condition::Compare(op, datum)) => {
+            // comparisons never match nulls
+            let Some(value) = value else {
+                return false;
+            };
+            let Some(target) = transformed(datum) else {
+                return true;
+            };
+            let Some(ordering) = value.partial_cmp(&target) else {
+                return true;
+            };
+
+            match (transform, op) {
+                // identity partitions hold exactly the values compared
+                (Transform::Identity, CompareOp::Eq) => ordering.is_eq(),
+                (Transform::Identity, CompareOp::NotEq) => ordering.is_ne(),
+                (Transform::Identity, CompareOp::Lt) => ordering.is_lt(),
+                (Transform::Identity, CompareOp::LtEq) => ordering.is_le(),
+                (Transform::Identity, CompareOp::Gt) => ordering.is_gt(),
+                (Transform::Identity, CompareOp::GtEq) => ordering.is_ge(),
+
+                (_, CompareOp::Eq) => ordering.is_eq(),
+                (_, CompareOp::NotEq) => true,
+
+                // other order preserving transforms map a range of values
+                // to each partition, so only the partition's side matters
+                (Transform::Bucket(_), _) => true,
+                (_, CompareOp::Lt | CompareOp::LtEq) => ordering.is_le(),
+                (_, CompareOp::Gt | CompareOp::GtEq) => ordering.is_ge(),
+            }
+        }
+    }
+}
+
+/// A partition value as a datum of the partition field's type
+fn partition_datum(value: &Literal, transform: &Transform, source_type: &Type) -> Option<Datum> {
+    let Type::Primitive(partition_type) = transform.result_type(source_type).ok()? else {
+        return None;
+    };
+
+    let datum = match (partition_type, value.as_primitive_literal()?) {
+        (PrimitiveType::Boolean, PrimitiveLiteral::Boolean(v)) => Datum::bool(v),
+        (PrimitiveType::Int, PrimitiveLiteral::Int(v)) => Datum::int(v),
+        (PrimitiveType::Date, PrimitiveLiteral::Int(v)) => Datum::date(v),
+        (PrimitiveType::Long, PrimitiveLiteral::Long(v)) => Datum::long(v),
+        (PrimitiveType::Timestamp, PrimitiveLiteral::Long(v)) => Datum::timestamp_micros(v),
+        (PrimitiveType::Timestamptz, PrimitiveLiteral::Long(v)) => Datum::timestamptz_micros(v),
+        (PrimitiveType::Float, PrimitiveLiteral::Float(v)) => Datum::float(v.0),
+        (PrimitiveType::Double, PrimitiveLiteral::Double(v)) => Datum::double(v.0),
+        (PrimitiveType::String, PrimitiveLiteral::String(v)) => Datum::string(v),
+        _ => return None,
+    };
+
+    Some(datum)
+}
+
+/// A file's partition, as `name=value` pairs
+pub(crate) fn partition_label(spec: &PartitionSpec, schema: &Schema, partition: &Struct) -> String {
+    spec.fields()
+        .iter()
+        .zip(partition.iter())
+        .map(|(field, value)| {
+            let value = match value {
+                None => "null".to_string(),
+                Some(value) => schema
+                    .field_by_id(field.source_id)
+                    .and_then(|source| partition_datum(value, &field.transform, &source.field_type))
+                    .map(|datum| datum.to_string())
+                    .unwrap_or_else(|| format!("{value:?}")),
+            };
+            format!("{}={value}", field.name)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(filter: &str) -> Check {
+        let schema = Schema::builder()
+            .with_fields(vec![
+                iceberg::spec::NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long))
+                    .into(),
+                iceberg::spec::NestedField::optional(
+                    2,
+                    "ts",
+                    Type::Primitive(PrimitiveType::Timestamp),
+                )
+                .into(),
+            ])
+            .build()
+            .unwrap();
+
+        Check::new(filter.parse::<RowFilter>().unwrap().rewrite_not(), &schema).unwrap()
+    }
+
+    fn condition(check: &Check) -> &Condition {
+        match check {
+            Check::Condition { condition, .. } => condition,
+            other => panic!("expected a condition, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn checks_bounds_inclusively() {
+        let (lower, upper) = (Datum::long(10), Datum::long(20));
+        let allows = |op, value| bounds_allow(op, &Datum::long(value), Some(&lower), Some(&upper));
+
+        assert!(allows(CompareOp::Eq, 10));
+        assert!(!allows(CompareOp::Eq, 21));
+        assert!(allows(CompareOp::Lt, 11));
+        assert!(!allows(CompareOp::Lt, 10));
+        assert!(allows(CompareOp::GtEq, 20));
+        assert!(!allows(CompareOp::Gt, 20));
+        assert!(allows(CompareOp::NotEq, 10));
+        assert!(bounds_allow(CompareOp::Gt, &Datum::long(99), None, None));
+    }
+
+    #[test]
+    fn checks_transformed_partition_values() {
+        let day = |date: &str| Some(Datum::date_from_str(date).unwrap());
+        let partition = |filter: &str, value| {
+            partition_might_match(&Transform::Day, value, condition(&check(filter)))
+        };
+
+        assert!(partition("ts >= '2024-01-01 12:00'", day("2024-01-01")));
+        assert!(!partition("ts >= '2024-01-02'", day("2024-01-01")));
+        assert!(!partition("ts < '2024-01-01'", day("2024-01-02")));
+        assert!(partition("ts != '2024-01-01'", day("2024-01-01")));
+        assert!(!partition("ts = '2024-01-01'", None));
+        assert!(partition("ts IS NULL", None));
+    }
+
+    #[test]
+    fn checks_identity_partitions_exactly() {
+        let partition = |filter: &str, value: i64| {
+            partition_might_match(
+                &Transform::Identity,
+                Some(Datum::long(value)),
+                condition(&check(filter)),
+            )
+        };
+
+        assert!(partition("id IN (1, 2)", 2));
+        assert!(!partition("id IN (1, 2)", 3));
+        assert!(!partition("NOT id = 3", 3));
+        assert!(!partition("id > 3", 3));
+    }
+}
@@ -1,23 +1,170 @@
+use serde::{Deserialize, Serialize};
+
 use tanic_core::config::ConnectionDetails;
 use tanic_core::message::{NamespaceDeets, TableDeets};
 
-#[derive(Debug)]
+use crate::comparison::{TableComparison, TableRef};
+use crate::deletes::DeleteHealth;
+use crate::export::{ExportRequest, SnapshotSelector};
+use crate::filter::RowFilter;
+use crate::health::HealthReport;
+use crate::maintenance::{MaintenanceOutcome, MaintenancePlan, MaintenanceRequest};
+use crate::preview::{PreviewRequest, TablePreview};
+use crate::profile::{ProfileRequest, TableProfile};
+use crate::report::{ReportFormat, ReportTarget};
+use crate::requests::RequestId;
+use crate::scan_plan::ScanPlan;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TanicAction {
     Exit,
 
     ConnectTo(ConnectionDetails),
 
     RetrievedNamespaceList(Vec<NamespaceDeets>),
+    /// Updated namespaces for the namespace list that is already in view
+    RefreshedNamespaceList(Vec<NamespaceDeets>),
     FocusPrevNamespace,
     FocusNextNamespace,
     SelectNamespace,
 
     RetrievedTableList(NamespaceDeets, Vec<TableDeets>),
-    EnrichedTableDetails(),
+    /// Updated tables for the table list that is already in view
+    RefreshedTableList(NamespaceDeets, Vec<TableDeets>),
+    EnrichedTableDetails(TableDeets),
     FocusPrevTable,
     FocusNextTable,
     SelectTable,
-    LeaveNamespace,
+
+    /// Remember the selected table, to compare against another table later
+    MarkTableForComparison,
+    /// Compare the marked table against the selected table
+    CompareWithMarkedTable,
+    CompareTables(Box<TableRef>, Box<TableRef>),
+    RetrievedTableComparison(Box<TableComparison>),
+    FocusPrevComparisonRow,
+    FocusNextComparisonRow,
+    ToggleComparisonDifferencesOnly,
+
+    /// Preview a table's data and schema
+    OpenTable(Box<TableRef>),
+    RetrievedTablePreview(Box<TablePreview>),
+    /// Read the table in view as of a snapshot, or as it is now if `None`
+    TimeTravel(Option<SnapshotSelector>),
+    /// Read only these columns of the table in view, and only the rows
+    /// matching the filter. Empty columns mean all of them.
+    FilterPreview {
+        columns: Vec<String>,
+        filter: Option<RowFilter>,
+    },
+    /// Switch the table preview between its data and its schema
+    ToggleTableSchema,
+    ScrollPreviewUp,
+    ScrollPreviewDown,
+
+    /// Plan a scan of the table in view, with its point in time and filter,
+    /// to show which files it would read
+    ExplainScan,
+    RetrievedScanPlan(Box<ScanPlan>),
+    ScrollScanPlanUp,
+    ScrollScanPlanDown,
+
+    /// Profile the columns of the selected table, or the table in view
+    ProfileSelectedTable,
+    ProfileTable(Box<ProfileRequest>),
+    RetrievedTableProfile(Box<TableProfile>),
+    FocusPrevProfileColumn,
+    FocusNextProfileColumn,
+    /// Sample a histogram of the selected column in the profile
+    SampleHistogram,
+
+    /// Show the delete files of the selected table, or the table in view
+    InspectSelectedTableDeletes,
+    InspectDeletes(Box<TableRef>),
+    RetrievedDeleteHealth(Box<DeleteHealth>),
+    FocusPrevDeletePartition,
+    FocusNextDeletePartition,
+
+    /// Run the health checks over the selected table, or the table in view
+    CheckSelectedTableHealth,
+    /// Run the health checks over every table in the selected namespace, or
+    /// the namespace in view
+    CheckNamespaceHealth,
+    CheckHealth(Box<ReportTarget>),
+    RetrievedHealthReport(Box<HealthReport>),
+    FocusPrevHealthFinding,
+    FocusNextHealthFinding,
+
+    /// Plan a maintenance operation as a dry run, to show what it would
+    /// remove
+    PlanMaintenance(Box<MaintenanceRequest>),
+    RetrievedMaintenancePlan(Box<MaintenancePlan>),
+    /// Apply the plan in view. The plan is only applied if this is the name
+    /// of its table, typed to confirm.
+    ApplyMaintenance(String),
+    AppliedMaintenance(Box<MaintenanceOutcome>),
+    ScrollMaintenancePlanUp,
+    ScrollMaintenancePlanDown,
+
+    /// Fetch the current view's metadata again, bypassing the cache
+    Refresh,
+
+    /// Abandon the catalog request that the current view is waiting on
+    CancelRequest,
+    /// A catalog request failed or timed out
+    RequestFailed(String),
+
+    NavigateBack,
+    NavigateForward,
+
+    /// Export a table to a local file, in the background
+    ExportTable(Box<ExportRequest>),
+    /// Rows written so far by an export
+    ExportProgressed {
+        id: RequestId,
+        rows_written: u64,
+        rows_expected: Option<u64>,
+    },
+    /// An export finished, having written this many rows
+    ExportFinished(u64),
+    /// Stop any running exports, and clear all exports from view
+    DismissExports,
+
+    /// Write a metadata report on the selected table or namespace to the
+    /// working directory
+    WriteReport(ReportFormat),
+
+    /// Open a connection in a new tab, leaving the active tab unchanged
+    OpenTab(ConnectionDetails),
+    CloseTab,
+    NextTab,
+    PrevTab,
+    SelectTab(usize),
+
+    /// The response to a catalog request, as actions for the tab that made it
+    CatalogResponse(RequestId, Vec<TanicAction>),
+}
+
+impl TanicAction {
+    /// Whether this action opens a child of the current view.
+    ///
+    /// When it does, the current view is kept in the navigation history so
+    /// that it can be returned to.
+    pub(crate) fn opens_child_view(&self) -> bool {
+        matches!(
+            self,
+            TanicAction::SelectNamespace
+                | TanicAction::OpenTable(_)
+                | TanicAction::TimeTravel(_)
+                | TanicAction::FilterPreview { .. }
+                | TanicAction::ExplainScan
+                | TanicAction::ProfileTable(_)
+                | TanicAction::InspectDeletes(_)
+                | TanicAction::CheckHealth(_)
+                | TanicAction::PlanMaintenance(_)
+                | TanicAction::CompareTables(..)
+        )
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -26,30 +173,197 @@ pub enum TanicAppState {
     Initializing,
     ConnectingTo(ConnectionDetails),
     ViewingNamespacesList(ViewingNamespacesListState),
-    RetrievingTableList(ViewingNamespacesListState),
+    RetrievingTableList(NamespaceDeets),
     ViewingTablesList(ViewingTablesListState),
-    Exiting,
+    RetrievingTableComparison(TableRef, TableRef),
+    ViewingTableComparison(ViewingTableComparisonState),
+    RetrievingTablePreview(PreviewRequest, TablePreviewMode),
+    ViewingTablePreview(ViewingTablePreviewState),
+    RetrievingScanPlan(PreviewRequest),
+    ViewingScanPlan(ViewingScanPlanState),
+    RetrievingTableProfile(ProfileRequest),
+    ViewingTableProfile(ViewingTableProfileState),
+    RetrievingDeleteHealth(TableRef),
+    ViewingDeleteHealth(ViewingDeleteHealthState),
+    RetrievingHealthReport(ReportTarget),
+    ViewingHealthReport(ViewingHealthReportState),
+    RetrievingMaintenancePlan(MaintenanceRequest),
+    ViewingMaintenancePlan(ViewingMaintenancePlanState),
+    ApplyingMaintenance(MaintenancePlan),
+
+    /// A catalog request that a view was waiting on failed
+    ShowingError(String),
 }
 
 #[derive(Clone, Debug)]
 pub struct ViewingNamespacesListState {
     pub namespaces: Vec<NamespaceDeets>,
     pub selected_idx: Option<usize>,
+
+    /// a refresh of the namespaces has been requested
+    pub refreshing: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct ViewingTablesListState {
-    pub namespaces: ViewingNamespacesListState,
     pub namespace: NamespaceDeets,
     pub tables: Vec<TableDeets>,
     pub selected_idx: Option<usize>,
+
+    /// a refresh of the tables has been requested
+    pub refreshing: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingTableComparisonState {
+    pub comparison: TableComparison,
+
+    /// index into the visible entries, across all sections
+    pub selected_idx: usize,
+
+    /// only show entries that differ between the two tables
+    pub differences_only: bool,
+}
+
+impl ViewingTableComparisonState {
+    /// The number of entries shown, given the differences-only filter
+    pub fn visible_entry_count(&self) -> usize {
+        self.comparison
+            .sections
+            .iter()
+            .flat_map(|section| &section.entries)
+            .filter(|entry| !self.differences_only || entry.is_different())
+            .count()
+    }
+}
+
+/// What a table preview shows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TablePreviewMode {
+    #[default]
+    Data,
+    Schema,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingTablePreviewState {
+    pub preview: TablePreview,
+    pub mode: TablePreviewMode,
+
+    /// index of the first row, or schema field, in view
+    pub scroll: usize,
+}
+
+impl ViewingTablePreviewState {
+    /// The number of rows, or schema fields, that can be scrolled through
+    pub fn line_count(&self) -> usize {
+        match self.mode {
+            TablePreviewMode::Data => self.preview.rows.len(),
+            TablePreviewMode::Schema => self.preview.schema.len(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingScanPlanState {
+    pub plan: ScanPlan,
+
+    /// index of the first line of the manifest tree in view
+    pub scroll: usize,
+}
+
+impl ViewingScanPlanState {
+    /// The number of lines in the manifest tree: one for each manifest, one
+    /// for each file listed under it, and one for any files not listed
+    pub fn line_count(&self) -> usize {
+        self.plan
+            .manifests
+            .iter()
+            .map(|manifest| {
+                let unlisted = manifest.files.total > manifest.listed.len();
+                1 + manifest.listed.len() + usize::from(unlisted)
+            })
+            .sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingTableProfileState {
+    pub profile: TableProfile,
+    pub selected_idx: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingDeleteHealthState {
+    pub health: DeleteHealth,
+
+    /// index into the partitions with delete files
+    pub selected_idx: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingHealthReportState {
+    pub report: HealthReport,
+
+    /// index into the findings, across all tables
+    pub selected_idx: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ViewingMaintenancePlanState {
+    pub plan: MaintenancePlan,
+
+    /// index of the first removal in view
+    pub scroll: usize,
+
+    /// what applying the plan removed, once it has been applied
+    pub outcome: Option<MaintenanceOutcome>,
+}
+
+impl ViewingMaintenancePlanState {
+    /// Whether the plan has anything to remove, and hasn't been applied yet
+    pub fn is_applicable(&self) -> bool {
+        !self.plan.removals.is_empty() && self.outcome.is_none()
+    }
 }
 
 impl TanicAppState {
+    /// Whether this state is a view that the user can navigate back or
+    /// forward to, as opposed to a transient loading state
+    pub(crate) fn is_navigable(&self) -> bool {
+        matches!(
+            self,
+            TanicAppState::ViewingNamespacesList(_)
+                | TanicAppState::ViewingTablesList(_)
+                | TanicAppState::ViewingTableComparison(_)
+                | TanicAppState::ViewingTablePreview(_)
+                | TanicAppState::ViewingScanPlan(_)
+                | TanicAppState::ViewingTableProfile(_)
+                | TanicAppState::ViewingDeleteHealth(_)
+                | TanicAppState::ViewingHealthReport(_)
+                | TanicAppState::ViewingMaintenancePlan(_)
+        )
+    }
+
+    /// Whether this state is waiting on a catalog request
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            TanicAppState::ConnectingTo(_)
+                | TanicAppState::RetrievingTableList(_)
+                | TanicAppState::RetrievingTableComparison(..)
+                | TanicAppState::RetrievingTablePreview(..)
+                | TanicAppState::RetrievingScanPlan(_)
+                | TanicAppState::RetrievingTableProfile(_)
+                | TanicAppState::RetrievingDeleteHealth(_)
+                | TanicAppState::RetrievingHealthReport(_)
+                | TanicAppState::RetrievingMaintenancePlan(_)
+                | TanicAppState::ApplyingMaintenance(_)
+        )
+    }
+
     pub(crate) fn reduce(self, action: TanicAction) -> Self {
         match (action, &self) {
-            (TanicAction::Exit, _) => TanicAppState::Exiting,
-
             (TanicAction::ConnectTo(conn_details), _) => TanicAppState::ConnectingTo(conn_details),
 
             (TanicAction::RetrievedNamespaceList(namespaces), _) => {
@@ -58,6 +372,25 @@ impl TanicAppState {
                 TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
                     namespaces,
                     selected_idx,
+                    refreshing: false,
+                })
+            }
+
+            (
+                TanicAction::RefreshedNamespaceList(namespaces),
+                TanicAppState::ViewingNamespacesList(view_state),
+            ) => {
+                let selected_idx = reselect(
+                    view_state.selected_idx,
+                    &view_state.namespaces,
+                    &namespaces,
+                    |namespace| &namespace.parts,
+                );
+
+                TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+                    namespaces,
+                    selected_idx,
+                    refreshing: false,
                 })
             }
 
@@ -66,19 +399,15 @@ impl TanicAppState {
                 TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
                     namespaces,
                     selected_idx,
+                    refreshing,
                 }),
             ) => {
-                let selected_idx = selected_idx.map(|selected_idx| {
-                    if selected_idx == 0 {
-                        namespaces.len() - 1
-                    } else {
-                        selected_idx - 1
-                    }
-                });
+                let selected_idx = focus_prev(*selected_idx, namespaces.len());
 
                 TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
                     namespaces: namespaces.clone(),
                     selected_idx,
+                    refreshing: *refreshing,
                 })
             }
 
@@ -87,111 +416,1454 @@ impl TanicAppState {
                 TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
                     namespaces,
                     selected_idx,
+                    refreshing,
                 }),
             ) => {
-                let selected_idx = selected_idx.map(|selected_idx| {
-                    if selected_idx == namespaces.len() - 1 {
-                        0
-                    } else {
-                        selected_idx + 1
-                    }
-                });
+                let selected_idx = focus_next(*selected_idx, namespaces.len());
 
                 TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
                     namespaces: namespaces.clone(),
                     selected_idx,
+                    refreshing: *refreshing,
                 })
             }
 
             (
                 TanicAction::SelectNamespace,
                 TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
-                    selected_idx,
+                    selected_idx: Some(selected_idx),
                     namespaces,
+                    ..
                 }),
-            ) => TanicAppState::RetrievingTableList(ViewingNamespacesListState {
-                selected_idx: *selected_idx,
-                namespaces: namespaces.clone(),
-            }),
+            ) => match namespaces.get(*selected_idx) {
+                Some(namespace) => TanicAppState::RetrievingTableList(namespace.clone()),
+                None => self,
+            },
 
             (
                 TanicAction::RetrievedTableList(namespace, tables),
-                TanicAppState::RetrievingTableList(ViewingNamespacesListState {
-                    selected_idx: namespace_selected_idx,
-                    namespaces,
-                }),
-            ) => {
+                TanicAppState::RetrievingTableList(retrieving),
+            ) if retrieving.parts == namespace.parts => {
                 let table_selected_idx = if tables.is_empty() { None } else { Some(0) };
 
                 TanicAppState::ViewingTablesList(ViewingTablesListState {
-                    namespaces: ViewingNamespacesListState {
-                        selected_idx: *namespace_selected_idx,
-                        namespaces: namespaces.clone(),
-                    },
                     namespace,
                     tables,
                     selected_idx: table_selected_idx,
+                    refreshing: false,
+                })
+            }
+
+            (
+                TanicAction::RefreshedTableList(namespace, tables),
+                TanicAppState::ViewingTablesList(view_state),
+            ) if view_state.namespace.parts == namespace.parts => {
+                let selected_idx = reselect(
+                    view_state.selected_idx,
+                    &view_state.tables,
+                    &tables,
+                    |table| &table.name,
+                );
+
+                TanicAppState::ViewingTablesList(ViewingTablesListState {
+                    namespace,
+                    tables,
+                    selected_idx,
+                    refreshing: false,
                 })
             }
 
             (
                 TanicAction::FocusPrevTable,
                 TanicAppState::ViewingTablesList(ViewingTablesListState {
-                    namespaces,
                     namespace,
                     tables,
                     selected_idx,
+                    refreshing,
                 }),
             ) => {
-                let selected_idx = selected_idx.map(|selected_idx| {
-                    if selected_idx == 0 {
-                        tables.len() - 1
-                    } else {
-                        selected_idx - 1
-                    }
-                });
+                let selected_idx = focus_prev(*selected_idx, tables.len());
 
                 TanicAppState::ViewingTablesList(ViewingTablesListState {
-                    namespaces: namespaces.clone(),
                     namespace: namespace.clone(),
                     tables: tables.clone(),
                     selected_idx,
+                    refreshing: *refreshing,
                 })
             }
 
             (
                 TanicAction::FocusNextTable,
                 TanicAppState::ViewingTablesList(ViewingTablesListState {
-                    namespaces,
                     namespace,
                     tables,
                     selected_idx,
+                    refreshing,
                 }),
             ) => {
-                let selected_idx = selected_idx.map(|selected_idx| {
-                    if selected_idx == tables.len() - 1 {
-                        0
-                    } else {
-                        selected_idx + 1
-                    }
-                });
+                let selected_idx = focus_next(*selected_idx, tables.len());
 
                 TanicAppState::ViewingTablesList(ViewingTablesListState {
-                    namespaces: namespaces.clone(),
                     namespace: namespace.clone(),
                     tables: tables.clone(),
                     selected_idx,
+                    refreshing: *refreshing,
+                })
+            }
+
+            (
+                TanicAction::EnrichedTableDetails(table),
+                TanicAppState::ViewingTablesList(ViewingTablesListState {
+                    namespace,
+                    tables,
+                    selected_idx,
+                    refreshing,
+                }),
+            ) => {
+                let tables = tables
+                    .iter()
+                    .map(|existing| {
+                        if existing.namespace == table.namespace && existing.name == table.name {
+                            table.clone()
+                        } else {
+                            existing.clone()
+                        }
+                    })
+                    .collect();
+
+                TanicAppState::ViewingTablesList(ViewingTablesListState {
+                    namespace: namespace.clone(),
+                    tables,
+                    selected_idx: *selected_idx,
+                    refreshing: *refreshing,
                 })
             }
 
             (TanicAction::SelectTable, _) => self,
 
+            (TanicAction::Refresh, TanicAppState::ViewingNamespacesList(view_state)) => {
+                TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+                    refreshing: true,
+                    ..view_state.clone()
+                })
+            }
+
+            (TanicAction::Refresh, TanicAppState::ViewingTablesList(view_state)) => {
+                TanicAppState::ViewingTablesList(ViewingTablesListState {
+                    refreshing: true,
+                    ..view_state.clone()
+                })
+            }
+
+            (TanicAction::CompareTables(left, right), TanicAppState::ViewingTablesList(_)) => {
+                TanicAppState::RetrievingTableComparison(*left, *right)
+            }
+
+            (
+                TanicAction::RetrievedTableComparison(comparison),
+                TanicAppState::RetrievingTableComparison(left, right),
+            ) if comparison.left == *left && comparison.right == *right => {
+                TanicAppState::ViewingTableComparison(ViewingTableComparisonState {
+                    comparison: *comparison,
+                    selected_idx: 0,
+                    differences_only: false,
+                })
+            }
+
+            (
+                TanicAction::FocusPrevComparisonRow,
+                TanicAppState::ViewingTableComparison(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                view_state.selected_idx = view_state.selected_idx.saturating_sub(1);
+                TanicAppState::ViewingTableComparison(view_state)
+            }
+
+            (
+                TanicAction::FocusNextComparisonRow,
+                TanicAppState::ViewingTableComparison(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.visible_entry_count().saturating_sub(1);
+                view_state.selected_idx = (view_state.selected_idx + 1).min(last_idx);
+                TanicAppState::ViewingTableComparison(view_state)
+            }
+
             (
-                TanicAction::LeaveNamespace,
-                TanicAppState::ViewingTablesList(ViewingTablesListState { namespaces, .. }),
-            ) => TanicAppState::ViewingNamespacesList(namespaces.clone()),
+                TanicAction::ToggleComparisonDifferencesOnly,
+                TanicAppState::ViewingTableComparison(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                view_state.differences_only = !view_state.differences_only;
+                view_state.selected_idx = 0;
+                TanicAppState::ViewingTableComparison(view_state)
+            }
+
+            (TanicAction::OpenTable(table), TanicAppState::ViewingTablesList(_)) => {
+                TanicAppState::RetrievingTablePreview(
+                    PreviewRequest::current(*table),
+                    TablePreviewMode::default(),
+                )
+            }
+
+            (
+                TanicAction::RetrievedTablePreview(preview),
+                TanicAppState::RetrievingTablePreview(request, mode),
+            ) if preview.request == *request => {
+                TanicAppState::ViewingTablePreview(ViewingTablePreviewState {
+                    preview: *preview,
+                    mode: *mode,
+                    scroll: 0,
+                })
+            }
+
+            (TanicAction::TimeTravel(as_of), TanicAppState::ViewingTablePreview(view_state)) => {
+                TanicAppState::RetrievingTablePreview(
+                    PreviewRequest {
+                        as_of,
+                        ..view_state.preview.request.clone()
+                    },
+                    view_state.mode,
+                )
+            }
+
+            (
+                TanicAction::FilterPreview { columns, filter },
+                TanicAppState::ViewingTablePreview(view_state),
+            ) => TanicAppState::RetrievingTablePreview(
+                PreviewRequest {
+                    columns,
+                    filter,
+                    ..view_state.preview.request.clone()
+                },
+                TablePreviewMode::Data,
+            ),
+
+            (TanicAction::Refresh, TanicAppState::ViewingTablePreview(view_state)) => {
+                TanicAppState::RetrievingTablePreview(
+                    view_state.preview.request.clone(),
+                    view_state.mode,
+                )
+            }
+
+            (TanicAction::ToggleTableSchema, TanicAppState::ViewingTablePreview(view_state)) => {
+                let mut view_state = view_state.clone();
+                view_state.mode = match view_state.mode {
+                    TablePreviewMode::Data => TablePreviewMode::Schema,
+                    TablePreviewMode::Schema => TablePreviewMode::Data,
+                };
+                view_state.scroll = 0;
+                TanicAppState::ViewingTablePreview(view_state)
+            }
+
+            (TanicAction::ScrollPreviewUp, TanicAppState::ViewingTablePreview(view_state)) => {
+                let mut view_state = view_state.clone();
+                view_state.scroll = view_state.scroll.saturating_sub(1);
+                TanicAppState::ViewingTablePreview(view_state)
+            }
+
+            (TanicAction::ScrollPreviewDown, TanicAppState::ViewingTablePreview(view_state)) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.line_count().saturating_sub(1);
+                view_state.scroll = (view_state.scroll + 1).min(last_idx);
+                TanicAppState::ViewingTablePreview(view_state)
+            }
+
+            (TanicAction::ExplainScan, TanicAppState::ViewingTablePreview(view_state)) => {
+                TanicAppState::RetrievingScanPlan(view_state.preview.request.clone())
+            }
+
+            (TanicAction::RetrievedScanPlan(plan), TanicAppState::RetrievingScanPlan(request))
+                if plan.request == *request =>
+            {
+                TanicAppState::ViewingScanPlan(ViewingScanPlanState {
+                    plan: *plan,
+                    scroll: 0,
+                })
+            }
+
+            (TanicAction::Refresh, TanicAppState::ViewingScanPlan(view_state)) => {
+                TanicAppState::RetrievingScanPlan(view_state.plan.request.clone())
+            }
+
+            (TanicAction::ScrollScanPlanUp, TanicAppState::ViewingScanPlan(view_state)) => {
+                let mut view_state = view_state.clone();
+                view_state.scroll = view_state.scroll.saturating_sub(1);
+                TanicAppState::ViewingScanPlan(view_state)
+            }
+
+            (TanicAction::ScrollScanPlanDown, TanicAppState::ViewingScanPlan(view_state)) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.line_count().saturating_sub(1);
+                view_state.scroll = (view_state.scroll + 1).min(last_idx);
+                TanicAppState::ViewingScanPlan(view_state)
+            }
+
+            (TanicAction::ProfileTable(request), _) if self.is_navigable() => {
+                TanicAppState::RetrievingTableProfile(*request)
+            }
+
+            (
+                TanicAction::RetrievedTableProfile(profile),
+                TanicAppState::RetrievingTableProfile(request),
+            ) if profile.request == *request => {
+                // the column that a histogram was sampled for stays selected
+                let selected_idx = request
+                    .histogram
+                    .as_ref()
+                    .and_then(|name| {
+                        profile
+                            .columns
+                            .iter()
+                            .position(|column| column.name == *name)
+                    })
+                    .unwrap_or_default();
+
+                TanicAppState::ViewingTableProfile(ViewingTableProfileState {
+                    profile: *profile,
+                    selected_idx,
+                })
+            }
+
+            (TanicAction::Refresh, TanicAppState::ViewingTableProfile(view_state)) => {
+                TanicAppState::RetrievingTableProfile(view_state.profile.request.clone())
+            }
+
+            (
+                TanicAction::FocusPrevProfileColumn,
+                TanicAppState::ViewingTableProfile(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                view_state.selected_idx = view_state.selected_idx.saturating_sub(1);
+                TanicAppState::ViewingTableProfile(view_state)
+            }
+
+            (
+                TanicAction::FocusNextProfileColumn,
+                TanicAppState::ViewingTableProfile(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.profile.columns.len().saturating_sub(1);
+                view_state.selected_idx = (view_state.selected_idx + 1).min(last_idx);
+                TanicAppState::ViewingTableProfile(view_state)
+            }
+
+            (TanicAction::SampleHistogram, TanicAppState::ViewingTableProfile(view_state)) => {
+                let profile = &view_state.profile;
+                match profile.columns.get(view_state.selected_idx) {
+                    Some(column) => TanicAppState::RetrievingTableProfile(ProfileRequest {
+                        histogram: Some(column.name.clone()),
+                        ..profile.request.clone()
+                    }),
+                    None => self,
+                }
+            }
+
+            (TanicAction::InspectDeletes(table), _) if self.is_navigable() => {
+                TanicAppState::RetrievingDeleteHealth(*table)
+            }
+
+            (
+                TanicAction::RetrievedDeleteHealth(health),
+                TanicAppState::RetrievingDeleteHealth(table),
+            ) if health.table == *table => {
+                TanicAppState::ViewingDeleteHealth(ViewingDeleteHealthState {
+                    health: *health,
+                    selected_idx: 0,
+                })
+            }
+
+            (TanicAction::Refresh, TanicAppState::ViewingDeleteHealth(view_state)) => {
+                TanicAppState::RetrievingDeleteHealth(view_state.health.table.clone())
+            }
+
+            (
+                TanicAction::FocusPrevDeletePartition,
+                TanicAppState::ViewingDeleteHealth(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                view_state.selected_idx = view_state.selected_idx.saturating_sub(1);
+                TanicAppState::ViewingDeleteHealth(view_state)
+            }
+
+            (
+                TanicAction::FocusNextDeletePartition,
+                TanicAppState::ViewingDeleteHealth(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.health.partitions.len().saturating_sub(1);
+                view_state.selected_idx = (view_state.selected_idx + 1).min(last_idx);
+                TanicAppState::ViewingDeleteHealth(view_state)
+            }
+
+            (TanicAction::CheckHealth(target), _) if self.is_navigable() => {
+                TanicAppState::RetrievingHealthReport(*target)
+            }
+
+            (
+                TanicAction::RetrievedHealthReport(report),
+                TanicAppState::RetrievingHealthReport(target),
+            ) if report.target == *target => {
+                TanicAppState::ViewingHealthReport(ViewingHealthReportState {
+                    report: *report,
+                    selected_idx: 0,
+                })
+            }
+
+            (TanicAction::Refresh, TanicAppState::ViewingHealthReport(view_state)) => {
+                TanicAppState::RetrievingHealthReport(view_state.report.target.clone())
+            }
+
+            (
+                TanicAction::FocusPrevHealthFinding,
+                TanicAppState::ViewingHealthReport(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                view_state.selected_idx = view_state.selected_idx.saturating_sub(1);
+                TanicAppState::ViewingHealthReport(view_state)
+            }
+
+            (
+                TanicAction::FocusNextHealthFinding,
+                TanicAppState::ViewingHealthReport(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.report.findings().count().saturating_sub(1);
+                view_state.selected_idx = (view_state.selected_idx + 1).min(last_idx);
+                TanicAppState::ViewingHealthReport(view_state)
+            }
+
+            (TanicAction::PlanMaintenance(request), _) if self.is_navigable() => {
+                TanicAppState::RetrievingMaintenancePlan(*request)
+            }
+
+            (
+                TanicAction::RetrievedMaintenancePlan(plan),
+                TanicAppState::RetrievingMaintenancePlan(request),
+            ) if plan.request == *request => {
+                TanicAppState::ViewingMaintenancePlan(ViewingMaintenancePlanState {
+                    plan: *plan,
+                    scroll: 0,
+                    outcome: None,
+                })
+            }
+
+            // plan again, to show what is left after applying
+            (TanicAction::Refresh, TanicAppState::ViewingMaintenancePlan(view_state)) => {
+                TanicAppState::RetrievingMaintenancePlan(view_state.plan.request.clone())
+            }
+
+            (
+                TanicAction::ApplyMaintenance(confirmation),
+                TanicAppState::ViewingMaintenancePlan(view_state),
+            ) if view_state.is_applicable()
+                && confirmation == view_state.plan.request.table.name =>
+            {
+                TanicAppState::ApplyingMaintenance(view_state.plan.clone())
+            }
+
+            (
+                TanicAction::AppliedMaintenance(outcome),
+                TanicAppState::ApplyingMaintenance(plan),
+            ) => TanicAppState::ViewingMaintenancePlan(ViewingMaintenancePlanState {
+                plan: plan.clone(),
+                scroll: 0,
+                outcome: Some(*outcome),
+            }),
+
+            (
+                TanicAction::ScrollMaintenancePlanUp,
+                TanicAppState::ViewingMaintenancePlan(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                view_state.scroll = view_state.scroll.saturating_sub(1);
+                TanicAppState::ViewingMaintenancePlan(view_state)
+            }
+
+            (
+                TanicAction::ScrollMaintenancePlanDown,
+                TanicAppState::ViewingMaintenancePlan(view_state),
+            ) => {
+                let mut view_state = view_state.clone();
+                let last_idx = view_state.plan.removals.len().saturating_sub(1);
+                view_state.scroll = (view_state.scroll + 1).min(last_idx);
+                TanicAppState::ViewingMaintenancePlan(view_state)
+            }
+
+            (TanicAction::RequestFailed(message), _) if self.is_pending() => {
+                TanicAppState::ShowingError(message)
+            }
+
+            // a failed refresh leaves the existing view in place
+            (TanicAction::RequestFailed(_), TanicAppState::ViewingNamespacesList(view_state)) => {
+                TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+                    refreshing: false,
+                    ..view_state.clone()
+                })
+            }
+
+            (TanicAction::RequestFailed(_), TanicAppState::ViewingTablesList(view_state)) => {
+                TanicAppState::ViewingTablesList(ViewingTablesListState {
+                    refreshing: false,
+                    ..view_state.clone()
+                })
+            }
 
             _ => self,
         }
     }
 }
+
+/// Select the previous item of a list, wrapping around to the last. Nothing
+/// is selected in an empty list.
+fn focus_prev(selected_idx: Option<usize>, len: usize) -> Option<usize> {
+    let last_idx = len.checked_sub(1)?;
+
+    selected_idx.map(|selected_idx| {
+        if selected_idx == 0 || selected_idx > last_idx {
+            last_idx
+        } else {
+            selected_idx - 1
+        }
+    })
+}
+
+/// Select the next item of a list, wrapping around to the first. Nothing is
+/// selected in an empty list.
+fn focus_next(selected_idx: Option<usize>, len: usize) -> Option<usize> {
+    let last_idx = len.checked_sub(1)?;
+
+    selected_idx.map(|selected_idx| {
+        if selected_idx >= last_idx {
+            0
+        } else {
+            selected_idx + 1
+        }
+    })
+}
+
+/// Keep the same item selected when a list is replaced, by key, falling back
+/// to the nearest remaining index
+fn reselect<T, K: PartialEq>(
+    selected_idx: Option<usize>,
+    previous: &[T],
+    current: &[T],
+    key: impl Fn(&T) -> &K,
+) -> Option<usize> {
+    if current.is_empty() {
+        return None;
+    }
+
+    let selected_idx = selected_idx.unwrap_or(0);
+    let same_item = previous
+        .get(selected_idx)
+        .and_then(|selected| current.iter().position(|item| key(item) == key(selected)));
+
+    Some(same_item.unwrap_or(selected_idx.min(current.len() - 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comparison::{ComparisonSection, DiffEntry};
+    use crate::maintenance::{MaintenanceOperation, Removal};
+    use crate::profile::ColumnProfile;
+    use tanic_core::message::TableSummary;
+    use uuid::Uuid;
+
+    fn conn_details(name: &str) -> ConnectionDetails {
+        ConnectionDetails {
+            id: Uuid::nil(),
+            name: name.to_string(),
+            uri: format!("http://{name}:8181").parse().unwrap(),
+        }
+    }
+
+    fn namespace(name: &str) -> NamespaceDeets {
+        NamespaceDeets {
+            parts: vec![name.to_string()],
+            name: name.to_string(),
+            table_count: 1,
+            stale: false,
+        }
+    }
+
+    fn table(namespace: &str, name: &str) -> TableDeets {
+        TableDeets {
+            namespace: vec![namespace.to_string()],
+            name: name.to_string(),
+            row_count: 1,
+            summary: None,
+            stale: false,
+        }
+    }
+
+    fn table_ref(connection: &str, name: &str) -> TableRef {
+        TableRef {
+            connection: conn_details(connection),
+            namespace: vec!["ns".to_string()],
+            name: name.to_string(),
+        }
+    }
+
+    fn namespaces_state(names: &[&str], selected_idx: Option<usize>) -> TanicAppState {
+        TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+            namespaces: names.iter().map(|name| namespace(name)).collect(),
+            selected_idx,
+            refreshing: false,
+        })
+    }
+
+    fn tables_state(names: &[&str], selected_idx: Option<usize>) -> TanicAppState {
+        TanicAppState::ViewingTablesList(ViewingTablesListState {
+            namespace: namespace("ns"),
+            tables: names.iter().map(|name| table("ns", name)).collect(),
+            selected_idx,
+            refreshing: false,
+        })
+    }
+
+    fn comparison(differing: usize, matching: usize) -> TableComparison {
+        let entry = |key: String, right: &str| DiffEntry {
+            key,
+            left: Some("a".to_string()),
+            right: Some(right.to_string()),
+        };
+
+        TableComparison {
+            left: table_ref("left", "t"),
+            right: table_ref("right", "t"),
+            left_found: true,
+            right_found: true,
+            sections: vec![ComparisonSection {
+                title: "Overview".to_string(),
+                entries: (0..differing)
+                    .map(|idx| entry(format!("diff-{idx}"), "b"))
+                    .chain((0..matching).map(|idx| entry(format!("same-{idx}"), "a")))
+                    .collect(),
+            }],
+        }
+    }
+
+    fn preview(as_of: Option<SnapshotSelector>) -> TablePreview {
+        TablePreview {
+            request: PreviewRequest {
+                as_of,
+                ..PreviewRequest::current(table_ref("prod", "t"))
+            },
+            snapshot: None,
+            schema_id: 0,
+            schema: vec![],
+            columns: vec!["id".to_string()],
+            rows: (0..3).map(|idx| vec![idx.to_string()]).collect(),
+            rows_error: None,
+            files: None,
+        }
+    }
+
+    fn preview_state(mode: TablePreviewMode, scroll: usize) -> TanicAppState {
+        TanicAppState::ViewingTablePreview(ViewingTablePreviewState {
+            preview: preview(None),
+            mode,
+            scroll,
+        })
+    }
+
+    fn preview_view(state: &TanicAppState) -> &ViewingTablePreviewState {
+        match state {
+            TanicAppState::ViewingTablePreview(view_state) => view_state,
+            other => panic!("expected a table preview, got {other:?}"),
+        }
+    }
+
+    fn comparison_state(comparison: TableComparison, selected_idx: usize) -> TanicAppState {
+        TanicAppState::ViewingTableComparison(ViewingTableComparisonState {
+            comparison,
+            selected_idx,
+            differences_only: false,
+        })
+    }
+
+    fn namespaces_view(state: &TanicAppState) -> &ViewingNamespacesListState {
+        match state {
+            TanicAppState::ViewingNamespacesList(view_state) => view_state,
+            other => panic!("expected a namespace list, got {other:?}"),
+        }
+    }
+
+    fn tables_view(state: &TanicAppState) -> &ViewingTablesListState {
+        match state {
+            TanicAppState::ViewingTablesList(view_state) => view_state,
+            other => panic!("expected a table list, got {other:?}"),
+        }
+    }
+
+    fn comparison_view(state: &TanicAppState) -> &ViewingTableComparisonState {
+        match state {
+            TanicAppState::ViewingTableComparison(view_state) => view_state,
+            other => panic!("expected a table comparison, got {other:?}"),
+        }
+    }
+
+    fn namespace_names(state: &TanicAppState) -> Vec<&str> {
+        namespaces_view(state)
+            .namespaces
+            .iter()
+            .map(|namespace| namespace.name.as_str())
+            .collect()
+    }
+
+    fn table_names(state: &TanicAppState) -> Vec<&str> {
+        tables_view(state)
+            .tables
+            .iter()
+            .map(|table| table.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn connect_to_replaces_any_state() {
+        for state in [
+            TanicAppState::Initializing,
+            namespaces_state(&["a"], Some(0)),
+            tables_state(&["t"], Some(0)),
+            TanicAppState::ShowingError("boom".to_string()),
+        ] {
+            let state = state.reduce(TanicAction::ConnectTo(conn_details("prod")));
+
+            assert!(
+                matches!(&state, TanicAppState::ConnectingTo(conn) if conn.name == "prod"),
+                "{state:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn retrieved_namespace_list_selects_the_first_namespace() {
+        let state = TanicAppState::ConnectingTo(conn_details("prod")).reduce(
+            TanicAction::RetrievedNamespaceList(vec![namespace("a"), namespace("b")]),
+        );
+
+        assert_eq!(namespace_names(&state), ["a", "b"]);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(0));
+        assert!(!namespaces_view(&state).refreshing);
+    }
+
+    #[test]
+    fn retrieved_empty_namespace_list_selects_nothing() {
+        let state = TanicAppState::ConnectingTo(conn_details("prod"))
+            .reduce(TanicAction::RetrievedNamespaceList(vec![]));
+
+        assert!(namespace_names(&state).is_empty());
+        assert_eq!(namespaces_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn refreshed_namespace_list_keeps_the_selected_namespace() {
+        let mut state = namespaces_state(&["a", "b", "c"], Some(1));
+        if let TanicAppState::ViewingNamespacesList(view_state) = &mut state {
+            view_state.refreshing = true;
+        }
+
+        let state = state.reduce(TanicAction::RefreshedNamespaceList(vec![
+            namespace("new"),
+            namespace("a"),
+            namespace("b"),
+        ]));
+
+        assert_eq!(namespace_names(&state), ["new", "a", "b"]);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(2));
+        assert!(!namespaces_view(&state).refreshing);
+    }
+
+    #[test]
+    fn refreshed_namespace_list_without_the_selected_namespace_selects_the_nearest() {
+        let state = namespaces_state(&["a", "b", "c"], Some(2)).reduce(
+            TanicAction::RefreshedNamespaceList(vec![namespace("a"), namespace("b")]),
+        );
+
+        assert_eq!(namespaces_view(&state).selected_idx, Some(1));
+    }
+
+    #[test]
+    fn refreshed_namespace_list_that_is_empty_selects_nothing() {
+        let state = namespaces_state(&["a", "b"], Some(1))
+            .reduce(TanicAction::RefreshedNamespaceList(vec![]));
+
+        assert!(namespace_names(&state).is_empty());
+        assert_eq!(namespaces_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn refreshed_namespace_list_is_ignored_outside_the_namespace_list() {
+        let state = tables_state(&["t"], Some(0))
+            .reduce(TanicAction::RefreshedNamespaceList(vec![namespace("a")]));
+
+        assert_eq!(table_names(&state), ["t"]);
+    }
+
+    #[test]
+    fn focus_next_namespace_advances_and_wraps_around() {
+        let state =
+            namespaces_state(&["a", "b", "c"], Some(1)).reduce(TanicAction::FocusNextNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(2));
+
+        let state = state.reduce(TanicAction::FocusNextNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(0));
+    }
+
+    #[test]
+    fn focus_prev_namespace_retreats_and_wraps_around() {
+        let state =
+            namespaces_state(&["a", "b", "c"], Some(1)).reduce(TanicAction::FocusPrevNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(0));
+
+        let state = state.reduce(TanicAction::FocusPrevNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(2));
+    }
+
+    #[test]
+    fn focus_namespace_with_a_single_namespace_stays_put() {
+        let state = namespaces_state(&["a"], Some(0))
+            .reduce(TanicAction::FocusNextNamespace)
+            .reduce(TanicAction::FocusPrevNamespace);
+
+        assert_eq!(namespaces_view(&state).selected_idx, Some(0));
+    }
+
+    #[test]
+    fn focus_namespace_in_an_empty_list_selects_nothing() {
+        let state = namespaces_state(&[], None)
+            .reduce(TanicAction::FocusNextNamespace)
+            .reduce(TanicAction::FocusPrevNamespace);
+
+        assert_eq!(namespaces_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn focus_namespace_with_a_stale_selection_in_an_empty_list_selects_nothing() {
+        let state = namespaces_state(&[], Some(0)).reduce(TanicAction::FocusPrevNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, None);
+
+        let state = namespaces_state(&[], Some(3)).reduce(TanicAction::FocusNextNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn focus_namespace_with_a_selection_past_the_end_wraps_around() {
+        let state = namespaces_state(&["a", "b"], Some(5)).reduce(TanicAction::FocusNextNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(0));
+
+        let state = namespaces_state(&["a", "b"], Some(5)).reduce(TanicAction::FocusPrevNamespace);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(1));
+    }
+
+    #[test]
+    fn focus_namespace_keeps_refreshing() {
+        let state = namespaces_state(&["a", "b"], Some(0))
+            .reduce(TanicAction::Refresh)
+            .reduce(TanicAction::FocusNextNamespace);
+
+        assert!(namespaces_view(&state).refreshing);
+    }
+
+    #[test]
+    fn select_namespace_retrieves_its_tables() {
+        let state = namespaces_state(&["a", "b"], Some(1)).reduce(TanicAction::SelectNamespace);
+
+        assert!(
+            matches!(&state, TanicAppState::RetrievingTableList(namespace) if namespace.name == "b"),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn select_namespace_without_a_selection_does_nothing() {
+        let state = namespaces_state(&[], None).reduce(TanicAction::SelectNamespace);
+
+        assert_eq!(namespaces_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn retrieved_table_list_selects_the_first_table() {
+        let state = TanicAppState::RetrievingTableList(namespace("ns")).reduce(
+            TanicAction::RetrievedTableList(
+                namespace("ns"),
+                vec![table("ns", "t1"), table("ns", "t2")],
+            ),
+        );
+
+        assert_eq!(table_names(&state), ["t1", "t2"]);
+        assert_eq!(tables_view(&state).selected_idx, Some(0));
+        assert_eq!(tables_view(&state).namespace.name, "ns");
+    }
+
+    #[test]
+    fn retrieved_empty_table_list_selects_nothing() {
+        let state = TanicAppState::RetrievingTableList(namespace("ns"))
+            .reduce(TanicAction::RetrievedTableList(namespace("ns"), vec![]));
+
+        assert!(table_names(&state).is_empty());
+        assert_eq!(tables_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn retrieved_table_list_for_another_namespace_is_ignored() {
+        let state = TanicAppState::RetrievingTableList(namespace("ns")).reduce(
+            TanicAction::RetrievedTableList(namespace("other"), vec![table("other", "t")]),
+        );
+
+        assert!(
+            matches!(&state, TanicAppState::RetrievingTableList(namespace) if namespace.name == "ns"),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn refreshed_table_list_keeps_the_selected_table() {
+        let state = tables_state(&["t1", "t2"], Some(1))
+            .reduce(TanicAction::Refresh)
+            .reduce(TanicAction::RefreshedTableList(
+                namespace("ns"),
+                vec![table("ns", "t2"), table("ns", "t3")],
+            ));
+
+        assert_eq!(table_names(&state), ["t2", "t3"]);
+        assert_eq!(tables_view(&state).selected_idx, Some(0));
+        assert!(!tables_view(&state).refreshing);
+    }
+
+    #[test]
+    fn refreshed_table_list_that_is_empty_selects_nothing() {
+        let state = tables_state(&["t1"], Some(0))
+            .reduce(TanicAction::RefreshedTableList(namespace("ns"), vec![]));
+
+        assert_eq!(tables_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn refreshed_table_list_for_another_namespace_is_ignored() {
+        let state = tables_state(&["t1"], Some(0)).reduce(TanicAction::RefreshedTableList(
+            namespace("other"),
+            vec![table("other", "x")],
+        ));
+
+        assert_eq!(table_names(&state), ["t1"]);
+    }
+
+    #[test]
+    fn focus_next_table_advances_and_wraps_around() {
+        let state = tables_state(&["t1", "t2"], Some(0)).reduce(TanicAction::FocusNextTable);
+        assert_eq!(tables_view(&state).selected_idx, Some(1));
+
+        let state = state.reduce(TanicAction::FocusNextTable);
+        assert_eq!(tables_view(&state).selected_idx, Some(0));
+    }
+
+    #[test]
+    fn focus_prev_table_retreats_and_wraps_around() {
+        let state = tables_state(&["t1", "t2"], Some(1)).reduce(TanicAction::FocusPrevTable);
+        assert_eq!(tables_view(&state).selected_idx, Some(0));
+
+        let state = state.reduce(TanicAction::FocusPrevTable);
+        assert_eq!(tables_view(&state).selected_idx, Some(1));
+    }
+
+    #[test]
+    fn focus_table_in_an_empty_list_selects_nothing() {
+        let state = tables_state(&[], None)
+            .reduce(TanicAction::FocusNextTable)
+            .reduce(TanicAction::FocusPrevTable);
+
+        assert_eq!(tables_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn focus_table_with_a_stale_selection_in_an_empty_list_selects_nothing() {
+        let state = tables_state(&[], Some(0))
+            .reduce(TanicAction::FocusNextTable)
+            .reduce(TanicAction::FocusPrevTable);
+
+        assert_eq!(tables_view(&state).selected_idx, None);
+    }
+
+    #[test]
+    fn select_namespace_with_a_selection_past_the_end_does_nothing() {
+        let state = namespaces_state(&["a"], Some(3)).reduce(TanicAction::SelectNamespace);
+
+        assert_eq!(namespace_names(&state), ["a"]);
+    }
+
+    #[test]
+    fn enriched_table_details_replace_only_that_table() {
+        let mut enriched = table("ns", "t2");
+        enriched.summary = Some(TableSummary {
+            total_size_bytes: Some(1024),
+            data_file_count: Some(2),
+            record_count: Some(10),
+            last_updated_ms: 0,
+            findings: vec![],
+        });
+
+        let state = tables_state(&["t1", "t2"], Some(0))
+            .reduce(TanicAction::EnrichedTableDetails(enriched));
+
+        let tables = &tables_view(&state).tables;
+        assert!(tables[0].summary.is_none());
+        assert_eq!(
+            tables[1]
+                .summary
+                .as_ref()
+                .and_then(|summary| summary.record_count),
+            Some(10)
+        );
+        assert_eq!(tables_view(&state).selected_idx, Some(0));
+    }
+
+    #[test]
+    fn select_table_does_nothing() {
+        let state = tables_state(&["t1", "t2"], Some(1)).reduce(TanicAction::SelectTable);
+
+        assert_eq!(tables_view(&state).selected_idx, Some(1));
+    }
+
+    #[test]
+    fn open_table_from_a_table_list_retrieves_its_preview() {
+        let state = tables_state(&["t"], Some(0))
+            .reduce(TanicAction::OpenTable(Box::new(table_ref("prod", "t"))));
+
+        assert!(
+            matches!(
+                &state,
+                TanicAppState::RetrievingTablePreview(request, TablePreviewMode::Data)
+                    if request.table.name == "t" && request.as_of.is_none()
+            ),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn time_travel_retrieves_the_table_as_of_a_snapshot_in_the_same_mode() {
+        let as_of = Some(SnapshotSelector::Ref("audit".to_string()));
+
+        let state = preview_state(TablePreviewMode::Schema, 2)
+            .reduce(TanicAction::TimeTravel(as_of.clone()));
+        assert!(
+            matches!(
+                &state,
+                TanicAppState::RetrievingTablePreview(request, TablePreviewMode::Schema)
+                    if request.as_of == as_of
+            ),
+            "{state:?}"
+        );
+
+        let state = state.reduce(TanicAction::RetrievedTablePreview(Box::new(preview(
+            as_of.clone(),
+        ))));
+        assert_eq!(preview_view(&state).mode, TablePreviewMode::Schema);
+        assert_eq!(preview_view(&state).preview.request.as_of, as_of);
+        assert_eq!(preview_view(&state).scroll, 0);
+    }
+
+    #[test]
+    fn retrieved_table_preview_for_another_point_in_time_is_ignored() {
+        let state = preview_state(TablePreviewMode::Data, 0)
+            .reduce(TanicAction::TimeTravel(Some(SnapshotSelector::Id(1))))
+            .reduce(TanicAction::RetrievedTablePreview(Box::new(preview(None))));
+
+        assert!(
+            matches!(state, TanicAppState::RetrievingTablePreview(..)),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn filtering_the_preview_keeps_its_point_in_time_and_shows_the_data() {
+        let as_of = Some(SnapshotSelector::Id(1));
+        let filter: Option<RowFilter> = Some("id > 1".parse().unwrap());
+
+        let state = preview_state(TablePreviewMode::Schema, 0)
+            .reduce(TanicAction::TimeTravel(as_of.clone()))
+            .reduce(TanicAction::RetrievedTablePreview(Box::new(preview(
+                as_of.clone(),
+            ))))
+            .reduce(TanicAction::FilterPreview {
+                columns: vec!["id".to_string()],
+                filter: filter.clone(),
+            });
+
+        assert!(
+            matches!(
+                &state,
+                TanicAppState::RetrievingTablePreview(request, TablePreviewMode::Data)
+                    if request.as_of == as_of
+                        && request.columns == ["id"]
+                        && request.filter == filter
+            ),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn explaining_the_scan_plans_the_preview_as_read() {
+        let state = preview_state(TablePreviewMode::Data, 0).reduce(TanicAction::ExplainScan);
+        let TanicAppState::RetrievingScanPlan(request) = &state else {
+            panic!("expected a scan plan to be retrieved, got {state:?}");
+        };
+        assert_eq!(*request, preview(None).request);
+
+        let plan = ScanPlan {
+            request: request.clone(),
+            snapshot_id: Some(1),
+            estimated: false,
+            data_files: Default::default(),
+            position_deletes: Default::default(),
+            equality_deletes: Default::default(),
+            manifests: vec![],
+        };
+        let state = state.reduce(TanicAction::RetrievedScanPlan(Box::new(plan)));
+        assert!(
+            matches!(&state, TanicAppState::ViewingScanPlan(view_state) if view_state.scroll == 0),
+            "{state:?}"
+        );
+
+        // there is nothing to scroll through in a plan without manifests
+        let state = state.reduce(TanicAction::ScrollScanPlanDown);
+        assert!(
+            matches!(&state, TanicAppState::ViewingScanPlan(view_state) if view_state.scroll == 0),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn sampling_a_histogram_keeps_its_column_selected() {
+        let request = ProfileRequest {
+            table: table_ref("prod", "t"),
+            histogram: None,
+        };
+        let column = |name: &str| ColumnProfile {
+            field_id: 0,
+            name: name.to_string(),
+            field_type: "long".to_string(),
+            files_with_counts: 0,
+            files_with_bounds: 0,
+            values: 0,
+            nulls: 0,
+            nans: None,
+            min: None,
+            max: None,
+        };
+        let profile = |request: &ProfileRequest| TableProfile {
+            request: request.clone(),
+            snapshot_id: Some(1),
+            data_files: 0,
+            records: 0,
+            columns: vec![column("id"), column("total")],
+            histogram: None,
+            histogram_error: None,
+        };
+
+        let state = tables_state(&["t"], Some(0))
+            .reduce(TanicAction::ProfileTable(Box::new(request.clone())))
+            .reduce(TanicAction::RetrievedTableProfile(Box::new(profile(
+                &request,
+            ))))
+            .reduce(TanicAction::FocusNextProfileColumn)
+            .reduce(TanicAction::SampleHistogram);
+
+        let TanicAppState::RetrievingTableProfile(request) = state.clone() else {
+            panic!("expected a profile to be retrieved, got {state:?}");
+        };
+        assert_eq!(request.histogram.as_deref(), Some("total"));
+
+        let state = state.reduce(TanicAction::RetrievedTableProfile(Box::new(profile(
+            &request,
+        ))));
+        assert!(
+            matches!(&state, TanicAppState::ViewingTableProfile(view_state) if view_state.selected_idx == 1),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn delete_health_for_another_table_is_ignored() {
+        let health = |name: &str| DeleteHealth {
+            table: table_ref("prod", name),
+            snapshot_id: Some(1),
+            data: Default::default(),
+            position_deletes: Default::default(),
+            equality_deletes: Default::default(),
+            partitions: vec![],
+        };
+
+        let state = tables_state(&["t1", "t2"], Some(0))
+            .reduce(TanicAction::InspectDeletes(Box::new(table_ref(
+                "prod", "t1",
+            ))))
+            .reduce(TanicAction::RetrievedDeleteHealth(Box::new(health("t2"))));
+        assert!(
+            matches!(state, TanicAppState::RetrievingDeleteHealth(_)),
+            "{state:?}"
+        );
+
+        let state = state.reduce(TanicAction::RetrievedDeleteHealth(Box::new(health("t1"))));
+        assert!(
+            matches!(state, TanicAppState::ViewingDeleteHealth(_)),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn maintenance_is_only_applied_once_confirmed_with_the_table_name() {
+        let request = MaintenanceRequest {
+            table: table_ref("prod", "t1"),
+            operation: MaintenanceOperation::ExpireSnapshots {
+                older_than_days: Some(7),
+                retain_last: None,
+            },
+        };
+        let plan = MaintenancePlan {
+            request: request.clone(),
+            snapshot_id: Some(3),
+            table_uuid: "00000000-0000-0000-0000-000000000000".to_string(),
+            removals: vec![Removal::Snapshot {
+                id: 1,
+                committed_at: "2025-01-01 00:00:00 UTC".to_string(),
+                operation: "append".to_string(),
+            }],
+            kept: 2,
+        };
+
+        let state = tables_state(&["t1"], Some(0))
+            .reduce(TanicAction::PlanMaintenance(Box::new(request)))
+            .reduce(TanicAction::RetrievedMaintenancePlan(Box::new(
+                plan.clone(),
+            )))
+            .reduce(TanicAction::ApplyMaintenance("t2".to_string()));
+        assert!(
+            matches!(&state, TanicAppState::ViewingMaintenancePlan(view_state) if view_state.is_applicable()),
+            "{state:?}"
+        );
+
+        let state = state.reduce(TanicAction::ApplyMaintenance("t1".to_string()));
+        assert!(
+            matches!(&state, TanicAppState::ApplyingMaintenance(applying) if *applying == plan),
+            "{state:?}"
+        );
+
+        // an applied plan can't be applied again
+        let state = state
+            .reduce(TanicAction::AppliedMaintenance(Box::new(
+                MaintenanceOutcome {
+                    removed: 1,
+                    failures: vec![],
+                },
+            )))
+            .reduce(TanicAction::ApplyMaintenance("t1".to_string()));
+        assert!(
+            matches!(&state, TanicAppState::ViewingMaintenancePlan(view_state) if !view_state.is_applicable()),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn toggling_the_schema_resets_the_scroll() {
+        let state = preview_state(TablePreviewMode::Data, 2).reduce(TanicAction::ToggleTableSchema);
+        assert_eq!(preview_view(&state).mode, TablePreviewMode::Schema);
+        assert_eq!(preview_view(&state).scroll, 0);
+
+        let state = state.reduce(TanicAction::ToggleTableSchema);
+        assert_eq!(preview_view(&state).mode, TablePreviewMode::Data);
+    }
+
+    #[test]
+    fn scrolling_the_preview_stops_at_either_end() {
+        let state = preview_state(TablePreviewMode::Data, 1)
+            .reduce(TanicAction::ScrollPreviewDown)
+            .reduce(TanicAction::ScrollPreviewDown);
+        assert_eq!(preview_view(&state).scroll, 2);
+
+        let state = state
+            .reduce(TanicAction::ScrollPreviewUp)
+            .reduce(TanicAction::ScrollPreviewUp)
+            .reduce(TanicAction::ScrollPreviewUp);
+        assert_eq!(preview_view(&state).scroll, 0);
+    }
+
+    #[test]
+    fn refresh_marks_lists_as_refreshing() {
+        let state = namespaces_state(&["a"], Some(0)).reduce(TanicAction::Refresh);
+        assert!(namespaces_view(&state).refreshing);
+
+        let state = tables_state(&["t"], Some(0)).reduce(TanicAction::Refresh);
+        assert!(tables_view(&state).refreshing);
+    }
+
+    #[test]
+    fn refresh_elsewhere_does_nothing() {
+        let state = TanicAppState::Initializing.reduce(TanicAction::Refresh);
+
+        assert!(matches!(state, TanicAppState::Initializing));
+    }
+
+    #[test]
+    fn compare_tables_from_a_table_list_retrieves_the_comparison() {
+        let state = tables_state(&["t"], Some(0)).reduce(TanicAction::CompareTables(
+            Box::new(table_ref("left", "t")),
+            Box::new(table_ref("right", "t")),
+        ));
+
+        assert!(
+            matches!(
+                &state,
+                TanicAppState::RetrievingTableComparison(left, right)
+                    if left.connection.name == "left" && right.connection.name == "right"
+            ),
+            "{state:?}"
+        );
+    }
+
+    #[test]
+    fn compare_tables_outside_a_table_list_is_ignored() {
+        let state = namespaces_state(&["a"], Some(0)).reduce(TanicAction::CompareTables(
+            Box::new(table_ref("left", "t")),
+            Box::new(table_ref("right", "t")),
+        ));
+
+        assert_eq!(namespace_names(&state), ["a"]);
+    }
+
+    #[test]
+    fn retrieved_table_comparison_shows_the_comparison() {
+        let state = TanicAppState::RetrievingTableComparison(
+            table_ref("left", "t"),
+            table_ref("right", "t"),
+        )
+        .reduce(TanicAction::RetrievedTableComparison(Box::new(comparison(
+            1, 1,
+        ))));
+
+        let view_state = comparison_view(&state);
+        assert_eq!(view_state.selected_idx, 0);
+        assert!(!view_state.differences_only);
+        assert_eq!(view_state.comparison.difference_count(), 1);
+    }
+
+    #[test]
+    fn retrieved_table_comparison_for_other_tables_is_ignored() {
+        let state = TanicAppState::RetrievingTableComparison(
+            table_ref("left", "x"),
+            table_ref("right", "x"),
+        )
+        .reduce(TanicAction::RetrievedTableComparison(Box::new(comparison(
+            1, 1,
+        ))));
+
+        assert!(state.is_pending(), "{state:?}");
+    }
+
+    #[test]
+    fn focus_comparison_rows_stops_at_either_end() {
+        let state =
+            comparison_state(comparison(1, 1), 0).reduce(TanicAction::FocusPrevComparisonRow);
+        assert_eq!(comparison_view(&state).selected_idx, 0);
+
+        let state = state
+            .reduce(TanicAction::FocusNextComparisonRow)
+            .reduce(TanicAction::FocusNextComparisonRow);
+        assert_eq!(comparison_view(&state).selected_idx, 1);
+
+        let state = state.reduce(TanicAction::FocusPrevComparisonRow);
+        assert_eq!(comparison_view(&state).selected_idx, 0);
+    }
+
+    #[test]
+    fn focus_comparison_rows_without_entries_stays_at_the_start() {
+        let state = comparison_state(comparison(0, 0), 0)
+            .reduce(TanicAction::FocusNextComparisonRow)
+            .reduce(TanicAction::FocusPrevComparisonRow);
+
+        assert_eq!(comparison_view(&state).selected_idx, 0);
+    }
+
+    #[test]
+    fn toggling_differences_only_resets_the_selection() {
+        let state = comparison_state(comparison(1, 2), 2)
+            .reduce(TanicAction::ToggleComparisonDifferencesOnly);
+
+        let view_state = comparison_view(&state);
+        assert!(view_state.differences_only);
+        assert_eq!(view_state.selected_idx, 0);
+        assert_eq!(view_state.visible_entry_count(), 1);
+
+        let state = state.reduce(TanicAction::FocusNextComparisonRow);
+        assert_eq!(comparison_view(&state).selected_idx, 0);
+
+        let state = state.reduce(TanicAction::ToggleComparisonDifferencesOnly);
+        assert!(!comparison_view(&state).differences_only);
+    }
+
+    #[test]
+    fn request_failed_while_pending_shows_the_error() {
+        for state in [
+            TanicAppState::ConnectingTo(conn_details("prod")),
+            TanicAppState::RetrievingTableList(namespace("ns")),
+            TanicAppState::RetrievingTableComparison(
+                table_ref("left", "t"),
+                table_ref("right", "t"),
+            ),
+        ] {
+            let state = state.reduce(TanicAction::RequestFailed("boom".to_string()));
+
+            assert!(
+                matches!(&state, TanicAppState::ShowingError(message) if message == "boom"),
+                "{state:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn request_failed_while_refreshing_keeps_the_list() {
+        let state = namespaces_state(&["a"], Some(0))
+            .reduce(TanicAction::Refresh)
+            .reduce(TanicAction::RequestFailed("boom".to_string()));
+        assert_eq!(namespace_names(&state), ["a"]);
+        assert!(!namespaces_view(&state).refreshing);
+
+        let state = tables_state(&["t"], Some(0))
+            .reduce(TanicAction::Refresh)
+            .reduce(TanicAction::RequestFailed("boom".to_string()));
+        assert_eq!(table_names(&state), ["t"]);
+        assert!(!tables_view(&state).refreshing);
+    }
+
+    #[test]
+    fn request_failed_elsewhere_does_nothing() {
+        let state = comparison_state(comparison(1, 0), 0)
+            .reduce(TanicAction::RequestFailed("boom".to_string()));
+
+        assert_eq!(comparison_view(&state).selected_idx, 0);
+    }
+
+    #[test]
+    fn actions_for_other_views_do_nothing() {
+        let state = namespaces_state(&["a", "b"], Some(1))
+            .reduce(TanicAction::FocusNextTable)
+            .reduce(TanicAction::FocusNextComparisonRow)
+            .reduce(TanicAction::ToggleComparisonDifferencesOnly)
+            .reduce(TanicAction::EnrichedTableDetails(table("ns", "t")))
+            .reduce(TanicAction::Exit);
+        assert_eq!(namespaces_view(&state).selected_idx, Some(1));
+
+        let state = tables_state(&["t1", "t2"], Some(1))
+            .reduce(TanicAction::FocusNextNamespace)
+            .reduce(TanicAction::SelectNamespace);
+        assert_eq!(tables_view(&state).selected_idx, Some(1));
+
+        let state = TanicAppState::Initializing
+            .reduce(TanicAction::FocusNextNamespace)
+            .reduce(TanicAction::FocusNextTable)
+            .reduce(TanicAction::SelectNamespace);
+        assert!(matches!(state, TanicAppState::Initializing));
+    }
+}
@@ -0,0 +1,870 @@
+//! Tabs
+//!
+//! Each tab holds its own connection, view state and navigation history,
+//! so that several catalogs can be browsed side by side. Tabs also keep
+//! track of the catalog requests that their views are waiting on.
+
+use tanic_core::config::ConnectionDetails;
+
+use crate::comparison::TableRef;
+use crate::export::{ExportJob, ExportStatus};
+use crate::navigation::NavigationHistory;
+use crate::profile::ProfileRequest;
+use crate::report::{ReportRequest, ReportTarget};
+use crate::requests::{CatalogCommand, CatalogRequest, CommandQueue, RequestId};
+use crate::state::{
+    TanicAction, TanicAppState, ViewingNamespacesListState, ViewingTablesListState,
+};
+
+/// Identifies a tab for as long as it is open
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TabId(u64);
+
+impl TabId {
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+/// A tab, as published to the UI and catalog tasks
+#[derive(Clone, Debug)]
+pub struct TanicTab {
+    pub id: TabId,
+    pub connection: Option<ConnectionDetails>,
+    pub state: TanicAppState,
+}
+
+/// State of every open tab, as published to the UI and catalog tasks
+#[derive(Clone, Debug, Default)]
+pub struct TanicTabsState {
+    pub tabs: Vec<TanicTab>,
+    pub active_idx: usize,
+
+    /// table marked for comparison, which may be in any tab
+    pub comparison_mark: Option<TableRef>,
+
+    /// exports started from any tab, in the order they were started
+    pub exports: Vec<ExportJob>,
+
+    /// exit has been asked for, and waits for maintenance being applied to
+    /// finish
+    pub exit_pending: bool,
+    pub exiting: bool,
+}
+
+impl TanicTabsState {
+    pub fn active_tab(&self) -> Option<&TanicTab> {
+        self.tabs.get(self.active_idx)
+    }
+
+    /// The table selected in the active tab's table list, or the table in
+    /// view, if any
+    pub fn selected_table(&self) -> Option<TableRef> {
+        let tab = self.active_tab()?;
+        selected_table(tab.connection.as_ref(), &tab.state)
+    }
+}
+
+#[derive(Debug)]
+struct Tab {
+    id: TabId,
+    connection: Option<ConnectionDetails>,
+    state: TanicAppState,
+    history: NavigationHistory,
+
+    /// catalog requests that haven't been responded to yet
+    pending: Vec<(RequestId, CatalogRequest)>,
+}
+
+impl Tab {
+    fn new(id: TabId) -> Self {
+        Self {
+            id,
+            connection: None,
+            state: TanicAppState::default(),
+            history: NavigationHistory::default(),
+            pending: vec![],
+        }
+    }
+
+    /// The table selected in this tab's table list, or the table in view,
+    /// if any
+    fn selected_table(&self) -> Option<TableRef> {
+        selected_table(self.connection.as_ref(), &self.state)
+    }
+
+    /// What a report from this tab would cover: the selected table, or the
+    /// selected namespace
+    fn report_target(&self) -> Option<ReportTarget> {
+        if let Some(table) = self.selected_table() {
+            return Some(ReportTarget::Table(table));
+        }
+
+        self.namespace_target()
+    }
+
+    /// The namespace selected in this tab's namespace list, or the namespace
+    /// whose tables are in view, if any
+    fn namespace_target(&self) -> Option<ReportTarget> {
+        let connection = self.connection.as_ref()?;
+        let namespace = match &self.state {
+            TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+                namespaces,
+                selected_idx: Some(selected_idx),
+                ..
+            }) => namespaces.get(*selected_idx)?,
+            TanicAppState::ViewingTablesList(view_state) => &view_state.namespace,
+            _ => return None,
+        };
+
+        Some(ReportTarget::Namespace {
+            connection: connection.clone(),
+            namespace: namespace.parts.clone(),
+        })
+    }
+
+    fn is_applying_maintenance(&self) -> bool {
+        matches!(self.state, TanicAppState::ApplyingMaintenance(_))
+    }
+
+    fn reduce(&mut self, action: TanicAction, queue: &mut CommandQueue) {
+        // a new or refreshed table list needs its tables' summaries loading
+        let load_tables = match action {
+            TanicAction::RetrievedTableList(..) => Some(false),
+            TanicAction::RefreshedTableList(..) => Some(true),
+            _ => None,
+        };
+
+        let state = std::mem::take(&mut self.state);
+
+        self.state = match action {
+            // leaving would cancel the removals partway through, so the view
+            // waits for them to finish
+            TanicAction::NavigateBack
+            | TanicAction::NavigateForward
+            | TanicAction::CancelRequest
+            | TanicAction::ConnectTo(_)
+                if matches!(state, TanicAppState::ApplyingMaintenance(_)) =>
+            {
+                state
+            }
+            TanicAction::NavigateBack => self.history.back(state),
+            TanicAction::NavigateForward => self.history.forward(state),
+            TanicAction::CancelRequest if state.is_pending() => {
+                // return to the view that made the request, if there was one
+                let previous = self.history.back(state);
+                if previous.is_pending() {
+                    TanicAppState::ShowingError("Request cancelled".to_string())
+                } else {
+                    previous
+                }
+            }
+            TanicAction::ConnectTo(conn_details) => {
+                self.history.clear();
+                self.connection = Some(conn_details.clone());
+                state.reduce(TanicAction::ConnectTo(conn_details))
+            }
+            action if action.opens_child_view() => {
+                let previous = state.clone();
+                let next_state = state.reduce(action);
+
+                // only remember the previous view if a child view was actually opened
+                if std::mem::discriminant(&next_state) != std::mem::discriminant(&previous) {
+                    self.history.push(previous);
+                }
+                next_state
+            }
+            action => state.reduce(action),
+        };
+
+        if let Some(refresh) = load_tables {
+            self.load_tables(refresh, queue);
+        }
+    }
+
+    /// Request the summaries of the tables in view. Unless refreshing, only
+    /// tables without a summary are loaded.
+    fn load_tables(&mut self, refresh: bool, queue: &mut CommandQueue) {
+        let (Some(connection), TanicAppState::ViewingTablesList(view_state)) =
+            (&self.connection, &self.state)
+        else {
+            return;
+        };
+
+        let requests = view_state
+            .tables
+            .iter()
+            .filter(|table| refresh || table.summary.is_none())
+            .map(|table| CatalogRequest::LoadTable {
+                connection: connection.clone(),
+                namespace: table.namespace.clone(),
+                name: table.name.clone(),
+                refresh,
+            })
+            .collect::<Vec<_>>();
+
+        for request in requests {
+            self.request(request, queue);
+        }
+    }
+
+    /// Cancel requests that the current view no longer needs, and make the
+    /// request that it is waiting on, if that hasn't been made already
+    fn sync_requests(&mut self, queue: &mut CommandQueue) {
+        let connection = self.connection.as_ref();
+
+        self.pending.retain(|(id, request)| {
+            let wanted = request.is_wanted_by(connection, &self.state);
+            if !wanted {
+                queue.cancel(*id);
+            }
+            wanted
+        });
+
+        if let Some(request) = CatalogRequest::for_state(connection, &self.state) {
+            self.request(request, queue);
+        }
+    }
+
+    fn request(&mut self, request: CatalogRequest, queue: &mut CommandQueue) {
+        if self.pending.iter().any(|(_, pending)| *pending == request) {
+            return;
+        }
+
+        let id = queue.request(request.clone());
+        self.pending.push((id, request));
+    }
+
+    /// Cancel the tab's requests, other than maintenance being applied,
+    /// which is left to finish
+    fn cancel_requests(&mut self, queue: &mut CommandQueue) {
+        self.pending.retain(|(id, request)| {
+            let applying = matches!(request, CatalogRequest::ApplyMaintenance(_));
+            if !applying {
+                queue.cancel(*id);
+            }
+            applying
+        });
+    }
+
+    /// Take a pending request, if the response is for one of this tab's
+    fn take_request(&mut self, id: RequestId) -> Option<CatalogRequest> {
+        let idx = self
+            .pending
+            .iter()
+            .position(|(pending_id, _)| *pending_id == id)?;
+        Some(self.pending.remove(idx).1)
+    }
+}
+
+/// The open tabs, along with their navigation histories
+#[derive(Debug, Default)]
+pub(crate) struct Tabs {
+    tabs: Vec<Tab>,
+    active_idx: usize,
+    next_id: u64,
+    comparison_mark: Option<TableRef>,
+    exports: Vec<ExportJob>,
+    exit_pending: bool,
+    exiting: bool,
+
+    queue: CommandQueue,
+}
+
+impl Tabs {
+    pub(crate) fn is_exiting(&self) -> bool {
+        self.exiting
+    }
+
+    /// Apply an action, returning the catalog commands that it calls for
+    pub(crate) fn reduce(&mut self, action: TanicAction) -> Vec<CatalogCommand> {
+        // once exit has been asked for, only responses are still of interest
+        if !self.exit_pending || matches!(action, TanicAction::CatalogResponse(..)) {
+            self.apply(action);
+        }
+        if self.exit_pending && !self.tabs.iter().any(Tab::is_applying_maintenance) {
+            self.exiting = true;
+        }
+
+        if self.exiting || self.exit_pending {
+            for tab in &mut self.tabs {
+                tab.cancel_requests(&mut self.queue);
+            }
+            self.cancel_exports();
+        } else {
+            for tab in &mut self.tabs {
+                tab.sync_requests(&mut self.queue);
+            }
+        }
+
+        self.queue.drain()
+    }
+
+    fn apply(&mut self, action: TanicAction) {
+        match action {
+            // removals are finished before exiting, with the tab applying
+            // them in view
+            TanicAction::Exit => match self.tabs.iter().position(Tab::is_applying_maintenance) {
+                Some(idx) => {
+                    self.active_idx = idx;
+                    self.exit_pending = true;
+                }
+                None => self.exiting = true,
+            },
+
+            TanicAction::OpenTab(conn_details) => {
+                self.open_tab(conn_details);
+            }
+
+            // closing would cancel the removals partway through
+            TanicAction::CloseTab
+                if self.active_tab().is_some_and(Tab::is_applying_maintenance) => {}
+
+            TanicAction::CloseTab => {
+                if self.active_idx < self.tabs.len() {
+                    let mut tab = self.tabs.remove(self.active_idx);
+                    tab.cancel_requests(&mut self.queue);
+                }
+                self.active_idx = self.active_idx.min(self.tabs.len().saturating_sub(1));
+            }
+
+            TanicAction::NextTab => {
+                if !self.tabs.is_empty() {
+                    self.active_idx = (self.active_idx + 1) % self.tabs.len();
+                }
+            }
+
+            TanicAction::PrevTab => {
+                if !self.tabs.is_empty() {
+                    self.active_idx = (self.active_idx + self.tabs.len() - 1) % self.tabs.len();
+                }
+            }
+
+            TanicAction::SelectTab(idx) => {
+                if idx < self.tabs.len() {
+                    self.active_idx = idx;
+                }
+            }
+
+            TanicAction::ExportTable(request) => {
+                tracing::info!(
+                    table = request.table.label(),
+                    path = %request.path.display(),
+                    "exporting table"
+                );
+                let id = self
+                    .queue
+                    .request(CatalogRequest::ExportTable(request.clone()));
+                self.exports.push(ExportJob {
+                    id,
+                    request: *request,
+                    rows_written: 0,
+                    rows_expected: None,
+                    status: ExportStatus::Running,
+                });
+            }
+
+            TanicAction::ExportProgressed {
+                id,
+                rows_written,
+                rows_expected,
+            } => {
+                if let Some(export) = self.running_export(id) {
+                    export.rows_written = rows_written;
+                    export.rows_expected = rows_expected;
+                }
+            }
+
+            TanicAction::CatalogResponse(id, actions) if self.running_export(id).is_some() => {
+                let Some(export) = self.running_export(id) else {
+                    return;
+                };
+
+                for action in actions {
+                    match action {
+                        TanicAction::ExportFinished(rows_written) => {
+                            export.rows_written = rows_written;
+                            export.status = ExportStatus::Finished;
+                        }
+                        TanicAction::RequestFailed(message) => {
+                            export.status = ExportStatus::Failed(message);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            TanicAction::DismissExports => {
+                self.cancel_exports();
+                self.exports.clear();
+            }
+
+            TanicAction::WriteReport(format) => {
+                let Some(target) = self.active_tab().and_then(Tab::report_target) else {
+                    return;
+                };
+                let path = target.default_path(format);
+                tracing::info!(
+                    target = target.label(),
+                    path = %path.display(),
+                    "writing metadata report"
+                );
+
+                // the report's outcome is logged; no view waits on it
+                self.queue
+                    .request(CatalogRequest::WriteReport(Box::new(ReportRequest {
+                        target,
+                        path,
+                        format,
+                    })));
+            }
+
+            TanicAction::CatalogResponse(id, actions) => {
+                let responding_tab = self
+                    .tabs
+                    .iter_mut()
+                    .position(|tab| tab.take_request(id).is_some());
+
+                let Some(tab_idx) = responding_tab else {
+                    tracing::debug!(?id, "ignoring response to a cancelled request");
+                    return;
+                };
+
+                let tab = &mut self.tabs[tab_idx];
+                for action in actions {
+                    tab.reduce(action, &mut self.queue);
+                }
+            }
+
+            TanicAction::MarkTableForComparison => {
+                if let Some(table) = self.active_tab().and_then(Tab::selected_table) {
+                    tracing::info!(table = table.label(), "marked table for comparison");
+                    self.comparison_mark = Some(table);
+                }
+            }
+
+            TanicAction::SelectTable => {
+                if let Some(table) = self.active_tab().and_then(Tab::selected_table) {
+                    self.apply(TanicAction::OpenTable(Box::new(table)));
+                }
+            }
+
+            TanicAction::ProfileSelectedTable => {
+                if let Some(table) = self.active_tab().and_then(Tab::selected_table) {
+                    self.apply(TanicAction::ProfileTable(Box::new(ProfileRequest {
+                        table,
+                        histogram: None,
+                    })));
+                }
+            }
+
+            TanicAction::InspectSelectedTableDeletes => {
+                if let Some(table) = self.active_tab().and_then(Tab::selected_table) {
+                    self.apply(TanicAction::InspectDeletes(Box::new(table)));
+                }
+            }
+
+            TanicAction::CheckSelectedTableHealth => {
+                if let Some(table) = self.active_tab().and_then(Tab::selected_table) {
+                    self.apply(TanicAction::CheckHealth(Box::new(ReportTarget::Table(
+                        table,
+                    ))));
+                }
+            }
+
+            TanicAction::CheckNamespaceHealth => {
+                if let Some(target) = self.active_tab().and_then(Tab::namespace_target) {
+                    self.apply(TanicAction::CheckHealth(Box::new(target)));
+                }
+            }
+
+            TanicAction::CompareWithMarkedTable => {
+                let selected = self.active_tab().and_then(Tab::selected_table);
+
+                if let (Some(left), Some(right)) = (self.comparison_mark.clone(), selected) {
+                    self.apply(TanicAction::CompareTables(Box::new(left), Box::new(right)));
+                }
+            }
+
+            // connecting with no open tabs opens one
+            TanicAction::ConnectTo(conn_details) if self.tabs.is_empty() => {
+                self.open_tab(conn_details);
+            }
+
+            action => {
+                if let Some(tab) = self.tabs.get_mut(self.active_idx) {
+                    tab.reduce(action, &mut self.queue);
+                }
+            }
+        }
+    }
+
+    fn active_tab(&self) -> Option<&Tab> {
+        self.tabs.get(self.active_idx)
+    }
+
+    fn running_export(&mut self, id: RequestId) -> Option<&mut ExportJob> {
+        self.exports
+            .iter_mut()
+            .find(|export| export.id == id && export.status == ExportStatus::Running)
+    }
+
+    fn cancel_exports(&mut self) {
+        for export in &self.exports {
+            if export.status == ExportStatus::Running {
+                self.queue.cancel(export.id);
+            }
+        }
+    }
+
+    /// Open a new tab, connecting to a catalog. The active tab only changes
+    /// if there were no tabs open.
+    fn open_tab(&mut self, conn_details: ConnectionDetails) {
+        let id = TabId(self.next_id);
+        self.next_id += 1;
+
+        let mut tab = Tab::new(id);
+        tab.reduce(TanicAction::ConnectTo(conn_details), &mut self.queue);
+
+        self.tabs.push(tab);
+        if self.tabs.len() == 1 {
+            self.active_idx = 0;
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> TanicTabsState {
+        TanicTabsState {
+            tabs: self
+                .tabs
+                .iter()
+                .map(|tab| TanicTab {
+                    id: tab.id,
+                    connection: tab.connection.clone(),
+                    state: tab.state.clone(),
+                })
+                .collect(),
+            active_idx: self.active_idx,
+            comparison_mark: self.comparison_mark.clone(),
+            exports: self.exports.clone(),
+            exit_pending: self.exit_pending,
+            exiting: self.exiting,
+        }
+    }
+}
+
+/// The table selected in a table list, or the table in view, if any
+fn selected_table(
+    connection: Option<&ConnectionDetails>,
+    state: &TanicAppState,
+) -> Option<TableRef> {
+    if let TanicAppState::ViewingTablePreview(view_state) = state {
+        return Some(view_state.preview.request.table.clone());
+    }
+
+    let (
+        Some(connection),
+        TanicAppState::ViewingTablesList(ViewingTablesListState {
+            tables,
+            selected_idx: Some(selected_idx),
+            ..
+        }),
+    ) = (connection, state)
+    else {
+        return None;
+    };
+
+    let table = tables.get(*selected_idx)?;
+
+    Some(TableRef {
+        connection: connection.clone(),
+        namespace: table.namespace.clone(),
+        name: table.name.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{ExportFormat, ExportOptions, ExportRequest};
+    use crate::maintenance::{
+        MaintenanceOperation, MaintenanceOutcome, MaintenancePlan, MaintenanceRequest,
+    };
+    use crate::report::ReportFormat;
+    use tanic_core::message::{NamespaceDeets, TableDeets, TableSummary};
+    use uuid::Uuid;
+
+    fn prod() -> ConnectionDetails {
+        ConnectionDetails {
+            id: Uuid::nil(),
+            name: "prod".to_string(),
+            uri: "http://prod:8181".parse().unwrap(),
+        }
+    }
+
+    fn export_request(name: &str) -> Box<ExportRequest> {
+        Box::new(ExportRequest {
+            table: TableRef {
+                connection: prod(),
+                namespace: vec!["ns".to_string()],
+                name: name.to_string(),
+            },
+            path: format!("{name}.csv").into(),
+            options: ExportOptions {
+                format: ExportFormat::Csv,
+                columns: vec![],
+                filter: None,
+                limit: Some(100),
+                snapshot: None,
+            },
+        })
+    }
+
+    /// Start an export, returning the ID of its request
+    fn start_export(tabs: &mut Tabs, name: &str) -> RequestId {
+        let commands = tabs.reduce(TanicAction::ExportTable(export_request(name)));
+
+        match commands.as_slice() {
+            [CatalogCommand::Request(id, CatalogRequest::ExportTable(request))] => {
+                assert_eq!(request.table.name, name);
+                *id
+            }
+            other => panic!("expected an export request, got {other:?}"),
+        }
+    }
+
+    fn cancelled(commands: &[CatalogCommand]) -> Vec<RequestId> {
+        commands
+            .iter()
+            .filter_map(|command| match command {
+                CatalogCommand::Cancel(id) => Some(*id),
+                CatalogCommand::Request(..) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tracks_export_progress() {
+        let mut tabs = Tabs::default();
+        let id = start_export(&mut tabs, "orders");
+
+        tabs.reduce(TanicAction::ExportProgressed {
+            id,
+            rows_written: 40,
+            rows_expected: Some(100),
+        });
+
+        let export = &tabs.snapshot().exports[0];
+        assert_eq!(export.status, ExportStatus::Running);
+        assert_eq!(export.rows_written, 40);
+        assert_eq!(export.ratio(), Some(0.4));
+    }
+
+    #[test]
+    fn finishes_and_fails_exports() {
+        let mut tabs = Tabs::default();
+        let finished = start_export(&mut tabs, "orders");
+        let failed = start_export(&mut tabs, "refunds");
+
+        tabs.reduce(TanicAction::CatalogResponse(
+            finished,
+            vec![TanicAction::ExportFinished(100)],
+        ));
+        tabs.reduce(TanicAction::CatalogResponse(
+            failed,
+            vec![TanicAction::RequestFailed("disk full".to_string())],
+        ));
+
+        let exports = tabs.snapshot().exports;
+        assert_eq!(exports[0].status, ExportStatus::Finished);
+        assert_eq!(exports[0].rows_written, 100);
+        assert_eq!(
+            exports[1].status,
+            ExportStatus::Failed("disk full".to_string())
+        );
+    }
+
+    #[test]
+    fn dismissing_exports_cancels_running_ones() {
+        let mut tabs = Tabs::default();
+        let finished = start_export(&mut tabs, "orders");
+        let running = start_export(&mut tabs, "refunds");
+        tabs.reduce(TanicAction::CatalogResponse(
+            finished,
+            vec![TanicAction::ExportFinished(100)],
+        ));
+
+        let commands = tabs.reduce(TanicAction::DismissExports);
+
+        assert_eq!(cancelled(&commands), vec![running]);
+        assert!(tabs.snapshot().exports.is_empty());
+    }
+
+    #[test]
+    fn exiting_cancels_running_exports() {
+        let mut tabs = Tabs::default();
+        let running = start_export(&mut tabs, "orders");
+
+        let commands = tabs.reduce(TanicAction::Exit);
+
+        assert_eq!(cancelled(&commands), vec![running]);
+    }
+
+    #[test]
+    fn exiting_waits_for_maintenance_being_applied() {
+        let mut tabs = Tabs::default();
+        tabs.reduce(TanicAction::OpenTab(prod()));
+        tabs.tabs[0].state = TanicAppState::ApplyingMaintenance(MaintenancePlan {
+            request: MaintenanceRequest {
+                table: export_request("orders").table,
+                operation: MaintenanceOperation::RemoveOldMetadata { retain_last: 10 },
+            },
+            snapshot_id: Some(1),
+            table_uuid: "00000000-0000-0000-0000-000000000000".to_string(),
+            removals: vec![],
+            kept: 10,
+        });
+        let commands = tabs.reduce(TanicAction::NextTab);
+        assert!(
+            matches!(
+                commands.as_slice(),
+                [
+                    _,
+                    CatalogCommand::Request(_, CatalogRequest::ApplyMaintenance(_))
+                ]
+            ),
+            "{commands:?}"
+        );
+
+        for action in [TanicAction::CloseTab, TanicAction::ConnectTo(prod())] {
+            let commands = tabs.reduce(action);
+            assert!(commands.is_empty(), "{commands:?}");
+            assert!(tabs.tabs[0].is_applying_maintenance());
+        }
+
+        let commands = tabs.reduce(TanicAction::Exit);
+        assert!(cancelled(&commands).is_empty(), "{commands:?}");
+        assert!(tabs.snapshot().exit_pending);
+        assert!(!tabs.is_exiting());
+
+        // exiting once the removals have finished
+        let applying = tabs.tabs[0].pending[0].0;
+        tabs.reduce(TanicAction::CatalogResponse(
+            applying,
+            vec![TanicAction::AppliedMaintenance(Box::new(
+                MaintenanceOutcome {
+                    removed: 0,
+                    failures: vec![],
+                },
+            ))],
+        ));
+        assert!(tabs.is_exiting());
+    }
+
+    #[test]
+    fn reports_on_the_selected_namespace() {
+        let mut tabs = Tabs::default();
+        let commands = tabs.reduce(TanicAction::OpenTab(prod()));
+        let [CatalogCommand::Request(id, _)] = commands.as_slice() else {
+            panic!("expected a namespace list request, got {commands:?}");
+        };
+        tabs.reduce(TanicAction::CatalogResponse(
+            *id,
+            vec![TanicAction::RetrievedNamespaceList(vec![NamespaceDeets {
+                parts: vec!["sales".to_string()],
+                name: "sales".to_string(),
+                table_count: 0,
+                stale: false,
+            }])],
+        ));
+
+        let commands = tabs.reduce(TanicAction::WriteReport(ReportFormat::Markdown));
+
+        match commands.as_slice() {
+            [CatalogCommand::Request(_, CatalogRequest::WriteReport(request))] => {
+                assert_eq!(
+                    request.target,
+                    ReportTarget::Namespace {
+                        connection: prod(),
+                        namespace: vec!["sales".to_string()],
+                    }
+                );
+                assert_eq!(request.path, std::path::PathBuf::from("sales.report.md"));
+            }
+            other => panic!("expected a report request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn selecting_a_table_previews_it() {
+        let mut tabs = Tabs::default();
+        tabs.reduce(TanicAction::OpenTab(prod()));
+        let namespace = NamespaceDeets {
+            parts: vec!["ns".to_string()],
+            name: "ns".to_string(),
+            table_count: 1,
+            stale: false,
+        };
+        tabs.tabs[0].state = TanicAppState::ViewingTablesList(ViewingTablesListState {
+            tables: vec![TableDeets {
+                namespace: namespace.parts.clone(),
+                name: "orders".to_string(),
+                row_count: 1,
+                summary: Some(TableSummary::default()),
+                stale: false,
+            }],
+            namespace,
+            selected_idx: Some(0),
+            refreshing: false,
+        });
+
+        let commands = tabs.reduce(TanicAction::SelectTable);
+
+        let previews = commands
+            .iter()
+            .filter_map(|command| match command {
+                CatalogCommand::Request(_, CatalogRequest::PreviewTable(request)) => Some(request),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(previews.len(), 1, "{commands:?}");
+        assert_eq!(previews[0].table, export_request("orders").table);
+        assert_eq!(previews[0].as_of, None);
+    }
+
+    #[test]
+    fn checks_the_health_of_the_namespace_in_view() {
+        let mut tabs = Tabs::default();
+        tabs.reduce(TanicAction::OpenTab(prod()));
+        let namespace = NamespaceDeets {
+            parts: vec!["ns".to_string()],
+            name: "ns".to_string(),
+            table_count: 0,
+            stale: false,
+        };
+        tabs.tabs[0].state = TanicAppState::ViewingTablesList(ViewingTablesListState {
+            tables: vec![],
+            namespace,
+            selected_idx: None,
+            refreshing: false,
+        });
+
+        let commands = tabs.reduce(TanicAction::CheckNamespaceHealth);
+
+        match commands.as_slice() {
+            [.., CatalogCommand::Request(_, CatalogRequest::CheckHealth(target))] => {
+                assert_eq!(
+                    **target,
+                    ReportTarget::Namespace {
+                        connection: prod(),
+                        namespace: vec!["ns".to_string()],
+                    }
+                );
+            }
+            other => panic!("expected a health check request, got {other:?}"),
+        }
+        assert!(matches!(
+            tabs.tabs[0].state,
+            TanicAppState::RetrievingHealthReport(_)
+        ));
+    }
+}
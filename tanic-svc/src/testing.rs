@@ -0,0 +1,381 @@
+//! Test Support
+//!
+//! An in-memory Iceberg catalog, populated with fixture namespaces and
+//! tables, so that the app can be exercised end to end without a real
+//! catalog. Only available with the `testing` feature, and to this crate's
+//! own tests.
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use http::Uri;
+use iceberg::io::{FileIO, FileIOBuilder};
+use iceberg::spec::{
+    NestedField, Operation, PrimitiveType, Schema, Snapshot, Summary, TableMetadata,
+    TableMetadataBuilder, Type,
+};
+use iceberg::table::Table;
+use iceberg::{
+    Catalog, Error, ErrorKind, Namespace, NamespaceIdent, TableCommit, TableCreation, TableIdent,
+    TableRequirement, TableUpdate,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use tanic_core::config::ConnectionDetails;
+
+use crate::commit::TableCommitter;
+use crate::iceberg_context::CatalogConnector;
+use crate::MS_PER_DAY;
+
+/// when fixture tables' current snapshots were taken, in ms since the epoch
+pub(crate) const FIXTURE_TIMESTAMP_MS: i64 = 1_700_000_000_000;
+
+/// An in-memory catalog. Tables have metadata and a current snapshot, but no
+/// data files.
+#[derive(Debug)]
+pub struct MemoryCatalog {
+    namespaces: Mutex<BTreeMap<Vec<String>, BTreeMap<String, TableMetadata>>>,
+    file_io: FileIO,
+
+    /// how long each request takes
+    latency: Duration,
+}
+
+impl Default for MemoryCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryCatalog {
+    pub fn new() -> Self {
+        Self {
+            namespaces: Mutex::default(),
+            file_io: FileIOBuilder::new("memory")
+                .build()
+                .expect("the memory file IO is always available"),
+            latency: Duration::ZERO,
+        }
+    }
+
+    /// Delay every request, to make loading states observable
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    pub fn with_namespace(self, namespace: &[&str]) -> Self {
+        self.namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(parts(namespace))
+            .or_default();
+        self
+    }
+
+    /// Add a table, creating its namespace if needed. The table's current
+    /// snapshot reports `record_count` records.
+    pub fn with_table(self, namespace: &[&str], name: &str, record_count: u64) -> Self {
+        self.with_table_history(namespace, name, record_count, 1)
+    }
+
+    /// Add a table with `snapshots` snapshots, taken a day apart, the last of
+    /// them current
+    pub fn with_table_history(
+        self,
+        namespace: &[&str],
+        name: &str,
+        record_count: u64,
+        snapshots: i64,
+    ) -> Self {
+        let metadata = fixture_metadata(namespace, name, record_count, snapshots)
+            .expect("fixture table metadata is valid");
+
+        self.namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(parts(namespace))
+            .or_default()
+            .insert(name.to_string(), metadata);
+        self
+    }
+
+    async fn simulate_latency(&self) {
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+    }
+
+    fn table_metadata(&self, table: &TableIdent) -> iceberg::Result<TableMetadata> {
+        self.namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(table.namespace().as_ref())
+            .and_then(|tables| tables.get(table.name()))
+            .cloned()
+            .ok_or_else(|| no_such_table(table))
+    }
+
+    /// Check a commit's requirements against a table, then apply its updates
+    fn commit_updates(
+        &self,
+        table: &TableIdent,
+        requirements: Vec<TableRequirement>,
+        updates: Vec<TableUpdate>,
+    ) -> iceberg::Result<()> {
+        let mut namespaces = self
+            .namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let metadata = namespaces
+            .get_mut(table.namespace().as_ref())
+            .and_then(|tables| tables.get_mut(table.name()))
+            .ok_or_else(|| no_such_table(table))?;
+
+        for requirement in &requirements {
+            requirement.check(Some(metadata))?;
+        }
+        let builder = updates.into_iter().try_fold(
+            TableMetadataBuilder::new_from_metadata(metadata.clone(), None),
+            |builder, update| update.apply(builder),
+        )?;
+        *metadata = builder.build()?.metadata;
+
+        Ok(())
+    }
+}
+
+impl TableCommitter for MemoryCatalog {
+    fn commit<'a>(
+        &'a self,
+        table: &'a TableIdent,
+        requirements: Vec<TableRequirement>,
+        updates: Vec<TableUpdate>,
+    ) -> BoxFuture<'a, tanic_core::Result<()>> {
+        Box::pin(async move {
+            self.simulate_latency().await;
+            Ok(self.commit_updates(table, requirements, updates)?)
+        })
+    }
+}
+
+#[async_trait]
+impl Catalog for MemoryCatalog {
+    async fn list_namespaces(
+        &self,
+        parent: Option<&NamespaceIdent>,
+    ) -> iceberg::Result<Vec<NamespaceIdent>> {
+        self.simulate_latency().await;
+
+        let parent = parent
+            .map(|parent| parent.as_ref().clone())
+            .unwrap_or_default();
+        self.namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .filter(|namespace| {
+                namespace.len() == parent.len() + 1 && namespace.starts_with(&parent)
+            })
+            .map(|namespace| NamespaceIdent::from_vec(namespace.clone()))
+            .collect()
+    }
+
+    async fn create_namespace(
+        &self,
+        namespace: &NamespaceIdent,
+        properties: HashMap<String, String>,
+    ) -> iceberg::Result<Namespace> {
+        self.namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(namespace.as_ref().clone())
+            .or_default();
+
+        Ok(Namespace::with_properties(namespace.clone(), properties))
+    }
+
+    async fn get_namespace(&self, namespace: &NamespaceIdent) -> iceberg::Result<Namespace> {
+        if self.namespace_exists(namespace).await? {
+            Ok(Namespace::new(namespace.clone()))
+        } else {
+            Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!("namespace does not exist: {}", namespace.join(".")),
+            ))
+        }
+    }
+
+    async fn namespace_exists(&self, namespace: &NamespaceIdent) -> iceberg::Result<bool> {
+        Ok(self
+            .namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(namespace.as_ref()))
+    }
+
+    async fn update_namespace(
+        &self,
+        _namespace: &NamespaceIdent,
+        _properties: HashMap<String, String>,
+    ) -> iceberg::Result<()> {
+        Err(unsupported("updating namespaces"))
+    }
+
+    async fn drop_namespace(&self, _namespace: &NamespaceIdent) -> iceberg::Result<()> {
+        Err(unsupported("dropping namespaces"))
+    }
+
+    async fn list_tables(&self, namespace: &NamespaceIdent) -> iceberg::Result<Vec<TableIdent>> {
+        self.simulate_latency().await;
+
+        let namespaces = self
+            .namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let tables = namespaces.get(namespace.as_ref()).ok_or_else(|| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("namespace does not exist: {}", namespace.join(".")),
+            )
+        })?;
+
+        Ok(tables
+            .keys()
+            .map(|name| TableIdent::new(namespace.clone(), name.clone()))
+            .collect())
+    }
+
+    async fn create_table(
+        &self,
+        _namespace: &NamespaceIdent,
+        _creation: TableCreation,
+    ) -> iceberg::Result<Table> {
+        Err(unsupported("creating tables"))
+    }
+
+    async fn load_table(&self, table: &TableIdent) -> iceberg::Result<Table> {
+        self.simulate_latency().await;
+
+        Table::builder()
+            .identifier(table.clone())
+            .metadata(self.table_metadata(table)?)
+            .file_io(self.file_io.clone())
+            .readonly(true)
+            .build()
+    }
+
+    async fn drop_table(&self, _table: &TableIdent) -> iceberg::Result<()> {
+        Err(unsupported("dropping tables"))
+    }
+
+    async fn table_exists(&self, table: &TableIdent) -> iceberg::Result<bool> {
+        Ok(self.table_metadata(table).is_ok())
+    }
+
+    async fn rename_table(&self, _src: &TableIdent, _dest: &TableIdent) -> iceberg::Result<()> {
+        Err(unsupported("renaming tables"))
+    }
+
+    async fn update_table(&self, _commit: TableCommit) -> iceberg::Result<Table> {
+        Err(unsupported("updating tables"))
+    }
+}
+
+/// Connects each catalog URI to its in-memory catalog. Unknown URIs get an
+/// empty catalog.
+#[derive(Debug, Default)]
+pub struct MemoryCatalogConnector {
+    catalogs: HashMap<Uri, Arc<MemoryCatalog>>,
+}
+
+impl MemoryCatalogConnector {
+    pub fn with_catalog(mut self, uri: Uri, catalog: MemoryCatalog) -> Self {
+        self.catalogs.insert(uri, Arc::new(catalog));
+        self
+    }
+}
+
+impl CatalogConnector for MemoryCatalogConnector {
+    fn connect(&self, connection_details: &ConnectionDetails) -> Arc<dyn Catalog> {
+        match self.catalogs.get(&connection_details.uri) {
+            Some(catalog) => catalog.clone(),
+            None => Arc::new(MemoryCatalog::new()),
+        }
+    }
+
+    fn committer(&self, connection_details: &ConnectionDetails) -> Arc<dyn TableCommitter> {
+        match self.catalogs.get(&connection_details.uri) {
+            Some(catalog) => catalog.clone(),
+            None => Arc::new(MemoryCatalog::new()),
+        }
+    }
+}
+
+fn parts(namespace: &[&str]) -> Vec<String> {
+    namespace.iter().map(|part| part.to_string()).collect()
+}
+
+fn no_such_table(table: &TableIdent) -> Error {
+    Error::new(
+        ErrorKind::DataInvalid,
+        format!("table does not exist: {}", table.name()),
+    )
+}
+
+fn unsupported(operation: &str) -> Error {
+    Error::new(
+        ErrorKind::FeatureUnsupported,
+        format!("{operation} is not supported by the in-memory catalog"),
+    )
+}
+
+/// Metadata for a table with `snapshots` appends, a day apart, with IDs
+/// counting up from 1 and the last taken at `FIXTURE_TIMESTAMP_MS`
+pub(crate) fn fixture_metadata(
+    namespace: &[&str],
+    name: &str,
+    record_count: u64,
+    snapshots: i64,
+) -> iceberg::Result<TableMetadata> {
+    let location = format!("memory://warehouse/{}/{name}", namespace.join("/"));
+
+    let schema = Schema::builder()
+        .with_fields(vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+            NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String)).into(),
+        ])
+        .build()?;
+
+    let creation = TableCreation::builder()
+        .name(name.to_string())
+        .location(location.clone())
+        .schema(schema)
+        .build();
+
+    let mut builder = TableMetadataBuilder::from_table_creation(creation)?;
+    for snapshot_id in 1..=snapshots {
+        let snapshot = Snapshot::builder()
+            .with_snapshot_id(snapshot_id)
+            .with_sequence_number(snapshot_id)
+            .with_timestamp_ms(FIXTURE_TIMESTAMP_MS - (snapshots - snapshot_id) * MS_PER_DAY)
+            .with_manifest_list(format!("{location}/metadata/snap-{snapshot_id}.avro"))
+            .with_schema_id(0)
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::from([
+                    ("total-records".to_string(), record_count.to_string()),
+                    ("total-data-files".to_string(), "1".to_string()),
+                    (
+                        "total-files-size".to_string(),
+                        (record_count * 100).to_string(),
+                    ),
+                ]),
+            })
+            .build();
+        builder = builder.set_branch_snapshot(snapshot, "main")?;
+    }
+
+    Ok(builder.build()?.metadata)
+}
@@ -0,0 +1,86 @@
+//! Headless Backend
+//!
+//! A terminal backend that draws to memory instead of a terminal, and that
+//! can be read while the UI is running. Used to replay recorded sessions and
+//! in tests.
+
+use ratatui::backend::{Backend, ClearType, TestBackend, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::layout::{Position, Size};
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+#[derive(Clone)]
+pub struct HeadlessBackend(Arc<Mutex<TestBackend>>);
+
+impl HeadlessBackend {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self(Arc::new(Mutex::new(TestBackend::new(width, height))))
+    }
+
+    /// The screen as drawn most recently, one line per row
+    pub fn screen(&self) -> String {
+        let backend = self.lock();
+        let buffer = backend.buffer();
+
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(Cell::symbol).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn resize(&self, width: u16, height: u16) {
+        self.lock().resize(width, height);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TestBackend> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        self.lock().draw(content)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.lock().hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.lock().show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.lock().get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.lock().set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.lock().clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.lock().clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.lock().size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.lock().window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lock().flush()
+    }
+}
@@ -0,0 +1,49 @@
+//! Icons for namespaces, tables and spinners, in Nerd Font, Unicode and ASCII
+//! variants
+
+use tanic_core::config::IconSet;
+
+// find more at https://www.nerdfonts.com/cheat-sheet
+const NERD_FONT_ICON_TABLE_FOLDER: &str = "\u{f12e4}"; // 󱋤
+const NERD_FONT_ICON_TABLE: &str = "\u{ebb7}"; // 
+const NERD_FONT_ICON_WARNING: &str = "\u{f071}"; // 
+
+const BRAILLE_SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_SPINNER: &[&str] = &["|", "/", "-", "\\"];
+
+#[derive(Clone, Debug)]
+pub(crate) struct Icons {
+    pub(crate) namespace: &'static str,
+    pub(crate) table: &'static str,
+
+    /// badge for tables with health findings
+    pub(crate) warning: &'static str,
+
+    /// frames of the spinner shown while waiting on the catalog
+    pub(crate) spinner: &'static [&'static str],
+}
+
+impl Icons {
+    pub(crate) fn new(icon_set: IconSet) -> Self {
+        match icon_set {
+            IconSet::NerdFont => Self {
+                namespace: NERD_FONT_ICON_TABLE_FOLDER,
+                table: NERD_FONT_ICON_TABLE,
+                warning: NERD_FONT_ICON_WARNING,
+                spinner: BRAILLE_SPINNER,
+            },
+            IconSet::Unicode => Self {
+                namespace: "▤",
+                table: "▦",
+                warning: "⚠",
+                spinner: BRAILLE_SPINNER,
+            },
+            IconSet::Ascii => Self {
+                namespace: "+",
+                table: "#",
+                warning: "!",
+                spinner: ASCII_SPINNER,
+            },
+        }
+    }
+}
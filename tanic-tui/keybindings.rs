@@ -0,0 +1,264 @@
+//! Key Bindings
+//!
+//! Each view declares its key bindings as data. The same tables are used
+//! to dispatch key events and to render the help overlay and footer bar.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use tanic_svc::TanicAction;
+use tui_logger::TuiWidgetEvent;
+
+use crate::ui_components::maintenance_dialog::MaintenanceKind;
+
+/// Something that a key press can trigger
+#[derive(Clone, Debug)]
+pub(crate) enum Command {
+    /// Dispatch an action to the app state
+    Action(TanicAction),
+
+    /// Show or hide the help overlay
+    ToggleHelp,
+
+    /// Show or hide the log panel
+    ToggleLogPanel,
+
+    /// Maximise the log panel, or restore it to its normal size
+    ToggleLogPanelMaximised,
+
+    /// Pass an event through to the log panel
+    LogPanel(TuiWidgetEvent),
+
+    /// Open the export form for the selected table
+    OpenExportForm,
+
+    /// Operate the open export form
+    ExportForm(FormCommand),
+
+    /// Ask which point in time to read the table in view as of
+    OpenTimeTravelForm,
+
+    /// Operate the open time travel form
+    TimeTravelForm(FormCommand),
+
+    /// Ask which columns and rows of the table in view to read
+    OpenFilterForm,
+
+    /// Operate the open filter form
+    FilterForm(FormCommand),
+
+    /// Ask how to run a maintenance operation on the selected table
+    OpenMaintenanceForm(MaintenanceKind),
+
+    /// Operate the open maintenance form
+    MaintenanceForm(FormCommand),
+
+    /// Ask for the maintenance plan in view to be confirmed, before it is
+    /// applied
+    OpenConfirmForm,
+
+    /// Operate the open confirmation form
+    ConfirmForm(FormCommand),
+}
+
+/// Operates a form that is open over the view
+#[derive(Clone, Debug)]
+pub(crate) enum FormCommand {
+    NextField,
+    PrevField,
+
+    /// Type into the focused field
+    Edit(KeyEvent),
+
+    /// Act on the form, if it is valid
+    Submit,
+
+    Close,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct KeyBinding {
+    pub(crate) key: KeyCode,
+
+    /// how the key is displayed in help and the footer
+    pub(crate) key_label: &'static str,
+
+    pub(crate) description: &'static str,
+
+    pub(crate) command: Command,
+
+    /// whether the binding is shown in the footer bar
+    pub(crate) in_footer: bool,
+}
+
+/// Bindings that are available in every view
+pub(crate) const GLOBAL_KEY_BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        key: KeyCode::Char('q'),
+        key_label: "Q",
+        description: "Quit",
+        command: Command::Action(TanicAction::Exit),
+        in_footer: true,
+    },
+    KeyBinding {
+        key: KeyCode::Char('?'),
+        key_label: "?",
+        description: "Help",
+        command: Command::ToggleHelp,
+        in_footer: true,
+    },
+    KeyBinding {
+        key: KeyCode::Esc,
+        key_label: "Esc",
+        description: "Back",
+        command: Command::Action(TanicAction::NavigateBack),
+        in_footer: true,
+    },
+    KeyBinding {
+        key: KeyCode::Backspace,
+        key_label: "Backspace",
+        description: "Back",
+        command: Command::Action(TanicAction::NavigateBack),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::Char(']'),
+        key_label: "]",
+        description: "Forward",
+        command: Command::Action(TanicAction::NavigateForward),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::Tab,
+        key_label: "Tab",
+        description: "Next tab",
+        command: Command::Action(TanicAction::NextTab),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::BackTab,
+        key_label: "Shift+Tab",
+        description: "Previous tab",
+        command: Command::Action(TanicAction::PrevTab),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::Char('w'),
+        key_label: "w",
+        description: "Close tab",
+        command: Command::Action(TanicAction::CloseTab),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::Char('X'),
+        key_label: "X",
+        description: "Dismiss exports",
+        command: Command::Action(TanicAction::DismissExports),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::Char('l'),
+        key_label: "l",
+        description: "Show/hide log",
+        command: Command::ToggleLogPanel,
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::Char('L'),
+        key_label: "L",
+        description: "Maximise/restore log",
+        command: Command::ToggleLogPanelMaximised,
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::PageUp,
+        key_label: "PgUp",
+        description: "Scroll log back",
+        command: Command::LogPanel(TuiWidgetEvent::PrevPageKey),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::PageDown,
+        key_label: "PgDn",
+        description: "Scroll log forward",
+        command: Command::LogPanel(TuiWidgetEvent::NextPageKey),
+        in_footer: false,
+    },
+    KeyBinding {
+        key: KeyCode::End,
+        key_label: "End",
+        description: "Follow latest log",
+        command: Command::LogPanel(TuiWidgetEvent::EscapeKey),
+        in_footer: false,
+    },
+];
+
+/// Bindings that are available while the help overlay is open
+pub(crate) const HELP_KEY_BINDINGS: &[KeyBinding] = &[KeyBinding {
+    key: KeyCode::Esc,
+    key_label: "Esc",
+    description: "Close help",
+    command: Command::ToggleHelp,
+    in_footer: true,
+}];
+
+/// Find the command bound to a key event. Bindings are for plain key
+/// presses, so Ctrl and Alt chords, and key releases, trigger nothing.
+pub(crate) fn lookup<'a>(
+    bindings: impl IntoIterator<Item = &'a KeyBinding>,
+    key_event: KeyEvent,
+) -> Option<Command> {
+    if key_event.kind != KeyEventKind::Press
+        || key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return None;
+    }
+
+    bindings
+        .into_iter()
+        .find(|binding| binding.key == key_event.code)
+        .map(|binding| binding.command.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEventState;
+
+    fn quits(key_event: KeyEvent) -> bool {
+        matches!(
+            lookup(GLOBAL_KEY_BINDINGS, key_event),
+            Some(Command::Action(TanicAction::Exit))
+        )
+    }
+
+    #[test]
+    fn plain_key_presses_trigger_their_binding() {
+        assert!(quits(KeyEvent::from(KeyCode::Char('q'))));
+        assert!(quits(KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::SHIFT
+        )));
+    }
+
+    #[test]
+    fn ctrl_and_alt_chords_trigger_nothing() {
+        assert!(!quits(KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::CONTROL
+        )));
+        assert!(!quits(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::ALT)));
+    }
+
+    #[test]
+    fn key_releases_trigger_nothing() {
+        let release = KeyEvent::new_with_kind_and_state(
+            KeyCode::Char('q'),
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+            KeyEventState::NONE,
+        );
+
+        assert!(!quits(release));
+    }
+}
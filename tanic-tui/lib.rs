@@ -1,58 +1,135 @@
 use crossterm::event::{Event, EventStream};
-use ratatui::Frame;
+use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
+use futures::Stream;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::{DefaultTerminal, Frame, Terminal};
 use tokio::sync::mpsc::UnboundedSender as MpscSender;
 use tokio::sync::watch::Receiver as WatchReceiver;
 use tokio_stream::{wrappers::WatchStream, StreamExt};
 
+use crate::keybindings::Command;
+use crate::theme::Theme;
 use crate::ui_components::app_container::AppContainer;
-use tanic_core::{Result, TanicError};
-use tanic_svc::{TanicAction, TanicAppState};
+use crate::ui_state::UiState;
+use std::time::Duration;
+use tanic_core::{Result, TanicConfig, TanicError};
+use tanic_svc::{TanicAction, TanicTabsState};
 
+pub mod headless;
+mod icons;
+mod keybindings;
+#[cfg(test)]
+mod test_support;
+mod theme;
 mod ui_components;
+mod ui_state;
+
+/// How often animations such as spinners advance
+const ANIMATION_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Put the terminal in raw mode on the alternate screen, ready to draw to.
+///
+/// Unlike `ratatui::init`, this doesn't install a panic hook that restores
+/// the terminal: panics that are recovered from mustn't take the terminal
+/// away from the UI, so the app's own hook decides what a panic does.
+pub fn init_terminal() -> std::io::Result<DefaultTerminal> {
+    enable_raw_mode()?;
+    if let Err(err) = crossterm::execute!(std::io::stdout(), EnterAlternateScreen) {
+        ratatui::restore();
+        return Err(err);
+    }
+
+    Terminal::new(CrosstermBackend::new(std::io::stdout()))
+}
 
 pub struct TanicTui {
     action_tx: MpscSender<TanicAction>,
+    theme: Theme,
 }
 
 impl TanicTui {
-    pub fn new(action_tx: MpscSender<TanicAction>) -> Self {
-        Self { action_tx }
+    pub fn new(action_tx: MpscSender<TanicAction>, config: &TanicConfig) -> Self {
+        Self {
+            action_tx,
+            theme: Theme::from_config(&config.theme),
+        }
+    }
+
+    pub async fn event_loop(self, state_rx: WatchReceiver<TanicTabsState>) -> Result<()> {
+        self.event_loop_with(EventStream::new(), state_rx).await
     }
 
-    pub async fn event_loop(self, state_rx: WatchReceiver<TanicAppState>) -> Result<()> {
-        let mut terminal = ratatui::init();
-        let mut term_event_stream = EventStream::new();
+    /// Run the UI in the terminal, reading terminal events from
+    /// `term_event_stream` rather than straight from the terminal
+    pub async fn event_loop_with(
+        self,
+        term_event_stream: impl Stream<Item = std::io::Result<Event>> + Unpin,
+        state_rx: WatchReceiver<TanicTabsState>,
+    ) -> Result<()> {
+        let mut terminal = init_terminal()?;
+        let result = self.run(&mut terminal, term_event_stream, state_rx).await;
+        ratatui::restore();
+
+        result
+    }
+
+    /// Run the UI on any terminal backend, reading terminal events from
+    /// `term_event_stream`. The terminal is left as it is when the UI exits.
+    pub async fn run<B: Backend>(
+        self,
+        terminal: &mut Terminal<B>,
+        mut term_event_stream: impl Stream<Item = std::io::Result<Event>> + Unpin,
+        state_rx: WatchReceiver<TanicTabsState>,
+    ) -> Result<()> {
         let mut state_stream = WatchStream::new(state_rx);
 
-        let mut state = TanicAppState::Initializing;
+        let mut animation_ticker = tokio::time::interval(ANIMATION_INTERVAL);
+
+        let mut state = TanicTabsState::default();
+        let mut ui_state = UiState::default();
 
-        while !matches!(&state, TanicAppState::Exiting) {
-            let ui = AppContainer::new(&state);
+        while !state.exiting {
+            let ui = AppContainer::new(&state, &ui_state, &self.theme);
             terminal.draw(|frame| self.draw(frame, &ui))?;
+            let is_animated = ui.is_animated();
 
             tokio::select! {
                 // Catch and handle crossterm events
                 maybe_event = term_event_stream.next() => match maybe_event {
-                    Some(Ok(Event::Key(key)))  => {
-                        if let Some(action) = ui.handle_key_event(key) {
+                    Some(Ok(Event::Key(key)))  => match ui.handle_key_event(key) {
+                        Some(Command::Action(action)) => {
                             self.action_tx.send(action)
                                 .map_err(|err| TanicError::UnexpectedError(
                                       err.to_string()
                                 ))?;
-                        }
+                        },
+                        Some(command) => {
+                            if let Some(action) = ui_state.handle_command(command, &state) {
+                                self.action_tx.send(action)
+                                    .map_err(|err| TanicError::UnexpectedError(
+                                          err.to_string()
+                                    ))?;
+                            }
+                        },
+                        None => (),
                     },
                     None => break,
                     _ => (),
                 },
 
-                // Handle state updates
-                Some(new_state) = state_stream.next() => {
-                    state = new_state;
+                // Handle state updates, stopping if the app state has gone
+                maybe_state = state_stream.next() => match maybe_state {
+                    Some(new_state) => state = new_state,
+                    None => break,
+                },
+
+                // Redraw spinners
+                _ = animation_ticker.tick(), if is_animated => {
+                    ui_state.tick = ui_state.tick.wrapping_add(1);
                 },
             }
         }
 
-        ratatui::restore();
         Ok(())
     }
 
@@ -0,0 +1,148 @@
+//! Fixtures for rendering views in tests
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::backend::TestBackend;
+use ratatui::widgets::Widget;
+use ratatui::Terminal;
+use uuid::Uuid;
+
+use tanic_core::config::{ConnectionDetails, IconSet, ThemeConfig};
+use tanic_core::message::{NamespaceDeets, TableDeets, TableSummary};
+use tanic_svc::comparison::TableRef;
+use tanic_svc::state::{ViewingNamespacesListState, ViewingTablesListState};
+use tanic_svc::TanicAppState;
+
+use crate::theme::Theme;
+use crate::ui_components::confirm_dialog::ConfirmForm;
+use crate::ui_components::export_dialog::ExportForm;
+use crate::ui_components::filter_dialog::FilterForm;
+use crate::ui_components::maintenance_dialog::MaintenanceForm;
+use crate::ui_components::time_travel_dialog::TimeTravelForm;
+
+/// Terminal sizes that views are snapshotted at, as (width, height)
+pub(crate) const SIZES: &[(u16, u16)] = &[(40, 12), (80, 24), (120, 40)];
+
+/// A theme with ASCII icons, so that snapshots don't depend on fonts
+pub(crate) fn theme() -> Theme {
+    Theme::from_config(&ThemeConfig {
+        icons: IconSet::Ascii,
+        ..ThemeConfig::default()
+    })
+}
+
+pub(crate) fn conn_details(name: &str) -> ConnectionDetails {
+    ConnectionDetails {
+        id: Uuid::nil(),
+        name: name.to_string(),
+        uri: format!("http://{name}:8181").parse().unwrap(),
+    }
+}
+
+/// Table "orders" in namespace "sales" of catalog "prod"
+pub(crate) fn orders_table() -> TableRef {
+    TableRef {
+        connection: conn_details("prod"),
+        namespace: vec!["sales".to_string()],
+        name: "orders".to_string(),
+    }
+}
+
+/// A form that text can be typed into
+pub(crate) trait TypeInto {
+    fn edit(&mut self, key_event: KeyEvent);
+}
+
+impl TypeInto for ConfirmForm {
+    fn edit(&mut self, key_event: KeyEvent) {
+        ConfirmForm::edit(self, key_event)
+    }
+}
+
+impl TypeInto for ExportForm {
+    fn edit(&mut self, key_event: KeyEvent) {
+        ExportForm::edit(self, key_event)
+    }
+}
+
+impl TypeInto for FilterForm {
+    fn edit(&mut self, key_event: KeyEvent) {
+        FilterForm::edit(self, key_event)
+    }
+}
+
+impl TypeInto for MaintenanceForm {
+    fn edit(&mut self, key_event: KeyEvent) {
+        MaintenanceForm::edit(self, key_event)
+    }
+}
+
+impl TypeInto for TimeTravelForm {
+    fn edit(&mut self, key_event: KeyEvent) {
+        TimeTravelForm::edit(self, key_event)
+    }
+}
+
+/// Type text into a form, a key at a time
+pub(crate) fn type_text(form: &mut impl TypeInto, text: &str) {
+    for c in text.chars() {
+        form.edit(KeyEvent::from(KeyCode::Char(c)));
+    }
+}
+
+pub(crate) fn namespaces_state(
+    namespaces: &[(&str, usize)],
+    selected_idx: Option<usize>,
+) -> TanicAppState {
+    TanicAppState::ViewingNamespacesList(ViewingNamespacesListState {
+        namespaces: namespaces
+            .iter()
+            .map(|(name, table_count)| NamespaceDeets {
+                parts: vec![name.to_string()],
+                name: name.to_string(),
+                table_count: *table_count,
+                stale: false,
+            })
+            .collect(),
+        selected_idx,
+        refreshing: false,
+    })
+}
+
+/// A table list for namespace "ns", of tables with the given row counts
+pub(crate) fn tables_state(tables: &[(&str, usize)], selected_idx: Option<usize>) -> TanicAppState {
+    TanicAppState::ViewingTablesList(ViewingTablesListState {
+        namespace: NamespaceDeets {
+            parts: vec!["ns".to_string()],
+            name: "ns".to_string(),
+            table_count: tables.len(),
+            stale: false,
+        },
+        tables: tables
+            .iter()
+            .map(|(name, row_count)| TableDeets {
+                namespace: vec!["ns".to_string()],
+                name: name.to_string(),
+                row_count: *row_count,
+                summary: Some(TableSummary {
+                    total_size_bytes: Some(*row_count as u64 * 100),
+                    data_file_count: Some(1),
+                    record_count: Some(*row_count as u64),
+                    last_updated_ms: 0,
+                    findings: vec![],
+                }),
+                stale: false,
+            })
+            .collect(),
+        selected_idx,
+        refreshing: false,
+    })
+}
+
+/// Render a widget into a terminal of the given size
+pub(crate) fn render(widget: impl Widget, (width, height): (u16, u16)) -> TestBackend {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|frame| frame.render_widget(widget, frame.area()))
+        .unwrap();
+    terminal.backend().clone()
+}
@@ -0,0 +1,454 @@
+//! End-to-end navigation flows, against in-memory catalogs
+
+mod support;
+
+use crossterm::event::KeyCode;
+use std::time::Duration;
+use tanic_svc::testing::{MemoryCatalog, MemoryCatalogConnector};
+
+use support::{catalog_uri, conn_details, TestApp};
+
+const SIZE: (u16, u16) = (100, 30);
+
+fn warehouse() -> MemoryCatalog {
+    MemoryCatalog::new()
+        .with_table(&["hr"], "employees", 10)
+        .with_table(&["sales"], "orders", 5000)
+        .with_table(&["sales"], "customers", 1200)
+        .with_namespace(&["staging"])
+}
+
+#[tokio::test]
+async fn browses_namespaces_and_tables() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    let screen = app.wait_for("Root Namespaces").await;
+    assert!(screen.contains("+ hr"), "{screen}");
+    assert!(screen.contains("+ sales"), "{screen}");
+    assert!(screen.contains("+ staging"), "{screen}");
+
+    // namespaces are listed in order, so "hr" is selected first
+    app.press(KeyCode::Enter);
+    app.wait_for("hr Namespace").await;
+    app.wait_for("# employees").await;
+
+    app.press(KeyCode::Esc);
+    app.wait_for("Root Namespaces").await;
+
+    app.press(KeyCode::Right);
+    app.press(KeyCode::Enter);
+    let screen = app.wait_for("sales Namespace").await;
+    assert!(screen.contains("# orders"), "{screen}");
+    assert!(screen.contains("# customers"), "{screen}");
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn shows_an_empty_namespace() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Left);
+    app.press(KeyCode::Enter);
+    let screen = app.wait_for("No tables in this namespace").await;
+    assert!(screen.contains("staging Namespace"), "{screen}");
+
+    // moving the focus around an empty list does nothing
+    app.press(KeyCode::Right);
+    app.press(KeyCode::Left);
+    app.press(KeyCode::Enter);
+    app.wait_for("No tables in this namespace").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn navigates_forward_again_after_going_back() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+
+    app.press(KeyCode::Backspace);
+    app.wait_for("Root Namespaces").await;
+
+    app.press(KeyCode::Char(']'));
+    app.wait_for("# employees").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn cancels_a_slow_request() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(
+            catalog_uri("slow"),
+            warehouse().with_latency(Duration::from_secs(60)),
+        ),
+        SIZE,
+    );
+    app.open(conn_details("slow"));
+
+    app.wait_for("Connecting to slow").await;
+    app.press(KeyCode::Esc);
+    app.wait_for("Request cancelled").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn switches_between_tabs() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default()
+            .with_catalog(catalog_uri("prod"), warehouse())
+            .with_catalog(
+                catalog_uri("dev"),
+                MemoryCatalog::new().with_table(&["scratch"], "experiments", 1),
+            ),
+        SIZE,
+    );
+    app.open(conn_details("prod"));
+    app.open(conn_details("dev"));
+
+    let screen = app.wait_for("1: prod").await;
+    assert!(screen.contains("2: dev"), "{screen}");
+    app.wait_for("+ sales").await;
+
+    app.press(KeyCode::Tab);
+    app.wait_for("+ scratch").await;
+
+    app.press(KeyCode::Char('w'));
+    app.wait_for_absence("2: dev").await;
+    app.wait_for("+ sales").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn compares_tables_across_catalogs() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default()
+            .with_catalog(catalog_uri("prod"), warehouse())
+            .with_catalog(
+                catalog_uri("staging"),
+                MemoryCatalog::new().with_table(&["sales"], "orders", 42),
+            ),
+        SIZE,
+    );
+    app.open(conn_details("prod"));
+    app.open(conn_details("staging"));
+
+    // mark prod's orders table
+    app.wait_for("+ sales").await;
+    app.press(KeyCode::Right);
+    app.press(KeyCode::Enter);
+    app.wait_for("# orders").await;
+    app.press(KeyCode::Right);
+    app.press(KeyCode::Char('m'));
+    app.wait_for("marked: prod: sales.orders").await;
+
+    // and compare staging's against it
+    app.press(KeyCode::Tab);
+    app.wait_for("+ sales").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# orders").await;
+    app.press(KeyCode::Char('c'));
+
+    let screen = app.wait_for("Table Comparison").await;
+    assert!(screen.contains("prod: sales.orders"), "{screen}");
+    assert!(screen.contains("staging: sales.orders"), "{screen}");
+    assert!(!screen.contains("not found"), "{screen}");
+
+    app.press(KeyCode::Char('d'));
+    app.wait_for("differences only").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn exports_the_selected_table() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("employees.ndjson");
+
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+
+    app.press(KeyCode::Char('x'));
+    app.wait_for("Export warehouse: hr.employees").await;
+
+    // replace the suggested file with one in the temporary directory
+    for _ in 0.."employees.csv".len() {
+        app.press(KeyCode::Backspace);
+    }
+    app.type_text(&path.display().to_string());
+    app.press(KeyCode::Enter);
+
+    // fixture tables have no data files to scan, so the export fails, and
+    // its partial output is cleaned up
+    let screen = app.wait_for("failed").await;
+    assert!(screen.contains(" Exports "), "{screen}");
+    assert!(
+        screen.contains(&format!("employees → {}", path.display())),
+        "{screen}"
+    );
+    assert!(!path.exists());
+
+    app.press(KeyCode::Char('X'));
+    app.wait_for_absence(" Exports ").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn previews_a_table_as_of_a_snapshot() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("current, snapshot 1").await;
+
+    app.press(KeyCode::Char('s'));
+    let screen = app.wait_for(" Schema ").await;
+    assert!(screen.contains("schema 0"), "{screen}");
+
+    app.press(KeyCode::Char('t'));
+    app.wait_for("Time travel employees").await;
+    app.press(KeyCode::Char('1'));
+    app.press(KeyCode::Enter);
+    let screen = app.wait_for("as of snapshot 1").await;
+    assert!(screen.contains(" Schema "), "{screen}");
+
+    app.press(KeyCode::Backspace);
+    app.wait_for("current, snapshot 1").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn filters_a_table_preview() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("current, snapshot 1").await;
+
+    app.press(KeyCode::Char('f'));
+    app.wait_for("Filter employees").await;
+    app.type_text("id > 3 and name = 'Ada'");
+    app.press(KeyCode::BackTab);
+    app.type_text("name");
+    app.press(KeyCode::Enter);
+    app.wait_for("name · where id > 3 AND name = 'Ada'").await;
+
+    app.press(KeyCode::Char('F'));
+    app.wait_for_absence("where id > 3").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn explains_the_scan_of_a_table_preview() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("current, snapshot 1").await;
+
+    // fixture tables have no manifests to plan a scan from
+    app.press(KeyCode::Char('e'));
+    let screen = app.wait_for("Tanic //// Error").await;
+    assert!(screen.contains("snap-1.avro"), "{screen}");
+
+    app.press(KeyCode::Esc);
+    app.wait_for("current, snapshot 1").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn profiles_the_columns_of_the_selected_table() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+
+    // fixture tables have no manifests to aggregate metrics from
+    app.press(KeyCode::Char('p'));
+    let screen = app.wait_for("Tanic //// Error").await;
+    assert!(screen.contains("snap-1.avro"), "{screen}");
+
+    app.press(KeyCode::Esc);
+    app.wait_for("hr Namespace").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn inspects_the_delete_files_of_a_table_preview() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("current, snapshot 1").await;
+
+    // fixture tables have no manifests to tally delete files from
+    app.press(KeyCode::Char('d'));
+    let screen = app.wait_for("Tanic //// Error").await;
+    assert!(screen.contains("snap-1.avro"), "{screen}");
+
+    app.press(KeyCode::Esc);
+    app.wait_for("current, snapshot 1").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn checks_the_health_of_a_namespace() {
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), warehouse()),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Right);
+    app.press(KeyCode::Enter);
+
+    // fixture tables declare no sort order, so their tiles are badged
+    app.wait_for("/100").await;
+
+    app.press(KeyCode::Char('H'));
+    let screen = app.wait_for("2 tables checked").await;
+    assert!(screen.contains("no sort order declared"), "{screen}");
+    assert!(screen.contains("customers"), "{screen}");
+    assert!(screen.contains("orders"), "{screen}");
+
+    app.press(KeyCode::Esc);
+    app.wait_for("sales Namespace").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn expires_snapshots_once_the_dry_run_is_confirmed() {
+    let catalog = MemoryCatalog::new().with_table_history(&["hr"], "employees", 10, 4);
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), catalog),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+
+    // every fixture snapshot is more than 7 days old, but the current one is kept
+    app.press(KeyCode::Char('E'));
+    app.wait_for("Expire snapshots of employees").await;
+    app.press(KeyCode::Enter);
+    let screen = app.wait_for("3 to remove · 1 kept").await;
+    assert!(
+        screen.contains("Dry run: nothing has been removed"),
+        "{screen}"
+    );
+
+    app.press(KeyCode::Enter);
+    app.wait_for("Type the table's name, employees, to confirm")
+        .await;
+    app.type_text("orders");
+    app.press(KeyCode::Enter);
+    app.wait_for("type employees to confirm").await;
+
+    for _ in 0.."orders".len() {
+        app.press(KeyCode::Backspace);
+    }
+    app.type_text("employees");
+    app.press(KeyCode::Enter);
+    app.wait_for("Removed 3 of 3").await;
+
+    app.press(KeyCode::Char('r'));
+    app.wait_for("Nothing to remove").await;
+
+    app.quit().await;
+}
+
+#[tokio::test]
+async fn finishes_maintenance_being_applied_before_exiting() {
+    let catalog = MemoryCatalog::new()
+        .with_table_history(&["hr"], "employees", 10, 4)
+        .with_latency(Duration::from_millis(200));
+    let app = TestApp::start(
+        MemoryCatalogConnector::default().with_catalog(catalog_uri("warehouse"), catalog),
+        SIZE,
+    );
+    app.open(conn_details("warehouse"));
+
+    app.wait_for("Root Namespaces").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("# employees").await;
+
+    app.press(KeyCode::Char('E'));
+    app.wait_for("Expire snapshots of employees").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("3 to remove · 1 kept").await;
+    app.press(KeyCode::Enter);
+    app.wait_for("to confirm").await;
+    app.type_text("employees");
+    app.press(KeyCode::Enter);
+
+    app.wait_for("Applying").await;
+    app.press(KeyCode::Char('q'));
+    app.wait_for("Finishing maintenance before exiting").await;
+
+    app.quit().await;
+}
@@ -0,0 +1,148 @@
+//! End-to-end test harness
+//!
+//! Runs the app state, catalog and UI tasks together against in-memory
+//! catalogs, with the UI drawing to a test backend. Tests script key events
+//! and wait for the rendered screen to show what they expect.
+
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::Terminal;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use uuid::Uuid;
+
+use tanic_core::config::{ConnectionDetails, IconSet};
+use tanic_core::TanicConfig;
+use tanic_svc::iceberg_context::IcebergContextManager;
+use tanic_svc::testing::MemoryCatalogConnector;
+use tanic_svc::{AppStateManager, TanicAction};
+use tanic_tui::headless::HeadlessBackend;
+use tanic_tui::TanicTui;
+
+/// How long to wait for the screen to show something before failing
+const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn conn_details(name: &str) -> ConnectionDetails {
+    ConnectionDetails {
+        id: Uuid::nil(),
+        name: name.to_string(),
+        uri: catalog_uri(name),
+    }
+}
+
+pub fn catalog_uri(name: &str) -> http::Uri {
+    format!("http://{name}:8181").parse().unwrap()
+}
+
+/// A running app
+pub struct TestApp {
+    screen: HeadlessBackend,
+    event_tx: UnboundedSender<io::Result<Event>>,
+    action_tx: UnboundedSender<TanicAction>,
+    tasks: Vec<JoinHandle<tanic_core::Result<()>>>,
+}
+
+impl TestApp {
+    pub fn start(connector: MemoryCatalogConnector, (width, height): (u16, u16)) -> Self {
+        let mut config = TanicConfig::default();
+        config.cache.persist = false;
+        config.theme.icons = IconSet::Ascii;
+
+        let (app_state, action_tx, state_rx, command_rx) = AppStateManager::new(config.clone());
+        let iceberg_ctx_mgr = IcebergContextManager::new(action_tx.clone(), &config)
+            .with_connector(Arc::new(connector));
+        let tanic_tui = TanicTui::new(action_tx.clone(), &config);
+
+        let screen = HeadlessBackend::new(width, height);
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let mut terminal = Terminal::new(screen.clone()).unwrap();
+        let tasks = vec![
+            tokio::spawn(app_state.event_loop()),
+            tokio::spawn(iceberg_ctx_mgr.event_loop(command_rx)),
+            tokio::spawn(async move {
+                tanic_tui
+                    .run(
+                        &mut terminal,
+                        UnboundedReceiverStream::new(event_rx),
+                        state_rx,
+                    )
+                    .await
+            }),
+        ];
+
+        Self {
+            screen,
+            event_tx,
+            action_tx,
+            tasks,
+        }
+    }
+
+    /// Open a connection in a new tab, as if it were given on the command line
+    pub fn open(&self, conn_details: ConnectionDetails) {
+        self.action_tx
+            .send(TanicAction::OpenTab(conn_details))
+            .unwrap();
+    }
+
+    pub fn press(&self, key: KeyCode) {
+        self.event_tx
+            .send(Ok(Event::Key(KeyEvent::from(key))))
+            .unwrap();
+    }
+
+    /// Type text into the open form, a key at a time
+    pub fn type_text(&self, text: &str) {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c));
+        }
+    }
+
+    pub fn screen(&self) -> String {
+        self.screen.screen()
+    }
+
+    /// Wait for the screen to show some text, returning the screen
+    pub async fn wait_for(&self, text: &str) -> String {
+        self.wait_until(&format!("{text:?}"), |screen| screen.contains(text))
+            .await
+    }
+
+    /// Wait for the screen to no longer show some text, returning the screen
+    pub async fn wait_for_absence(&self, text: &str) -> String {
+        self.wait_until(&format!("no {text:?}"), |screen| !screen.contains(text))
+            .await
+    }
+
+    async fn wait_until(&self, description: &str, condition: impl Fn(&str) -> bool) -> String {
+        let deadline = tokio::time::Instant::now() + WAIT_TIMEOUT;
+
+        loop {
+            let screen = self.screen();
+            if condition(&screen) {
+                return screen;
+            }
+            if tokio::time::Instant::now() > deadline {
+                panic!("timed out waiting for {description}, screen was:\n{screen}");
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Quit, and wait for every task to finish
+    pub async fn quit(self) {
+        self.press(KeyCode::Char('q'));
+
+        for task in self.tasks {
+            tokio::time::timeout(WAIT_TIMEOUT, task)
+                .await
+                .expect("task did not finish after quitting")
+                .unwrap()
+                .unwrap();
+        }
+    }
+}
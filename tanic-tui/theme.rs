@@ -0,0 +1,268 @@
+//! UI Themes
+//!
+//! Built-in colour schemes, selected by the `[theme]` section of the config.
+//! Setting `NO_COLOR` in the environment overrides the configured preset.
+//! See <https://no-color.org>.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ratatui::prelude::*;
+use tanic_core::config::{IconSet, ThemeConfig, ThemePreset, TileMetric};
+use tanic_core::message::TableDeets;
+
+use crate::icons::Icons;
+
+const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Theme {
+    /// default style for text
+    pub(crate) base: Style,
+
+    /// style for view titles
+    pub(crate) title: Style,
+
+    /// style for key names in key hints
+    pub(crate) key_hint: Style,
+
+    /// outline colour of treemap tiles
+    pub(crate) tile: Color,
+
+    /// label style for unselected treemap tiles
+    pub(crate) tile_label: Style,
+
+    /// label style for the selected treemap tile
+    pub(crate) tile_selected: Style,
+
+    /// style for the log panel
+    pub(crate) log: Style,
+
+    /// style for values that differ between compared tables
+    pub(crate) diff: Style,
+
+    /// style for error messages
+    pub(crate) error: Style,
+
+    /// colours used for tiles coloured by a metric, from lowest to highest
+    pub(crate) metric_scale: Vec<Color>,
+
+    /// metric that tiles are coloured by
+    pub(crate) tile_metric: TileMetric,
+
+    /// icons used in tile labels
+    pub(crate) icons: Icons,
+}
+
+impl Theme {
+    pub(crate) fn from_config(config: &ThemeConfig) -> Self {
+        let theme = if no_color_requested() {
+            Self::no_color()
+        } else {
+            match config.preset {
+                ThemePreset::Dark => Self::dark(),
+                ThemePreset::Light => Self::light(),
+                ThemePreset::HighContrast => Self::high_contrast(),
+            }
+        };
+
+        Self {
+            tile_metric: config.tile_metric,
+            icons: Icons::new(config.icons),
+            ..theme
+        }
+    }
+
+    fn dark() -> Self {
+        Self {
+            base: Style::new().white(),
+            title: Style::new().white().bold(),
+            key_hint: Style::new().blue().bold(),
+            tile: Color::White,
+            tile_label: Style::new().white(),
+            tile_selected: Style::new().black().bold().on_white(),
+            log: Style::new().white(),
+            diff: Style::new().yellow(),
+            error: Style::new().red().bold(),
+            metric_scale: vec![
+                Color::Blue,
+                Color::Cyan,
+                Color::Green,
+                Color::Yellow,
+                Color::Red,
+            ],
+            tile_metric: TileMetric::None,
+            icons: Icons::new(IconSet::default()),
+        }
+    }
+
+    fn light() -> Self {
+        Self {
+            base: Style::new().black(),
+            title: Style::new().black().bold(),
+            key_hint: Style::new().blue().bold(),
+            tile: Color::DarkGray,
+            tile_label: Style::new().black(),
+            tile_selected: Style::new().white().bold().on_black(),
+            log: Style::new().black(),
+            diff: Style::new().red(),
+            error: Style::new().red().bold(),
+            metric_scale: vec![Color::Blue, Color::Green, Color::Magenta, Color::Red],
+            tile_metric: TileMetric::None,
+            icons: Icons::new(IconSet::default()),
+        }
+    }
+
+    fn high_contrast() -> Self {
+        Self {
+            base: Style::new().white().bold(),
+            title: Style::new().yellow().bold(),
+            key_hint: Style::new().yellow().bold(),
+            tile: Color::White,
+            tile_label: Style::new().white().bold(),
+            tile_selected: Style::new().black().bold().on_yellow(),
+            log: Style::new().white(),
+            diff: Style::new().light_red().bold(),
+            error: Style::new().light_red().bold(),
+            metric_scale: vec![Color::LightCyan, Color::LightYellow, Color::LightRed],
+            tile_metric: TileMetric::None,
+            icons: Icons::new(IconSet::default()),
+        }
+    }
+
+    fn no_color() -> Self {
+        Self {
+            base: Style::new(),
+            title: Style::new().bold(),
+            key_hint: Style::new().bold(),
+            tile: Color::Reset,
+            tile_label: Style::new(),
+            tile_selected: Style::new().bold().reversed(),
+            log: Style::new(),
+            diff: Style::new().underlined(),
+            error: Style::new().bold(),
+            metric_scale: vec![],
+            tile_metric: TileMetric::None,
+            icons: Icons::new(IconSet::default()),
+        }
+    }
+
+    /// Colour a table's tile according to the configured metric.
+    ///
+    /// `range` is the (min, max) of the metric across all tables in view.
+    pub(crate) fn metric_color(&self, value: Option<f64>, range: Option<(f64, f64)>) -> Color {
+        let (Some(value), Some((min, max))) = (value, range) else {
+            return self.tile;
+        };
+        if self.metric_scale.is_empty() {
+            return self.tile;
+        }
+
+        let position = if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.0
+        };
+        let bucket = (position * (self.metric_scale.len() - 1) as f64).round() as usize;
+
+        self.metric_scale[bucket.min(self.metric_scale.len() - 1)]
+    }
+
+    /// Legend describing the metric colour scale, if tiles are coloured by a metric
+    pub(crate) fn metric_legend(&self, range: Option<(f64, f64)>) -> Option<Line<'static>> {
+        let (min, max) = range?;
+        if self.metric_scale.is_empty() {
+            return None;
+        }
+
+        let mut spans = vec![
+            Span::styled(format!(" {}: ", metric_name(self.tile_metric)), self.base),
+            Span::styled(format_metric(self.tile_metric, min), self.base),
+            Span::raw(" "),
+        ];
+        spans.extend(
+            self.metric_scale
+                .iter()
+                .map(|color| Span::styled("■", Style::new().fg(*color))),
+        );
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format_metric(self.tile_metric, max),
+            self.base,
+        ));
+        spans.push(Span::raw(" "));
+
+        Some(Line::from(spans))
+    }
+
+    /// The (min, max) of the configured metric across a set of tables
+    pub(crate) fn metric_range<'a>(
+        &self,
+        tables: impl IntoIterator<Item = &'a TableDeets>,
+    ) -> Option<(f64, f64)> {
+        tables
+            .into_iter()
+            .filter_map(|table| metric_value(self.tile_metric, table))
+            .fold(None, |range, value| match range {
+                None => Some((value, value)),
+                Some((min, max)) => Some((value.min(min), value.max(max))),
+            })
+    }
+}
+
+/// Value of a metric for a table, if the table's summary has it
+pub(crate) fn metric_value(metric: TileMetric, table: &TableDeets) -> Option<f64> {
+    let summary = table.summary.as_ref()?;
+
+    match metric {
+        TileMetric::None => None,
+        TileMetric::Size => summary.total_size_bytes.map(|size| size as f64),
+        TileMetric::FileCount => summary.data_file_count.map(|count| count as f64),
+        TileMetric::Staleness => {
+            let now_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|now| now.as_millis() as i64)
+                .unwrap_or_default();
+
+            Some((now_ms - summary.last_updated_ms).max(0) as f64)
+        }
+    }
+}
+
+fn metric_name(metric: TileMetric) -> &'static str {
+    match metric {
+        TileMetric::None => "",
+        TileMetric::Size => "Size",
+        TileMetric::Staleness => "Staleness",
+        TileMetric::FileCount => "Files",
+    }
+}
+
+fn format_metric(metric: TileMetric, value: f64) -> String {
+    match metric {
+        TileMetric::None => String::new(),
+        TileMetric::Size => format_bytes(value as u64),
+        TileMetric::Staleness => format!("{:.1}d", value / MS_PER_DAY),
+        TileMetric::FileCount => format!("{}", value as u64),
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|val| !val.is_empty())
+}
@@ -1,81 +1,344 @@
+use crate::keybindings::{
+    self, Command, FormCommand, KeyBinding, GLOBAL_KEY_BINDINGS, HELP_KEY_BINDINGS,
+};
+use crate::theme::Theme;
 use crate::ui_components::{
-    namespace_list_view::NamespaceListView, splash_screen::SplashScreen,
-    table_list_view::TableListView,
+    confirm_dialog::ConfirmDialog, confirm_dialog::ConfirmForm,
+    delete_health_view::DeleteHealthView, error_view::ErrorView, export_dialog::ExportDialog,
+    export_dialog::ExportForm, export_progress::ExportProgress, filter_dialog::FilterDialog,
+    filter_dialog::FilterForm, footer_bar::FooterBar, health_report_view::HealthReportView,
+    help_overlay::HelpOverlay, loading_view::LoadingView, log_panel::LogPanel,
+    maintenance_dialog::MaintenanceDialog, maintenance_dialog::MaintenanceForm,
+    maintenance_plan_view::MaintenancePlanView, namespace_list_view::NamespaceListView,
+    scan_plan_view::ScanPlanView, splash_screen::SplashScreen, tab_bar::TabBar,
+    table_comparison_view::TableComparisonView, table_list_view::TableListView,
+    table_preview_view::TablePreviewView, table_profile_view::TableProfileView,
+    time_travel_dialog::TimeTravelDialog, time_travel_dialog::TimeTravelForm,
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui_state::{LogPanelMode, UiState};
+use crossterm::event::{KeyEvent, KeyEventKind};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::prelude::{Color, Style, Widget};
-use ratatui::widgets::Block;
-use tanic_svc::{TanicAction, TanicAppState};
-use tui_logger::{LevelFilter, TuiLoggerLevelOutput, TuiLoggerWidget, TuiWidgetState};
+use ratatui::prelude::Widget;
+use tanic_svc::{TanicAppState, TanicTabsState};
+
+/// What to show before any connection has been opened
+const NO_TABS_STATE: &TanicAppState = &TanicAppState::Initializing;
 
 pub(crate) struct AppContainer<'a> {
+    tabs_state: &'a TanicTabsState,
     state: &'a TanicAppState,
+    ui_state: &'a UiState,
+    theme: &'a Theme,
     namespace_list_view: NamespaceListView<'a>,
     table_list_view: TableListView<'a>,
+    table_comparison_view: TableComparisonView<'a>,
+    table_preview_view: TablePreviewView<'a>,
+    scan_plan_view: ScanPlanView<'a>,
+    table_profile_view: TableProfileView<'a>,
+    delete_health_view: DeleteHealthView<'a>,
+    health_report_view: HealthReportView<'a>,
+    maintenance_plan_view: MaintenancePlanView<'a>,
     splash_screen: SplashScreen<'a>,
+    loading_view: LoadingView<'a>,
+    error_view: ErrorView<'a>,
 }
 
 impl<'a> AppContainer<'a> {
-    pub(crate) fn new(state: &'a TanicAppState) -> Self {
+    pub(crate) fn new(
+        tabs_state: &'a TanicTabsState,
+        ui_state: &'a UiState,
+        theme: &'a Theme,
+    ) -> Self {
+        let state = tabs_state
+            .active_tab()
+            .map(|tab| &tab.state)
+            .unwrap_or(NO_TABS_STATE);
+
         Self {
+            tabs_state,
             state,
+            ui_state,
+            theme,
 
-            namespace_list_view: NamespaceListView::new(state),
-            table_list_view: TableListView::new(state),
-            splash_screen: SplashScreen::new(state),
+            namespace_list_view: NamespaceListView::new(state, theme),
+            table_list_view: TableListView::new(state, tabs_state.comparison_mark.as_ref(), theme),
+            table_comparison_view: TableComparisonView::new(state, theme),
+            table_preview_view: TablePreviewView::new(state, theme),
+            scan_plan_view: ScanPlanView::new(state, theme),
+            table_profile_view: TableProfileView::new(state, theme),
+            delete_health_view: DeleteHealthView::new(state, theme),
+            health_report_view: HealthReportView::new(state, theme),
+            maintenance_plan_view: MaintenancePlanView::new(state, theme),
+            splash_screen: SplashScreen::new(state, theme),
+            loading_view: LoadingView::new(state, theme, ui_state.tick, tabs_state.exit_pending),
+            error_view: ErrorView::new(state, theme),
         }
     }
 
-    pub(crate) fn handle_key_event(&self, key_event: KeyEvent) -> Option<TanicAction> {
-        match key_event {
-            KeyEvent {
-                code: KeyCode::Char('q'),
-                ..
-            } => {
-                // User pressed Q. Dispatch an exit action
-                Some(TanicAction::Exit)
-            }
-            key_event => match &self.state {
-                TanicAppState::ViewingNamespacesList(_) => {
-                    self.namespace_list_view.handle_key_event(key_event)
+    /// Key bindings for the current view, most specific first
+    pub(crate) fn view_key_bindings(&self) -> Vec<&'static KeyBinding> {
+        let view_bindings: &[KeyBinding] = match (&self.state, self.ui_state.log_panel_mode) {
+            (_, LogPanelMode::Maximised) => LogPanel::MAXIMISED_KEY_BINDINGS,
+            (TanicAppState::ViewingNamespacesList(_), _) => NamespaceListView::KEY_BINDINGS,
+            (TanicAppState::ViewingTablesList(_), _) => TableListView::KEY_BINDINGS,
+            (TanicAppState::ViewingTableComparison(_), _) => TableComparisonView::KEY_BINDINGS,
+            (TanicAppState::ViewingTablePreview(_), _) => TablePreviewView::KEY_BINDINGS,
+            (TanicAppState::ViewingScanPlan(_), _) => ScanPlanView::KEY_BINDINGS,
+            (TanicAppState::ViewingTableProfile(_), _) => TableProfileView::KEY_BINDINGS,
+            (TanicAppState::ViewingDeleteHealth(_), _) => DeleteHealthView::KEY_BINDINGS,
+            (TanicAppState::ViewingHealthReport(_), _) => HealthReportView::KEY_BINDINGS,
+            (TanicAppState::ViewingMaintenancePlan(view_state), _) => {
+                if view_state.is_applicable() {
+                    MaintenancePlanView::KEY_BINDINGS
+                } else {
+                    // nothing left to apply
+                    &MaintenancePlanView::KEY_BINDINGS[1..]
                 }
-                TanicAppState::ViewingTablesList(_) => {
-                    self.table_list_view.handle_key_event(key_event)
-                }
-                _ => None,
-            },
+            }
+            // removals can't be abandoned partway through
+            (TanicAppState::ApplyingMaintenance(_), _) => &[],
+            (state, _) if state.is_pending() => LoadingView::KEY_BINDINGS,
+            _ => &[],
+        };
+
+        view_bindings.iter().chain(GLOBAL_KEY_BINDINGS).collect()
+    }
+
+    /// Key bindings that are currently active, taking any open overlay into account
+    pub(crate) fn key_bindings(&self) -> Vec<&'static KeyBinding> {
+        if self.ui_state.export_form.is_some() {
+            // other keys are typed into the form
+            ExportForm::KEY_BINDINGS.iter().collect()
+        } else if self.ui_state.time_travel_form.is_some() {
+            TimeTravelForm::KEY_BINDINGS.iter().collect()
+        } else if self.ui_state.filter_form.is_some() {
+            FilterForm::KEY_BINDINGS.iter().collect()
+        } else if self.ui_state.maintenance_form.is_some() {
+            MaintenanceForm::KEY_BINDINGS.iter().collect()
+        } else if self.ui_state.confirm_form.is_some() {
+            ConfirmForm::KEY_BINDINGS.iter().collect()
+        } else if self.ui_state.show_help {
+            HELP_KEY_BINDINGS
+                .iter()
+                .chain(GLOBAL_KEY_BINDINGS)
+                .collect()
+        } else {
+            self.view_key_bindings()
+        }
+    }
+
+    /// Whether anything on screen is animated, and so needs redrawing
+    /// periodically
+    pub(crate) fn is_animated(&self) -> bool {
+        self.state.is_pending()
+    }
+
+    pub(crate) fn handle_key_event(&self, key_event: KeyEvent) -> Option<Command> {
+        // on Windows, releases are reported too, and would type into forms twice
+        if key_event.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        keybindings::lookup(self.key_bindings(), key_event).or_else(|| {
+            if self.ui_state.export_form.is_some() {
+                Some(Command::ExportForm(FormCommand::Edit(key_event)))
+            } else if self.ui_state.time_travel_form.is_some() {
+                Some(Command::TimeTravelForm(FormCommand::Edit(key_event)))
+            } else if self.ui_state.filter_form.is_some() {
+                Some(Command::FilterForm(FormCommand::Edit(key_event)))
+            } else if self.ui_state.maintenance_form.is_some() {
+                Some(Command::MaintenanceForm(FormCommand::Edit(key_event)))
+            } else if self.ui_state.confirm_form.is_some() {
+                Some(Command::ConfirmForm(FormCommand::Edit(key_event)))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl AppContainer<'_> {
+    fn render_view(&self, area: Rect, buf: &mut Buffer) {
+        match &self.state {
+            TanicAppState::Initializing => self.splash_screen.render(area, buf),
+            TanicAppState::ViewingNamespacesList(_) => self.namespace_list_view.render(area, buf),
+            TanicAppState::ViewingTablesList(_) => self.table_list_view.render(area, buf),
+            TanicAppState::ViewingTableComparison(_) => {
+                self.table_comparison_view.render(area, buf)
+            }
+            TanicAppState::ViewingTablePreview(_) => self.table_preview_view.render(area, buf),
+            TanicAppState::ViewingScanPlan(_) => self.scan_plan_view.render(area, buf),
+            TanicAppState::ViewingTableProfile(_) => self.table_profile_view.render(area, buf),
+            TanicAppState::ViewingDeleteHealth(_) => self.delete_health_view.render(area, buf),
+            TanicAppState::ViewingHealthReport(_) => self.health_report_view.render(area, buf),
+            TanicAppState::ViewingMaintenancePlan(_) => {
+                self.maintenance_plan_view.render(area, buf)
+            }
+            TanicAppState::ShowingError(_) => self.error_view.render(area, buf),
+            TanicAppState::ConnectingTo(_)
+            | TanicAppState::RetrievingTableList(_)
+            | TanicAppState::RetrievingTableComparison(..)
+            | TanicAppState::RetrievingTablePreview(..)
+            | TanicAppState::RetrievingScanPlan(_)
+            | TanicAppState::RetrievingTableProfile(_)
+            | TanicAppState::RetrievingDeleteHealth(_)
+            | TanicAppState::RetrievingHealthReport(_)
+            | TanicAppState::RetrievingMaintenancePlan(_)
+            | TanicAppState::ApplyingMaintenance(_) => self.loading_view.render(area, buf),
         }
     }
 }
 
 impl Widget for &AppContainer<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let [top, bottom] = Layout::vertical([Constraint::Fill(1), Constraint::Max(6)]).areas(area);
-
-        let filter_state = TuiWidgetState::new()
-            .set_default_display_level(LevelFilter::Info)
-            .set_level_for_target("tanic_svc", LevelFilter::Debug);
-
-        TuiLoggerWidget::default()
-            .block(Block::bordered().title("Log"))
-            .output_separator('|')
-            .output_timestamp(Some("%F %H:%M:%S%.3f".to_string()))
-            .output_level(Some(TuiLoggerLevelOutput::Long))
-            .output_target(false)
-            .output_file(false)
-            .output_line(false)
-            .style(Style::default().fg(Color::White))
-            .state(&filter_state)
-            .render(bottom, buf);
+        let tab_bar_height = if self.tabs_state.tabs.is_empty() {
+            0
+        } else {
+            1
+        };
+        let [tab_bar, main, exports, footer] = Layout::vertical([
+            Constraint::Length(tab_bar_height),
+            Constraint::Fill(1),
+            Constraint::Length(ExportProgress::height(&self.tabs_state.exports)),
+            Constraint::Length(1),
+        ])
+        .areas(area);
 
-        match &self.state {
-            TanicAppState::Initializing => self.splash_screen.render(top, buf),
-            TanicAppState::ViewingNamespacesList(_) => self.namespace_list_view.render(top, buf),
-            TanicAppState::ViewingTablesList(_) => self.table_list_view.render(top, buf),
-            TanicAppState::Exiting => {}
-            _ => {}
+        TabBar::new(self.tabs_state, self.theme).render(tab_bar, buf);
+        if !self.tabs_state.exports.is_empty() {
+            ExportProgress::new(&self.tabs_state.exports, self.theme).render(exports, buf);
+        }
+
+        let log_panel_mode = self.ui_state.log_panel_mode;
+        let log_panel = LogPanel::new(
+            &self.ui_state.log_widget_state,
+            self.theme,
+            log_panel_mode == LogPanelMode::Maximised,
+        );
+
+        match log_panel_mode {
+            LogPanelMode::Normal => {
+                let [top, bottom] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Max(6)]).areas(main);
+
+                self.render_view(top, buf);
+                log_panel.render(bottom, buf);
+            }
+            LogPanelMode::Hidden => self.render_view(main, buf),
+            LogPanelMode::Maximised => log_panel.render(main, buf),
+        }
+
+        FooterBar::new(&self.key_bindings(), self.theme).render(footer, buf);
+
+        if self.ui_state.show_help {
+            HelpOverlay::new(&self.view_key_bindings(), self.theme).render(main, buf);
         }
+        if let Some(form) = &self.ui_state.export_form {
+            ExportDialog::new(form, self.theme).render(main, buf);
+        }
+        if let Some(form) = &self.ui_state.time_travel_form {
+            TimeTravelDialog::new(form, self.theme).render(main, buf);
+        }
+        if let Some(form) = &self.ui_state.filter_form {
+            FilterDialog::new(form, self.theme).render(main, buf);
+        }
+        if let Some(form) = &self.ui_state.maintenance_form {
+            MaintenanceDialog::new(form, self.theme).render(main, buf);
+        }
+        if let Some(form) = &self.ui_state.confirm_form {
+            ConfirmDialog::new(form, self.theme).render(main, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{conn_details, namespaces_state, render, tables_state, theme, SIZES};
+    use tanic_core::message::NamespaceDeets;
+    use tanic_svc::{TabId, TanicTab};
+
+    fn tabs_state(states: Vec<TanicAppState>) -> TanicTabsState {
+        TanicTabsState {
+            tabs: states
+                .into_iter()
+                .enumerate()
+                .map(|(idx, state)| TanicTab {
+                    id: TabId::new(idx as u64),
+                    connection: Some(conn_details(&format!("catalog-{}", idx + 1))),
+                    state,
+                })
+                .collect(),
+            ..TanicTabsState::default()
+        }
+    }
+
+    fn snapshot(name: &str, tabs_state: &TanicTabsState, ui_state: &UiState) {
+        let theme = theme();
+
+        for &size in SIZES {
+            let app = AppContainer::new(tabs_state, ui_state, &theme);
+            insta::assert_snapshot!(format!("{name}_{}x{}", size.0, size.1), render(&app, size));
+        }
+    }
+
+    #[test]
+    fn renders_splash_screen_without_tabs() {
+        snapshot("splash", &TanicTabsState::default(), &UiState::default());
+    }
+
+    #[test]
+    fn renders_namespace_list() {
+        let tabs_state = tabs_state(vec![namespaces_state(&[("sales", 8), ("hr", 1)], Some(0))]);
+
+        snapshot("namespaces", &tabs_state, &UiState::default());
+    }
+
+    #[test]
+    fn renders_active_tab_without_log_panel() {
+        let mut tabs_state = tabs_state(vec![
+            namespaces_state(&[("sales", 8)], Some(0)),
+            tables_state(&[("orders", 5000), ("customers", 1200)], Some(1)),
+        ]);
+        tabs_state.active_idx = 1;
+        let ui_state = UiState {
+            log_panel_mode: LogPanelMode::Hidden,
+            ..UiState::default()
+        };
+
+        snapshot("tables_log_hidden", &tabs_state, &ui_state);
+    }
+
+    #[test]
+    fn renders_help_overlay() {
+        let tabs_state = tabs_state(vec![tables_state(&[("orders", 5000)], Some(0))]);
+        let ui_state = UiState {
+            show_help: true,
+            ..UiState::default()
+        };
+
+        snapshot("help", &tabs_state, &ui_state);
+    }
+
+    #[test]
+    fn renders_loading_view() {
+        let tabs_state = tabs_state(vec![TanicAppState::RetrievingTableList(NamespaceDeets {
+            parts: vec!["sales".to_string()],
+            name: "sales".to_string(),
+            table_count: 8,
+            stale: false,
+        })]);
+
+        snapshot("loading", &tabs_state, &UiState::default());
+    }
+
+    #[test]
+    fn renders_error_view() {
+        let tabs_state = tabs_state(vec![TanicAppState::ShowingError(
+            "Request timed out after 30s".to_string(),
+        )]);
+
+        snapshot("error", &tabs_state, &UiState::default());
     }
 }
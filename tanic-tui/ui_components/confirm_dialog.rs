@@ -0,0 +1,178 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Clear, Padding, Paragraph, Wrap};
+
+use tanic_svc::maintenance::MaintenancePlan;
+
+use crate::keybindings::{Command, FormCommand, KeyBinding};
+use crate::theme::Theme;
+
+/// Confirmation of a maintenance plan, by typing the name of its table
+#[derive(Clone, Debug)]
+pub(crate) struct ConfirmForm {
+    table_name: String,
+
+    /// what applying the plan does, in a sentence
+    summary: String,
+
+    value: String,
+
+    /// why the form could not be submitted
+    error: Option<String>,
+}
+
+impl ConfirmForm {
+    pub(crate) fn new(plan: &MaintenancePlan) -> Self {
+        let removals = match plan.removals.len() {
+            1 => "1 item".to_string(),
+            count => format!("{count} items"),
+        };
+
+        Self {
+            table_name: plan.request.table.name.clone(),
+            summary: format!(
+                "This will {}, removing the {removals} listed from {}. It can't be undone.",
+                plan.request.operation,
+                plan.request.table.label()
+            ),
+            value: String::new(),
+            error: None,
+        }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Enter,
+            key_label: "Enter",
+            description: "Apply",
+            command: Command::ConfirmForm(FormCommand::Submit),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Esc,
+            key_label: "Esc",
+            description: "Cancel",
+            command: Command::ConfirmForm(FormCommand::Close),
+            in_footer: true,
+        },
+    ];
+
+    pub(crate) fn edit(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.value.push(c)
+            }
+            KeyCode::Backspace => {
+                self.value.pop();
+            }
+            _ => return,
+        }
+        self.error = None;
+    }
+
+    /// The typed confirmation, if it is the table's name
+    pub(crate) fn submit(&mut self) -> Option<String> {
+        if self.value == self.table_name {
+            return Some(self.value.clone());
+        }
+
+        self.error = Some(format!("type {} to confirm", self.table_name));
+        None
+    }
+}
+
+/// Popup for confirming a maintenance plan
+pub(crate) struct ConfirmDialog<'a> {
+    form: &'a ConfirmForm,
+    theme: &'a Theme,
+}
+
+impl<'a> ConfirmDialog<'a> {
+    pub(crate) fn new(form: &'a ConfirmForm, theme: &'a Theme) -> Self {
+        Self { form, theme }
+    }
+}
+
+impl Widget for &ConfirmDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![
+            Line::styled(self.form.summary.as_str(), self.theme.diff),
+            Line::default(),
+            Line::styled(
+                format!(
+                    "Type the table's name, {}, to confirm:",
+                    self.form.table_name
+                ),
+                self.theme.base,
+            ),
+            Line::from(vec![
+                Span::styled("> ", self.theme.key_hint),
+                Span::styled(format!("{}_", self.form.value), self.theme.title),
+            ]),
+        ];
+        if let Some(error) = &self.form.error {
+            lines.push(Line::default());
+            lines.push(Line::styled(error.as_str(), self.theme.error));
+        }
+
+        // borders and padding, and room for the summary to wrap
+        let width = 64.min(area.width);
+        let height = (lines.len() as u16 + 4).min(area.height);
+
+        let [popup] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        let block = Block::bordered()
+            .title(Line::styled(" Confirm ", self.theme.title).centered())
+            .border_set(border::THICK)
+            .border_style(self.theme.base)
+            .padding(Padding::horizontal(1));
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme, type_text};
+    use tanic_svc::maintenance::{MaintenanceOperation, MaintenanceRequest, Removal};
+
+    #[test]
+    fn only_submits_the_table_name() {
+        let plan = MaintenancePlan {
+            request: MaintenanceRequest {
+                table: orders_table(),
+                operation: MaintenanceOperation::RemoveOldMetadata { retain_last: 10 },
+            },
+            snapshot_id: Some(1),
+            table_uuid: "00000000-0000-0000-0000-000000000000".to_string(),
+            removals: vec![
+                Removal::File {
+                    path: "s3://warehouse/sales/orders/metadata/00001.metadata.json".to_string(),
+                    size_bytes: None,
+                };
+                3
+            ],
+            kept: 10,
+        };
+        let mut form = ConfirmForm::new(&plan);
+        type_text(&mut form, "order");
+
+        assert_eq!(form.submit(), None);
+        insta::assert_snapshot!(render(&ConfirmDialog::new(&form, &theme()), (80, 14)));
+
+        form.edit(KeyEvent::from(KeyCode::Char('s')));
+        assert_eq!(form.submit().as_deref(), Some("orders"));
+    }
+}
@@ -0,0 +1,281 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+
+use tanic_svc::deletes::{DeleteHealth, FileTotals, PartitionDeletes};
+use tanic_svc::state::ViewingDeleteHealthState;
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::{format_bytes, Theme};
+use crate::ui_components::empty_state::EmptyState;
+
+/// A table's delete files, per partition, and the partitions to compact
+pub(crate) struct DeleteHealthView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> DeleteHealthView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Previous partition",
+            command: Command::Action(TanicAction::FocusPrevDeletePartition),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Next partition",
+            command: Command::Action(TanicAction::FocusNextDeletePartition),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: false,
+        },
+    ];
+
+    fn block(&self, health: &DeleteHealth) -> Block<'static> {
+        let snapshot = match health.snapshot_id {
+            Some(id) => format!(" snapshot {id} "),
+            None => " no snapshots ".to_string(),
+        };
+
+        let to_compact = health.needing_compaction().count();
+        let (compaction, compaction_style) = if to_compact == 0 {
+            (
+                " no partitions need compaction ".to_string(),
+                self.theme.base,
+            )
+        } else {
+            (
+                format!(
+                    " {to_compact} of {} partitions need compaction ",
+                    health.partitions.len()
+                ),
+                self.theme.diff,
+            )
+        };
+
+        Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} ", health.table.label()),
+                self.theme.title,
+            ))
+            .title(Line::styled(snapshot, self.theme.base).right_aligned())
+            .title_bottom(Line::styled(" Delete files ", self.theme.title))
+            .title_bottom(Line::styled(compaction, compaction_style).right_aligned())
+            .border_style(self.theme.base)
+    }
+
+    /// A line for each kind of file in the snapshot
+    fn summary_lines(&self, health: &DeleteHealth) -> Vec<Line<'static>> {
+        [
+            ("Data files", health.data),
+            ("Position deletes", health.position_deletes),
+            ("Equality deletes", health.equality_deletes),
+        ]
+        .into_iter()
+        .map(|(label, totals)| {
+            Line::from(vec![
+                Span::styled(format!("{label:<18}"), self.theme.title),
+                Span::styled(
+                    format!(
+                        "{} · {} records · {}",
+                        totals.files,
+                        totals.records,
+                        format_bytes(totals.size_bytes)
+                    ),
+                    self.theme.base,
+                ),
+            ])
+        })
+        .collect()
+    }
+
+    fn row(&self, partition: &PartitionDeletes) -> Row<'static> {
+        let deletes = |totals: FileTotals| {
+            if totals.files == 0 {
+                "–".to_string()
+            } else {
+                format!("{} · {}", totals.files, format_bytes(totals.size_bytes))
+            }
+        };
+        let ratio = partition
+            .delete_ratio()
+            .map(|ratio| format!("{:.1}%", ratio * 100.0))
+            .unwrap_or_else(|| "no data".to_string());
+        let flag = if partition.needs_compaction() {
+            Span::styled("compact", self.theme.diff)
+        } else {
+            Span::raw("")
+        };
+        let partition_label = if partition.partition.is_empty() {
+            "unpartitioned".to_string()
+        } else {
+            partition.partition.clone()
+        };
+
+        Row::new(vec![
+            Cell::from(Span::styled(partition_label, self.theme.title)),
+            Cell::from(Span::styled(
+                format!("{} · {}", partition.data.files, partition.data.records),
+                self.theme.base,
+            )),
+            Cell::from(Span::styled(
+                deletes(partition.position_deletes),
+                self.theme.base,
+            )),
+            Cell::from(Span::styled(
+                deletes(partition.equality_deletes),
+                self.theme.base,
+            )),
+            Cell::from(Span::styled(
+                (partition.position_deletes.records + partition.equality_deletes.records)
+                    .to_string(),
+                self.theme.base,
+            )),
+            Cell::from(Span::styled(ratio, self.theme.base)),
+            Cell::from(flag),
+        ])
+    }
+}
+
+impl Widget for &DeleteHealthView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingDeleteHealth(ViewingDeleteHealthState {
+            health,
+            selected_idx,
+        }) = self.state
+        else {
+            return;
+        };
+        let block = self.block(health);
+
+        if health.snapshot_id.is_none() {
+            EmptyState::new(block, "This table has no snapshots", self.theme).render(area, buf);
+            return;
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let summary = self.summary_lines(health);
+        let [summary_area, partitions_area] = Layout::vertical([
+            Constraint::Length(summary.len() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+        Paragraph::new(summary).render(summary_area, buf);
+
+        if health.partitions.is_empty() {
+            Paragraph::new(Line::styled(
+                "No delete files in this snapshot",
+                self.theme.base,
+            ))
+            .centered()
+            .render(partitions_area, buf);
+            return;
+        }
+
+        let header = Row::new(
+            [
+                "Partition",
+                "Data files · records",
+                "Position deletes",
+                "Equality deletes",
+                "Deleted",
+                "Ratio",
+                "",
+            ]
+            .map(|title| Cell::from(Span::styled(title, self.theme.title))),
+        );
+        let table = Table::new(
+            health
+                .partitions
+                .iter()
+                .map(|partition| self.row(partition)),
+            [
+                Constraint::Fill(2),
+                Constraint::Length(20),
+                Constraint::Length(16),
+                Constraint::Length(16),
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(7),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(self.theme.tile_selected);
+
+        let mut table_state = TableState::default().with_selected(Some(*selected_idx));
+        StatefulWidget::render(table, partitions_area, buf, &mut table_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{conn_details, render, theme};
+    use tanic_svc::comparison::TableRef;
+
+    fn totals(files: usize, records: u64) -> FileTotals {
+        FileTotals {
+            files,
+            records,
+            size_bytes: records * 100,
+        }
+    }
+
+    #[test]
+    fn renders_deletes_per_partition() {
+        let partition = |day: &str, data, position, equality| PartitionDeletes {
+            partition: format!("day={day}"),
+            data,
+            position_deletes: position,
+            equality_deletes: equality,
+        };
+        let state = TanicAppState::ViewingDeleteHealth(ViewingDeleteHealthState {
+            health: DeleteHealth {
+                table: TableRef {
+                    connection: conn_details("prod"),
+                    namespace: vec!["cdc".to_string()],
+                    name: "orders".to_string(),
+                },
+                snapshot_id: Some(42),
+                data: totals(30, 30_000),
+                position_deletes: totals(4, 2_500),
+                equality_deletes: totals(1, 100),
+                partitions: vec![
+                    partition(
+                        "2024-01-02",
+                        totals(10, 10_000),
+                        totals(3, 2_000),
+                        totals(1, 100),
+                    ),
+                    partition(
+                        "2024-01-01",
+                        totals(10, 10_000),
+                        totals(1, 500),
+                        totals(0, 0),
+                    ),
+                ],
+            },
+            selected_idx: 0,
+        });
+        let theme = theme();
+
+        let view = DeleteHealthView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (110, 10)));
+    }
+}
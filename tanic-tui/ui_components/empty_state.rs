@@ -0,0 +1,35 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+
+use crate::theme::Theme;
+
+/// A view's frame with a message in place of its contents, for when there
+/// is nothing to show
+pub(crate) struct EmptyState<'a> {
+    block: Block<'a>,
+    message: &'a str,
+    theme: &'a Theme,
+}
+
+impl<'a> EmptyState<'a> {
+    pub(crate) fn new(block: Block<'a>, message: &'a str, theme: &'a Theme) -> Self {
+        Self {
+            block,
+            message,
+            theme,
+        }
+    }
+}
+
+impl Widget for EmptyState<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(Text::from(vec![
+            Line::default(),
+            Line::styled(self.message, self.theme.base),
+        ]))
+        .centered()
+        .wrap(Wrap { trim: true })
+        .block(self.block)
+        .render(area, buf);
+    }
+}
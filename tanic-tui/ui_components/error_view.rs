@@ -0,0 +1,39 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+
+use tanic_svc::TanicAppState;
+
+use crate::theme::Theme;
+
+/// Shown when a request that a view was waiting on failed
+pub(crate) struct ErrorView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> ErrorView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+}
+
+impl Widget for &ErrorView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ShowingError(message) = self.state else {
+            return;
+        };
+
+        let block = Block::bordered()
+            .title(Line::styled(" Tanic //// Error ", self.theme.error))
+            .border_style(self.theme.base);
+
+        Paragraph::new(Text::from(vec![
+            Line::default(),
+            Line::styled(message.as_str(), self.theme.error),
+        ]))
+        .centered()
+        .wrap(Wrap { trim: true })
+        .block(block)
+        .render(area, buf);
+    }
+}
@@ -0,0 +1,247 @@
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use std::path::PathBuf;
+
+use tanic_svc::comparison::TableRef;
+use tanic_svc::export::{ExportFormat, ExportOptions, ExportRequest, SnapshotSelector};
+use tanic_svc::filter::RowFilter;
+use tanic_svc::preview::PreviewRequest;
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::text_form::{self, text_form_key_bindings, Field, TextFields};
+
+/// Label and hint of each of the form's fields
+const FIELDS: &[Field] = &[
+    ("File", ".csv, .ndjson or .parquet"),
+    ("Columns", "comma separated, or blank for all"),
+    ("Filter", "such as country = 'GB', or blank for all rows"),
+    ("Limit", "rows, or blank for all"),
+    ("Snapshot", "ID, branch, tag or time, or blank for current"),
+];
+
+const FILE: usize = 0;
+const COLUMNS: usize = 1;
+const FILTER: usize = 2;
+const LIMIT: usize = 3;
+const SNAPSHOT: usize = 4;
+
+/// The options of an export that is being set up
+#[derive(Clone, Debug)]
+pub(crate) struct ExportForm {
+    table: TableRef,
+    fields: TextFields,
+}
+
+impl ExportForm {
+    pub(crate) fn new(table: TableRef) -> Self {
+        let mut fields = TextFields::new(FIELDS);
+        fields.set_value(FILE, format!("{}.csv", table.name));
+
+        Self { table, fields }
+    }
+
+    /// An export of what a table preview shows: its columns and the rows
+    /// matching its filter, as of the same point in time
+    pub(crate) fn for_preview(request: &PreviewRequest) -> Self {
+        let mut form = Self::new(request.table.clone());
+        form.fields.set_value(COLUMNS, request.columns.join(", "));
+        if let Some(filter) = &request.filter {
+            form.fields.set_value(FILTER, filter.to_string());
+        }
+        if let Some(as_of) = &request.as_of {
+            form.fields.set_value(SNAPSHOT, as_of.to_string());
+        }
+
+        form
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] =
+        text_form_key_bindings!(Command::ExportForm, "Export");
+
+    pub(crate) fn next_field(&mut self) {
+        self.fields.next_field();
+    }
+
+    pub(crate) fn prev_field(&mut self) {
+        self.fields.prev_field();
+    }
+
+    /// Type into the focused field
+    pub(crate) fn edit(&mut self, key_event: KeyEvent) {
+        self.fields.edit(key_event);
+    }
+
+    /// The export that the form describes. If the form isn't valid, the
+    /// reason is shown in the form.
+    pub(crate) fn submit(&mut self) -> Option<ExportRequest> {
+        let request = self.request();
+        self.fields.submit(request)
+    }
+
+    fn request(&self) -> Result<ExportRequest, String> {
+        let file = self.fields.value(FILE).trim();
+        if file.is_empty() {
+            return Err("enter a file to export to".to_string());
+        }
+        let path = PathBuf::from(file);
+        let format = ExportFormat::from_path(&path)
+            .ok_or("the file should end with .csv, .ndjson or .parquet")?;
+
+        let columns = self
+            .fields
+            .value(COLUMNS)
+            .split(',')
+            .map(str::trim)
+            .filter(|column| !column.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let filter = match self.fields.value(FILTER).trim() {
+            "" => None,
+            filter => Some(filter.parse::<RowFilter>()?),
+        };
+
+        let limit = match self.fields.value(LIMIT).trim() {
+            "" => None,
+            limit => Some(
+                limit
+                    .parse()
+                    .map_err(|_| format!("'{limit}' is not a number of rows"))?,
+            ),
+        };
+
+        let snapshot = match self.fields.value(SNAPSHOT).trim() {
+            "" => None,
+            snapshot => Some(snapshot.parse::<SnapshotSelector>()?),
+        };
+
+        Ok(ExportRequest {
+            table: self.table.clone(),
+            path,
+            options: ExportOptions {
+                format,
+                columns,
+                filter,
+                limit,
+                snapshot,
+            },
+        })
+    }
+}
+
+/// Popup for setting up an export
+pub(crate) struct ExportDialog<'a> {
+    form: &'a ExportForm,
+    theme: &'a Theme,
+}
+
+impl<'a> ExportDialog<'a> {
+    pub(crate) fn new(form: &'a ExportForm, theme: &'a Theme) -> Self {
+        Self { form, theme }
+    }
+}
+
+impl Widget for &ExportDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = self.form.fields.lines(self.theme);
+        lines.extend(self.form.fields.error_lines(self.theme));
+
+        let title = format!(" Export {} ", self.form.table.label());
+        text_form::render_popup(title, lines, 64, self.theme, area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme, type_text};
+    use crossterm::event::KeyCode;
+
+    fn form() -> ExportForm {
+        ExportForm::new(orders_table())
+    }
+
+    #[test]
+    fn defaults_to_a_csv_of_the_whole_table() {
+        let request = form().submit().unwrap();
+
+        assert_eq!(request.path, PathBuf::from("orders.csv"));
+        assert_eq!(
+            request.options,
+            ExportOptions {
+                format: ExportFormat::Csv,
+                columns: vec![],
+                filter: None,
+                limit: None,
+                snapshot: None,
+            }
+        );
+    }
+
+    #[test]
+    fn builds_a_request_from_the_fields() {
+        let mut form = form();
+        for _ in 0.."orders.csv".len() {
+            form.edit(KeyEvent::from(KeyCode::Backspace));
+        }
+        type_text(&mut form, "sample.parquet");
+        form.next_field();
+        type_text(&mut form, "id, total ,");
+        form.next_field();
+        type_text(&mut form, "total > 100");
+        form.next_field();
+        type_text(&mut form, "500");
+        form.next_field();
+        type_text(&mut form, "audit");
+
+        let request = form.submit().unwrap();
+
+        assert_eq!(request.path, PathBuf::from("sample.parquet"));
+        assert_eq!(
+            request.options,
+            ExportOptions {
+                format: ExportFormat::Parquet,
+                columns: vec!["id".to_string(), "total".to_string()],
+                filter: Some("total > 100".parse().unwrap()),
+                limit: Some(500),
+                snapshot: Some(SnapshotSelector::Ref("audit".to_string())),
+            }
+        );
+    }
+
+    #[test]
+    fn starts_from_what_a_preview_shows() {
+        let preview = PreviewRequest {
+            table: form().table,
+            as_of: Some(SnapshotSelector::AsOf(1_735_732_800_000)),
+            columns: vec!["id".to_string(), "total".to_string()],
+            filter: Some("country = 'GB'".parse().unwrap()),
+        };
+
+        let request = ExportForm::for_preview(&preview).submit().unwrap();
+
+        assert_eq!(
+            request.options,
+            ExportOptions {
+                format: ExportFormat::Csv,
+                columns: preview.columns,
+                filter: preview.filter,
+                limit: None,
+                snapshot: preview.as_of,
+            }
+        );
+    }
+
+    #[test]
+    fn shows_why_a_form_is_invalid() {
+        let mut form = form();
+        form.prev_field();
+        type_text(&mut form, "main");
+        form.prev_field();
+        type_text(&mut form, "lots");
+
+        assert_eq!(form.submit(), None);
+        insta::assert_snapshot!(render(&ExportDialog::new(&form, &theme()), (80, 24)));
+    }
+}
@@ -0,0 +1,148 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, LineGauge, Paragraph};
+
+use tanic_svc::export::{ExportJob, ExportStatus};
+
+use crate::theme::Theme;
+
+/// Most exports shown at once. Older exports scroll out of view.
+pub(crate) const MAX_VISIBLE_EXPORTS: usize = 3;
+
+/// Width of each export's progress bar
+const GAUGE_WIDTH: u16 = 24;
+
+/// Progress of each export, newest last
+pub(crate) struct ExportProgress<'a> {
+    exports: &'a [ExportJob],
+    theme: &'a Theme,
+}
+
+impl<'a> ExportProgress<'a> {
+    pub(crate) fn new(exports: &'a [ExportJob], theme: &'a Theme) -> Self {
+        Self { exports, theme }
+    }
+
+    /// Height needed to show the exports, including borders
+    pub(crate) fn height(exports: &[ExportJob]) -> u16 {
+        match exports.len() {
+            0 => 0,
+            count => count.min(MAX_VISIBLE_EXPORTS) as u16 + 2,
+        }
+    }
+
+    fn render_export(&self, export: &ExportJob, area: Rect, buf: &mut Buffer) {
+        let [description, progress] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(GAUGE_WIDTH)])
+                .spacing(1)
+                .areas(area);
+
+        let mut spans = vec![Span::styled(
+            format!(
+                "{} {} → {}",
+                self.theme.icons.table,
+                export.request.table.name,
+                export.request.path.display()
+            ),
+            self.theme.base,
+        )];
+        if let ExportStatus::Failed(message) = &export.status {
+            spans.push(Span::styled(format!(": {message}"), self.theme.error));
+        }
+        Line::from(spans).render(description, buf);
+
+        let rows = match export.rows_expected {
+            Some(expected) if export.status == ExportStatus::Running => {
+                format!("{}/{expected} rows", export.rows_written)
+            }
+            _ => format!("{} rows", export.rows_written),
+        };
+
+        match (&export.status, export.ratio()) {
+            (ExportStatus::Running, Some(ratio)) => LineGauge::default()
+                .ratio(ratio)
+                .label(Span::styled(
+                    format!("{:>3.0}%", ratio * 100.0),
+                    self.theme.base,
+                ))
+                .filled_style(self.theme.title)
+                .unfilled_style(self.theme.log)
+                .render(progress, buf),
+            (ExportStatus::Running, None) => {
+                Paragraph::new(Line::styled(rows, self.theme.base).right_aligned())
+                    .render(progress, buf)
+            }
+            (ExportStatus::Finished, _) => Paragraph::new(
+                Line::styled(format!("done, {rows}"), self.theme.title).right_aligned(),
+            )
+            .render(progress, buf),
+            (ExportStatus::Failed(_), _) => {
+                Paragraph::new(Line::styled("failed", self.theme.error).right_aligned())
+                    .render(progress, buf)
+            }
+        }
+    }
+}
+
+impl Widget for &ExportProgress<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(Line::styled(" Exports ", self.theme.title))
+            .title_bottom(Line::styled(" X to dismiss ", self.theme.base).right_aligned())
+            .border_style(self.theme.base);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let visible = &self.exports[self.exports.len().saturating_sub(MAX_VISIBLE_EXPORTS)..];
+        let rows = Layout::vertical(vec![Constraint::Length(1); visible.len()]).split(inner);
+
+        for (export, row) in visible.iter().zip(rows.iter()) {
+            self.render_export(export, *row, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{conn_details, render, theme};
+    use tanic_svc::comparison::TableRef;
+    use tanic_svc::export::{ExportFormat, ExportOptions, ExportRequest};
+    use tanic_svc::requests::RequestId;
+
+    fn export(name: &str, rows_written: u64, status: ExportStatus) -> ExportJob {
+        ExportJob {
+            id: RequestId::new(0),
+            request: ExportRequest {
+                table: TableRef {
+                    connection: conn_details("prod"),
+                    namespace: vec!["sales".to_string()],
+                    name: name.to_string(),
+                },
+                path: format!("{name}.csv").into(),
+                options: ExportOptions {
+                    format: ExportFormat::Csv,
+                    columns: vec![],
+                    filter: None,
+                    limit: None,
+                    snapshot: None,
+                },
+            },
+            rows_written,
+            rows_expected: Some(1000),
+            status,
+        }
+    }
+
+    #[test]
+    fn renders_export_progress() {
+        let exports = vec![
+            export("orders", 1000, ExportStatus::Finished),
+            export("customers", 250, ExportStatus::Running),
+            export("refunds", 0, ExportStatus::Failed("disk full".to_string())),
+        ];
+        let theme = theme();
+
+        let view = ExportProgress::new(&exports, &theme);
+        insta::assert_snapshot!(render(&view, (80, 5)));
+    }
+}
@@ -0,0 +1,188 @@
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+
+use tanic_svc::filter::RowFilter;
+use tanic_svc::preview::TablePreview;
+use tanic_svc::TanicAction;
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::text_form::{self, text_form_key_bindings, Field, TextFields};
+
+/// Label and hint of each of the form's fields
+const FIELDS: &[Field] = &[
+    ("Columns", "comma separated, or blank for all"),
+    (
+        "Filter",
+        "e.g. country = 'GB' AND ts > '2024-01-01', or blank",
+    ),
+];
+
+const COLUMNS: usize = 0;
+const FILTER: usize = 1;
+
+/// The columns and rows to narrow the table in view down to
+#[derive(Clone, Debug)]
+pub(crate) struct FilterForm {
+    /// the table, for the dialog's title
+    table_name: String,
+
+    /// the columns of the schema being read, to check chosen columns against
+    schema_columns: Vec<String>,
+
+    fields: TextFields,
+}
+
+impl FilterForm {
+    /// Start from the columns and filter that the preview was read with
+    pub(crate) fn new(preview: &TablePreview) -> Self {
+        let request = &preview.request;
+        let mut fields = TextFields::new(FIELDS);
+        fields.set_value(COLUMNS, request.columns.join(", "));
+        if let Some(filter) = &request.filter {
+            fields.set_value(FILTER, filter.to_string());
+        }
+        fields.focus(FILTER);
+
+        Self {
+            table_name: request.table.name.clone(),
+            schema_columns: preview
+                .schema
+                .iter()
+                .map(|field| field.name.clone())
+                .collect(),
+            fields,
+        }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] =
+        text_form_key_bindings!(Command::FilterForm, "Apply");
+
+    pub(crate) fn next_field(&mut self) {
+        self.fields.next_field();
+    }
+
+    pub(crate) fn prev_field(&mut self) {
+        self.fields.prev_field();
+    }
+
+    /// Type into the focused field
+    pub(crate) fn edit(&mut self, key_event: KeyEvent) {
+        self.fields.edit(key_event);
+    }
+
+    /// The action that applies the form. If the form isn't valid, the
+    /// reason is shown in the form.
+    pub(crate) fn submit(&mut self) -> Option<TanicAction> {
+        let action = self.action();
+        self.fields.submit(action)
+    }
+
+    fn action(&self) -> Result<TanicAction, String> {
+        let columns = self
+            .fields
+            .value(COLUMNS)
+            .split(',')
+            .map(str::trim)
+            .filter(|column| !column.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if let Some(unknown) = columns
+            .iter()
+            .find(|column| !self.schema_columns.contains(column))
+        {
+            return Err(format!("there is no column '{unknown}'"));
+        }
+
+        let filter = match self.fields.value(FILTER).trim() {
+            "" => None,
+            filter => Some(filter.parse::<RowFilter>()?),
+        };
+
+        Ok(TanicAction::FilterPreview { columns, filter })
+    }
+}
+
+/// Popup for narrowing down a table preview
+pub(crate) struct FilterDialog<'a> {
+    form: &'a FilterForm,
+    theme: &'a Theme,
+}
+
+impl<'a> FilterDialog<'a> {
+    pub(crate) fn new(form: &'a FilterForm, theme: &'a Theme) -> Self {
+        Self { form, theme }
+    }
+}
+
+impl Widget for &FilterDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = self.form.fields.lines(self.theme);
+        lines.extend(self.form.fields.error_lines(self.theme));
+
+        let title = format!(" Filter {} ", self.form.table_name);
+        text_form::render_popup(title, lines, 72, self.theme, area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme, type_text};
+    use tanic_svc::preview::PreviewRequest;
+    use tanic_svc::report::SchemaField;
+
+    fn form() -> FilterForm {
+        let field = |id, name: &str| SchemaField {
+            id,
+            name: name.to_string(),
+            field_type: "string".to_string(),
+            required: false,
+            doc: None,
+        };
+
+        FilterForm::new(&TablePreview {
+            request: PreviewRequest::current(orders_table()),
+            snapshot: None,
+            schema_id: 0,
+            schema: vec![field(1, "id"), field(2, "country")],
+            columns: vec![],
+            rows: vec![],
+            rows_error: None,
+            files: None,
+        })
+    }
+
+    #[test]
+    fn applies_columns_and_a_filter() {
+        let mut form = form();
+        type_text(&mut form, "country = 'GB'");
+        form.prev_field();
+        type_text(&mut form, "country, id");
+
+        let Some(TanicAction::FilterPreview { columns, filter }) = form.submit() else {
+            panic!("expected the form to submit");
+        };
+        assert_eq!(columns, ["country", "id"]);
+        assert_eq!(filter, Some("country = 'GB'".parse().unwrap()));
+    }
+
+    #[test]
+    fn shows_why_a_filter_is_invalid() {
+        let mut form = form();
+        type_text(&mut form, "country = ");
+
+        assert!(form.submit().is_none());
+        insta::assert_snapshot!(render(&FilterDialog::new(&form, &theme()), (80, 14)));
+
+        form.prev_field();
+        type_text(&mut form, "region");
+        form.next_field();
+        type_text(&mut form, "'GB'");
+        assert!(form.submit().is_none());
+        assert_eq!(
+            form.fields.error.as_deref(),
+            Some("there is no column 'region'")
+        );
+    }
+}
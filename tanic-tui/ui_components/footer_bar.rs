@@ -0,0 +1,37 @@
+use ratatui::prelude::*;
+
+use crate::keybindings::KeyBinding;
+use crate::theme::Theme;
+
+/// One-line bar showing the most relevant key bindings for the current view
+pub(crate) struct FooterBar<'a> {
+    key_bindings: &'a [&'a KeyBinding],
+    theme: &'a Theme,
+}
+
+impl<'a> FooterBar<'a> {
+    pub(crate) fn new(key_bindings: &'a [&'a KeyBinding], theme: &'a Theme) -> Self {
+        Self {
+            key_bindings,
+            theme,
+        }
+    }
+}
+
+impl Widget for &FooterBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let spans = self
+            .key_bindings
+            .iter()
+            .filter(|binding| binding.in_footer)
+            .flat_map(|binding| {
+                [
+                    Span::styled(format!(" {} ", binding.description), self.theme.base),
+                    Span::styled(format!("<{}> ", binding.key_label), self.theme.key_hint),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        Line::from(spans).render(area, buf);
+    }
+}
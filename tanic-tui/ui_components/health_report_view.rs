@@ -0,0 +1,249 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+
+use tanic_core::message::Severity;
+use tanic_svc::health::HealthReport;
+use tanic_svc::state::ViewingHealthReportState;
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::empty_state::EmptyState;
+
+/// The findings of a health pass over a table or namespace, least healthy
+/// table first
+pub(crate) struct HealthReportView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> HealthReportView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Previous finding",
+            command: Command::Action(TanicAction::FocusPrevHealthFinding),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Next finding",
+            command: Command::Action(TanicAction::FocusNextHealthFinding),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: false,
+        },
+    ];
+
+    fn block(&self, report: &HealthReport) -> Block<'static> {
+        let findings = report.findings().count();
+        let checked = match report.tables.len() {
+            1 => " 1 table checked ".to_string(),
+            count => format!(" {count} tables checked "),
+        };
+        let (found, found_style) = if findings == 0 {
+            (" no findings ".to_string(), self.theme.base)
+        } else {
+            (
+                format!(
+                    " {findings} findings · {} of {} tables healthy ",
+                    report.healthy().count(),
+                    report.tables.len()
+                ),
+                self.theme.diff,
+            )
+        };
+
+        Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} ", report.target.label()),
+                self.theme.title,
+            ))
+            .title(Line::styled(checked, self.theme.base).right_aligned())
+            .title_bottom(Line::styled(" Health ", self.theme.title))
+            .title_bottom(Line::styled(found, found_style).right_aligned())
+            .border_style(self.theme.base)
+    }
+
+    /// A row for each finding, naming its table and score on the table's
+    /// first finding only
+    fn rows(&self, report: &HealthReport) -> Vec<Row<'static>> {
+        let mut rows = vec![];
+
+        for table in report.tables.iter() {
+            for (idx, finding) in table.findings.iter().enumerate() {
+                let (name, score) = if idx == 0 {
+                    (table.name.clone(), table.score().to_string())
+                } else {
+                    (String::new(), String::new())
+                };
+                let severity = match finding.severity {
+                    Severity::Warning => Span::styled("warning", self.theme.diff),
+                    Severity::Info => Span::styled("info", self.theme.base),
+                };
+
+                rows.push(Row::new(vec![
+                    Cell::from(Span::styled(name, self.theme.title)),
+                    Cell::from(Span::styled(score, self.theme.base)),
+                    Cell::from(severity),
+                    Cell::from(Span::styled(finding.check.label(), self.theme.base)),
+                    Cell::from(Span::styled(finding.message.clone(), self.theme.base)),
+                ]));
+            }
+        }
+
+        rows
+    }
+
+    /// The tables that could not be checked, and why
+    fn failure_lines(&self, report: &HealthReport) -> Vec<Line<'static>> {
+        report
+            .failures
+            .iter()
+            .map(|failure| {
+                Line::styled(
+                    format!("{} could not be checked: {}", failure.table, failure.error),
+                    self.theme.error,
+                )
+            })
+            .collect()
+    }
+}
+
+impl Widget for &HealthReportView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingHealthReport(ViewingHealthReportState {
+            report,
+            selected_idx,
+        }) = self.state
+        else {
+            return;
+        };
+        let block = self.block(report);
+
+        if report.tables.is_empty() && report.failures.is_empty() {
+            EmptyState::new(block, "No tables to check", self.theme).render(area, buf);
+            return;
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let failures = self.failure_lines(report);
+        let [findings_area, failures_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(failures.len() as u16),
+        ])
+        .areas(inner);
+        Paragraph::new(failures).render(failures_area, buf);
+
+        let rows = self.rows(report);
+        if rows.is_empty() {
+            Paragraph::new(Line::styled(
+                "No findings: every table checked is healthy",
+                self.theme.base,
+            ))
+            .centered()
+            .render(findings_area, buf);
+            return;
+        }
+
+        let header = Row::new(
+            ["Table", "Score", "Severity", "Check", "Finding"]
+                .map(|title| Cell::from(Span::styled(title, self.theme.title))),
+        );
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Length(16),
+                Constraint::Fill(3),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(self.theme.tile_selected);
+
+        let mut table_state = TableState::default().with_selected(Some(*selected_idx));
+        StatefulWidget::render(table, findings_area, buf, &mut table_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{conn_details, render, theme};
+    use tanic_core::message::{HealthCheck, HealthFinding};
+    use tanic_svc::health::TableHealth;
+    use tanic_svc::report::{ReportTarget, TableFailure};
+
+    #[test]
+    fn renders_findings_least_healthy_table_first() {
+        let finding = |check, severity, message: &str| HealthFinding {
+            check,
+            severity,
+            message: message.to_string(),
+        };
+        let table = |name: &str, findings| TableHealth {
+            name: name.to_string(),
+            findings,
+            exact_file_sizes: true,
+        };
+        let state = TanicAppState::ViewingHealthReport(ViewingHealthReportState {
+            report: HealthReport::new(
+                ReportTarget::Namespace {
+                    connection: conn_details("prod"),
+                    namespace: vec!["sales".to_string()],
+                },
+                vec![
+                    table("customers", vec![]),
+                    table(
+                        "refunds",
+                        vec![finding(
+                            HealthCheck::SortOrder,
+                            Severity::Info,
+                            "no sort order declared",
+                        )],
+                    ),
+                    table(
+                        "orders",
+                        vec![
+                            finding(
+                                HealthCheck::SmallFiles,
+                                Severity::Warning,
+                                "240 data files smaller than 32 MiB, more than 100",
+                            ),
+                            finding(
+                                HealthCheck::StaleCommit,
+                                Severity::Info,
+                                "last commit 45 days ago, more than 30",
+                            ),
+                        ],
+                    ),
+                ],
+                vec![TableFailure {
+                    table: "returns".to_string(),
+                    error: "table does not exist".to_string(),
+                }],
+            ),
+            selected_idx: 1,
+        });
+        let theme = theme();
+
+        let view = HealthReportView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (110, 9)));
+    }
+}
@@ -0,0 +1,77 @@
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Cell, Clear, Padding, Row, Table};
+
+use crate::keybindings::KeyBinding;
+use crate::theme::Theme;
+
+pub(crate) struct HelpOverlay<'a> {
+    key_bindings: &'a [&'a KeyBinding],
+    theme: &'a Theme,
+}
+
+impl<'a> HelpOverlay<'a> {
+    pub(crate) fn new(key_bindings: &'a [&'a KeyBinding], theme: &'a Theme) -> Self {
+        Self {
+            key_bindings,
+            theme,
+        }
+    }
+}
+
+impl Widget for &HelpOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let key_width = self
+            .key_bindings
+            .iter()
+            .map(|binding| Line::from(binding.key_label).width())
+            .max()
+            .unwrap_or_default() as u16;
+        let description_width = self
+            .key_bindings
+            .iter()
+            .map(|binding| Line::from(binding.description).width())
+            .max()
+            .unwrap_or_default() as u16;
+
+        // borders, padding and column spacing
+        let width = (key_width + description_width + 7).min(area.width);
+        let height = (self.key_bindings.len() as u16 + 2).min(area.height);
+
+        let [popup] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        let rows = self.key_bindings.iter().map(|binding| {
+            Row::new(vec![
+                Cell::from(Span::styled(binding.key_label, self.theme.key_hint)),
+                Cell::from(Span::styled(binding.description, self.theme.base)),
+            ])
+        });
+
+        let block = Block::bordered()
+            .title(Line::styled(" Keys ", self.theme.title).centered())
+            .border_set(border::THICK)
+            .border_style(self.theme.base)
+            .padding(Padding::horizontal(1));
+
+        Clear.render(popup, buf);
+        Widget::render(
+            Table::new(
+                rows,
+                [
+                    Constraint::Length(key_width),
+                    Constraint::Length(description_width),
+                ],
+            )
+            .column_spacing(2)
+            .block(block),
+            popup,
+            buf,
+        );
+    }
+}
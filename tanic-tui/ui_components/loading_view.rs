@@ -0,0 +1,127 @@
+use crossterm::event::KeyCode;
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph};
+
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+
+/// Shown while a view is waiting on the catalog
+pub(crate) struct LoadingView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+
+    /// spinner animation frame
+    tick: usize,
+
+    /// whether the app exits once the view's request is done
+    exit_pending: bool,
+}
+
+impl<'a> LoadingView<'a> {
+    pub(crate) fn new(
+        state: &'a TanicAppState,
+        theme: &'a Theme,
+        tick: usize,
+        exit_pending: bool,
+    ) -> Self {
+        Self {
+            state,
+            theme,
+            tick,
+            exit_pending,
+        }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[KeyBinding {
+        key: KeyCode::Esc,
+        key_label: "Esc",
+        description: "Cancel",
+        command: Command::Action(TanicAction::CancelRequest),
+        in_footer: true,
+    }];
+
+    fn progress_text(&self) -> String {
+        match self.state {
+            TanicAppState::ConnectingTo(conn_details) => {
+                format!("Connecting to {} ({})", conn_details.name, conn_details.uri)
+            }
+            TanicAppState::RetrievingTableList(namespace) => {
+                format!("Retrieving tables in {}", namespace.name)
+            }
+            TanicAppState::RetrievingTableComparison(left, right) => {
+                format!("Comparing {} with {}", left.label(), right.label())
+            }
+            TanicAppState::RetrievingTablePreview(request, _) => match &request.as_of {
+                Some(as_of) => format!("Loading {} as of {as_of}", request.table.label()),
+                None => format!("Loading {}", request.table.label()),
+            },
+            TanicAppState::RetrievingScanPlan(request) => {
+                format!("Planning a scan of {}", request.table.label())
+            }
+            TanicAppState::RetrievingTableProfile(request) => match &request.histogram {
+                Some(column) => format!(
+                    "Sampling a histogram of {} in {}",
+                    column,
+                    request.table.label()
+                ),
+                None => format!("Profiling the columns of {}", request.table.label()),
+            },
+            TanicAppState::RetrievingDeleteHealth(table) => {
+                format!("Reading the delete files of {}", table.label())
+            }
+            TanicAppState::RetrievingHealthReport(target) => {
+                format!("Checking the health of {}", target.label())
+            }
+            TanicAppState::RetrievingMaintenancePlan(request) => {
+                format!(
+                    "Dry run: planning to {} of {}",
+                    request.operation,
+                    request.table.label()
+                )
+            }
+            TanicAppState::ApplyingMaintenance(plan) if self.exit_pending => {
+                format!(
+                    "Finishing maintenance before exiting: {} of {}",
+                    plan.request.operation,
+                    plan.request.table.label()
+                )
+            }
+            TanicAppState::ApplyingMaintenance(plan) => {
+                format!(
+                    "Applying: {} of {}",
+                    plan.request.operation,
+                    plan.request.table.label()
+                )
+            }
+            _ => "Loading".to_string(),
+        }
+    }
+}
+
+impl Widget for &LoadingView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let spinner = self.theme.icons.spinner;
+        let frame = spinner[self.tick % spinner.len()];
+
+        let block = Block::bordered()
+            .title(Line::styled(" Tanic ", self.theme.title))
+            .border_style(self.theme.base);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [line] = Layout::vertical([Constraint::Length(1)])
+            .flex(Flex::Center)
+            .areas(inner);
+
+        Paragraph::new(Line::from(vec![
+            Span::styled(frame, self.theme.key_hint),
+            Span::raw(" "),
+            Span::styled(format!("{}…", self.progress_text()), self.theme.base),
+        ]))
+        .centered()
+        .render(line, buf);
+    }
+}
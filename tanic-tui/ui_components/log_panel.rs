@@ -0,0 +1,120 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
+use tui_logger::{
+    TuiLoggerLevelOutput, TuiLoggerSmartWidget, TuiLoggerWidget, TuiWidgetEvent, TuiWidgetState,
+};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+
+const TIMESTAMP_FORMAT: &str = "%F %H:%M:%S%.3f";
+
+pub(crate) struct LogPanel<'a> {
+    widget_state: &'a TuiWidgetState,
+    theme: &'a Theme,
+    maximised: bool,
+}
+
+impl<'a> LogPanel<'a> {
+    pub(crate) fn new(widget_state: &'a TuiWidgetState, theme: &'a Theme, maximised: bool) -> Self {
+        Self {
+            widget_state,
+            theme,
+            maximised,
+        }
+    }
+
+    /// Bindings that are available while the log panel is maximised
+    pub(crate) const MAXIMISED_KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Previous target",
+            command: Command::LogPanel(TuiWidgetEvent::UpKey),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Next target",
+            command: Command::LogPanel(TuiWidgetEvent::DownKey),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Left,
+            key_label: "←",
+            description: "Show fewer levels for target",
+            command: Command::LogPanel(TuiWidgetEvent::LeftKey),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Right,
+            key_label: "→",
+            description: "Show more levels for target",
+            command: Command::LogPanel(TuiWidgetEvent::RightKey),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('-'),
+            key_label: "-",
+            description: "Capture fewer levels for target",
+            command: Command::LogPanel(TuiWidgetEvent::MinusKey),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('+'),
+            key_label: "+",
+            description: "Capture more levels for target",
+            command: Command::LogPanel(TuiWidgetEvent::PlusKey),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('f'),
+            key_label: "f",
+            description: "Only show selected target",
+            command: Command::LogPanel(TuiWidgetEvent::FocusKey),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Esc,
+            key_label: "Esc",
+            description: "Restore log",
+            command: Command::ToggleLogPanelMaximised,
+            in_footer: true,
+        },
+    ];
+}
+
+impl Widget for &LogPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.maximised {
+            TuiLoggerSmartWidget::default()
+                .title_target("Log Targets")
+                .title_log("Log")
+                .border_style(self.theme.base)
+                .highlight_style(self.theme.tile_selected)
+                .output_separator('|')
+                .output_timestamp(Some(TIMESTAMP_FORMAT.to_string()))
+                .output_level(Some(TuiLoggerLevelOutput::Long))
+                .output_target(true)
+                .output_file(false)
+                .output_line(false)
+                .style(self.theme.log)
+                .state(self.widget_state)
+                .render(area, buf);
+        } else {
+            TuiLoggerWidget::default()
+                .block(Block::bordered().title("Log").border_style(self.theme.base))
+                .output_separator('|')
+                .output_timestamp(Some(TIMESTAMP_FORMAT.to_string()))
+                .output_level(Some(TuiLoggerLevelOutput::Long))
+                .output_target(false)
+                .output_file(false)
+                .output_line(false)
+                .style(self.theme.log)
+                .state(self.widget_state)
+                .render(area, buf);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crossterm::event::KeyEvent;
+use ratatui::prelude::*;
+use std::str::FromStr;
+
+use tanic_svc::comparison::TableRef;
+use tanic_svc::maintenance::{MaintenanceOperation, MaintenanceRequest};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::text_form::{self, text_form_key_bindings, Field, TextFields};
+
+/// Most days ago that an age can go back, a century
+const MAX_DAYS: u64 = 36_500;
+
+/// Which maintenance operation a form sets up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MaintenanceKind {
+    ExpireSnapshots,
+    RemoveOldMetadata,
+    DeleteOrphanFiles,
+}
+
+impl MaintenanceKind {
+    /// Label and hint of each of the form's fields
+    fn fields(self) -> &'static [Field] {
+        match self {
+            MaintenanceKind::ExpireSnapshots => &[
+                ("Older than", "days, or blank for any age"),
+                ("Retain last", "snapshots, or blank for all"),
+            ],
+            MaintenanceKind::RemoveOldMetadata => &[("Retain last", "previous metadata files")],
+            MaintenanceKind::DeleteOrphanFiles => {
+                &[("Older than", "days, so that files being committed are kept")]
+            }
+        }
+    }
+
+    /// Default value of each of the form's fields
+    fn defaults(self) -> &'static [&'static str] {
+        match self {
+            MaintenanceKind::ExpireSnapshots => &["7", ""],
+            MaintenanceKind::RemoveOldMetadata => &["10"],
+            MaintenanceKind::DeleteOrphanFiles => &["3"],
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            MaintenanceKind::ExpireSnapshots => "Expire snapshots of",
+            MaintenanceKind::RemoveOldMetadata => "Remove old metadata of",
+            MaintenanceKind::DeleteOrphanFiles => "Delete orphan files of",
+        }
+    }
+}
+
+/// The options of a maintenance operation that is being set up. Submitting
+/// the form only plans the operation, as a dry run.
+#[derive(Clone, Debug)]
+pub(crate) struct MaintenanceForm {
+    table: TableRef,
+    kind: MaintenanceKind,
+    fields: TextFields,
+}
+
+impl MaintenanceForm {
+    pub(crate) fn new(table: TableRef, kind: MaintenanceKind) -> Self {
+        let mut fields = TextFields::new(kind.fields());
+        for (idx, default) in kind.defaults().iter().enumerate() {
+            fields.set_value(idx, *default);
+        }
+
+        Self {
+            table,
+            kind,
+            fields,
+        }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] =
+        text_form_key_bindings!(Command::MaintenanceForm, "Dry run");
+
+    pub(crate) fn next_field(&mut self) {
+        self.fields.next_field();
+    }
+
+    pub(crate) fn prev_field(&mut self) {
+        self.fields.prev_field();
+    }
+
+    /// Type into the focused field
+    pub(crate) fn edit(&mut self, key_event: KeyEvent) {
+        self.fields.edit(key_event);
+    }
+
+    /// The maintenance operation that the form describes. If the form isn't
+    /// valid, the reason is shown in the form.
+    pub(crate) fn submit(&mut self) -> Option<MaintenanceRequest> {
+        let request = self.request();
+        self.fields.submit(request)
+    }
+
+    fn request(&self) -> Result<MaintenanceRequest, String> {
+        let operation = match self.kind {
+            MaintenanceKind::ExpireSnapshots => {
+                let older_than_days = self.days(0)?;
+                let retain_last = self.number(1)?;
+                if older_than_days.is_none() && retain_last.is_none() {
+                    return Err(
+                        "enter an age, a number of snapshots to retain, or both".to_string()
+                    );
+                }
+
+                MaintenanceOperation::ExpireSnapshots {
+                    older_than_days,
+                    retain_last,
+                }
+            }
+            MaintenanceKind::RemoveOldMetadata => MaintenanceOperation::RemoveOldMetadata {
+                retain_last: self
+                    .number(0)?
+                    .ok_or("enter how many metadata files to retain")?,
+            },
+            MaintenanceKind::DeleteOrphanFiles => {
+                let older_than_days = self.days(0)?.ok_or("enter an age in days")?;
+                // younger files may belong to a commit in progress
+                if older_than_days == 0 {
+                    return Err("orphan files must be at least a day old".to_string());
+                }
+
+                MaintenanceOperation::DeleteOrphanFiles { older_than_days }
+            }
+        };
+
+        Ok(MaintenanceRequest {
+            table: self.table.clone(),
+            operation,
+        })
+    }
+
+    /// The number in a field, or `None` if it is blank
+    fn number<T: FromStr>(&self, idx: usize) -> Result<Option<T>, String> {
+        match self.fields.value(idx).trim() {
+            "" => Ok(None),
+            value => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("'{value}' is not a whole number")),
+        }
+    }
+
+    /// The age in days in a field, or `None` if it is blank
+    fn days(&self, idx: usize) -> Result<Option<u64>, String> {
+        match self.number(idx)? {
+            Some(days) if days > MAX_DAYS => Err(format!("enter at most {MAX_DAYS} days")),
+            days => Ok(days),
+        }
+    }
+}
+
+/// Popup for setting up a maintenance operation
+pub(crate) struct MaintenanceDialog<'a> {
+    form: &'a MaintenanceForm,
+    theme: &'a Theme,
+}
+
+impl<'a> MaintenanceDialog<'a> {
+    pub(crate) fn new(form: &'a MaintenanceForm, theme: &'a Theme) -> Self {
+        Self { form, theme }
+    }
+}
+
+impl Widget for &MaintenanceDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = self.form.fields.lines(self.theme);
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            "Nothing is removed until the dry run is confirmed",
+            self.theme.log,
+        ));
+        lines.extend(self.form.fields.error_lines(self.theme));
+
+        let title = format!(" {} {} ", self.form.kind.title(), self.form.table.name);
+        text_form::render_popup(title, lines, 64, self.theme, area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme, type_text};
+    use crossterm::event::KeyCode;
+
+    fn form(kind: MaintenanceKind) -> MaintenanceForm {
+        MaintenanceForm::new(orders_table(), kind)
+    }
+
+    #[test]
+    fn builds_an_operation_from_the_fields() {
+        let mut expiry = form(MaintenanceKind::ExpireSnapshots);
+        expiry.next_field();
+        type_text(&mut expiry, "20");
+
+        assert_eq!(
+            expiry.submit().unwrap().operation,
+            MaintenanceOperation::ExpireSnapshots {
+                older_than_days: Some(7),
+                retain_last: Some(20),
+            }
+        );
+
+        let request = form(MaintenanceKind::DeleteOrphanFiles).submit().unwrap();
+        assert_eq!(
+            request.operation,
+            MaintenanceOperation::DeleteOrphanFiles { older_than_days: 3 }
+        );
+    }
+
+    #[test]
+    fn shows_why_a_form_is_invalid() {
+        let mut form = form(MaintenanceKind::ExpireSnapshots);
+        form.edit(KeyEvent::from(KeyCode::Backspace));
+
+        assert_eq!(form.submit(), None);
+        insta::assert_snapshot!(render(&MaintenanceDialog::new(&form, &theme()), (80, 14)));
+    }
+
+    #[test]
+    fn rejects_ages_out_of_range() {
+        let mut expiry = form(MaintenanceKind::ExpireSnapshots);
+        type_text(&mut expiry, "000000");
+        assert_eq!(expiry.submit(), None);
+        assert_eq!(
+            expiry.fields.error.as_deref(),
+            Some("enter at most 36500 days")
+        );
+
+        let mut orphans = form(MaintenanceKind::DeleteOrphanFiles);
+        orphans.edit(KeyEvent::from(KeyCode::Backspace));
+        type_text(&mut orphans, "0");
+        assert_eq!(orphans.submit(), None);
+        assert_eq!(
+            orphans.fields.error.as_deref(),
+            Some("orphan files must be at least a day old")
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Cell, Padding, Paragraph, Row, Table, Wrap};
+
+use tanic_svc::maintenance::{MaintenanceOutcome, MaintenancePlan, Removal};
+use tanic_svc::state::ViewingMaintenancePlanState;
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::{format_bytes, Theme};
+use crate::ui_components::empty_state::EmptyState;
+
+/// A dry run of a maintenance operation, listing exactly what applying it
+/// removes, and what was removed once it has been applied
+pub(crate) struct MaintenancePlanView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> MaintenancePlanView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Enter,
+            key_label: "Enter",
+            description: "Apply",
+            command: Command::OpenConfirmForm,
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Scroll up",
+            command: Command::Action(TanicAction::ScrollMaintenancePlanUp),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Scroll down",
+            command: Command::Action(TanicAction::ScrollMaintenancePlanDown),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Dry run again",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: false,
+        },
+    ];
+
+    fn block(&self, plan: &MaintenancePlan, applied: bool) -> Block<'static> {
+        let mut totals = format!(" {} to remove · {} kept ", plan.removals.len(), plan.kept);
+        let bytes = plan.bytes_to_remove();
+        if bytes > 0 {
+            totals = format!(
+                " {} to remove, {} · {} kept ",
+                plan.removals.len(),
+                format_bytes(bytes),
+                plan.kept
+            );
+        }
+        let mode = if applied { " Applied " } else { " Dry run " };
+
+        Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} ", plan.request.table.label()),
+                self.theme.title,
+            ))
+            .title(
+                Line::styled(format!(" {} ", plan.request.operation), self.theme.base)
+                    .right_aligned(),
+            )
+            .title_bottom(Line::styled(mode, self.theme.title))
+            .title_bottom(Line::styled(totals, self.theme.base).right_aligned())
+            .border_style(self.theme.base)
+            .padding(Padding::horizontal(1))
+    }
+
+    /// Whether the plan has been applied, and if so what came of it
+    fn status_lines(
+        &self,
+        outcome: Option<&MaintenanceOutcome>,
+        total: usize,
+    ) -> Vec<Line<'static>> {
+        let Some(outcome) = outcome else {
+            return vec![Line::styled(
+                "Dry run: nothing has been removed. Press Enter to apply it.",
+                self.theme.diff,
+            )];
+        };
+
+        let mut lines = vec![Line::styled(
+            format!(
+                "Removed {} of {total}. Press r to run the dry run again.",
+                outcome.removed
+            ),
+            self.theme.base,
+        )];
+        lines.extend(outcome.failures.iter().map(|failure| {
+            Line::styled(
+                format!("{} could not be removed: {}", failure.target, failure.error),
+                self.theme.error,
+            )
+        }));
+
+        lines
+    }
+
+    fn table(&self, plan: &MaintenancePlan, scroll: usize) -> Table<'static> {
+        let cell = |text: String| Cell::from(Span::styled(text, self.theme.base));
+
+        let snapshots = matches!(plan.removals.first(), Some(Removal::Snapshot { .. }));
+        let (titles, widths) = if snapshots {
+            (
+                vec!["Snapshot", "Committed", "Operation"],
+                vec![
+                    Constraint::Length(20),
+                    Constraint::Length(24),
+                    Constraint::Fill(1),
+                ],
+            )
+        } else {
+            (
+                vec!["File", "Size"],
+                vec![Constraint::Fill(1), Constraint::Length(10)],
+            )
+        };
+
+        let rows = plan
+            .removals
+            .iter()
+            .skip(scroll)
+            .map(|removal| match removal {
+                Removal::Snapshot {
+                    id,
+                    committed_at,
+                    operation,
+                } => Row::new(vec![
+                    cell(id.to_string()),
+                    cell(committed_at.clone()),
+                    cell(operation.clone()),
+                ]),
+                Removal::File { path, size_bytes } => Row::new(vec![
+                    cell(path.clone()),
+                    cell(size_bytes.map(format_bytes).unwrap_or_default()),
+                ]),
+            });
+
+        let header = Row::new(
+            titles
+                .into_iter()
+                .map(|title| Cell::from(Span::styled(title, self.theme.title))),
+        );
+
+        Table::new(rows, widths).header(header)
+    }
+}
+
+impl Widget for &MaintenancePlanView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingMaintenancePlan(ViewingMaintenancePlanState {
+            plan,
+            scroll,
+            outcome,
+        }) = self.state
+        else {
+            return;
+        };
+        let block = self.block(plan, outcome.is_some());
+
+        if plan.removals.is_empty() {
+            EmptyState::new(block, "Nothing to remove", self.theme).render(area, buf);
+            return;
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let status = self.status_lines(outcome.as_ref(), plan.removals.len());
+        let [status_area, removals_area] = Layout::vertical([
+            Constraint::Length(status.len() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        Paragraph::new(status)
+            .wrap(Wrap { trim: true })
+            .render(status_area, buf);
+        Widget::render(self.table(plan, *scroll), removals_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme};
+    use tanic_svc::maintenance::{MaintenanceOperation, MaintenanceRequest, RemovalFailure};
+
+    fn plan(operation: MaintenanceOperation, removals: Vec<Removal>) -> MaintenancePlan {
+        MaintenancePlan {
+            request: MaintenanceRequest {
+                table: orders_table(),
+                operation,
+            },
+            snapshot_id: Some(3),
+            table_uuid: "00000000-0000-0000-0000-000000000000".to_string(),
+            removals,
+            kept: 2,
+        }
+    }
+
+    #[test]
+    fn lists_the_snapshots_a_dry_run_would_expire() {
+        let snapshot = |id, committed_at: &str| Removal::Snapshot {
+            id,
+            committed_at: committed_at.to_string(),
+            operation: "append".to_string(),
+        };
+        let state = TanicAppState::ViewingMaintenancePlan(ViewingMaintenancePlanState {
+            plan: plan(
+                MaintenanceOperation::ExpireSnapshots {
+                    older_than_days: Some(7),
+                    retain_last: None,
+                },
+                vec![
+                    snapshot(1, "2025-01-01 00:00:00 UTC"),
+                    snapshot(2, "2025-01-02 00:00:00 UTC"),
+                ],
+            ),
+            scroll: 0,
+            outcome: None,
+        });
+        let theme = theme();
+
+        let view = MaintenancePlanView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (100, 9)));
+    }
+
+    #[test]
+    fn shows_what_was_removed_once_applied() {
+        let file = |name: &str| Removal::File {
+            path: format!("/warehouse/sales/orders/data/{name}"),
+            size_bytes: Some(3 << 20),
+        };
+        let state = TanicAppState::ViewingMaintenancePlan(ViewingMaintenancePlanState {
+            plan: plan(
+                MaintenanceOperation::DeleteOrphanFiles { older_than_days: 3 },
+                vec![file("a.parquet"), file("b.parquet")],
+            ),
+            scroll: 0,
+            outcome: Some(MaintenanceOutcome {
+                removed: 1,
+                failures: vec![RemovalFailure {
+                    target: "/warehouse/sales/orders/data/b.parquet".to_string(),
+                    error: "permission denied".to_string(),
+                }],
+            }),
+        });
+        let theme = theme();
+
+        let view = MaintenancePlanView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (100, 10)));
+    }
+}
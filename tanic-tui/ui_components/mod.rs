@@ -1,4 +1,25 @@
 pub(crate) mod app_container;
+pub(crate) mod confirm_dialog;
+pub(crate) mod delete_health_view;
+mod empty_state;
+mod error_view;
+pub(crate) mod export_dialog;
+mod export_progress;
+pub(crate) mod filter_dialog;
+mod footer_bar;
+pub(crate) mod health_report_view;
+mod help_overlay;
+mod loading_view;
+mod log_panel;
+pub(crate) mod maintenance_dialog;
+pub(crate) mod maintenance_plan_view;
 pub(crate) mod namespace_list_view;
+pub(crate) mod scan_plan_view;
 mod splash_screen;
+mod tab_bar;
+pub(crate) mod table_comparison_view;
 pub(crate) mod table_list_view;
+pub(crate) mod table_preview_view;
+pub(crate) mod table_profile_view;
+mod text_form;
+pub(crate) mod time_travel_dialog;
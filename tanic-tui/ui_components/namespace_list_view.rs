@@ -1,31 +1,77 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::KeyCode;
 use ratatui::prelude::*;
 use ratatui::widgets::canvas::{Canvas, Rectangle};
 use ratatui::widgets::Block;
 use treemap::{MapItem, Mappable, Rect as TreeMapRect, TreemapLayout};
 
+use tanic_svc::report::ReportFormat;
 use tanic_svc::{TanicAction, TanicAppState};
 
-// find more at https://www.nerdfonts.com/cheat-sheet
-const NERD_FONT_ICON_TABLE_FOLDER: &str = "\u{f12e4}"; // 󱋤
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::empty_state::EmptyState;
 
 pub(crate) struct NamespaceListView<'a> {
     state: &'a TanicAppState,
+    theme: &'a Theme,
 }
 
 impl<'a> NamespaceListView<'a> {
-    pub(crate) fn new(state: &'a TanicAppState) -> Self {
-        Self { state }
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
     }
 
-    pub(crate) fn handle_key_event(&self, key_event: KeyEvent) -> Option<TanicAction> {
-        match key_event.code {
-            KeyCode::Left => Some(TanicAction::FocusPrevNamespace),
-            KeyCode::Right => Some(TanicAction::FocusNextNamespace),
-            KeyCode::Enter => Some(TanicAction::SelectNamespace),
-            _ => None,
-        }
-    }
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Left,
+            key_label: "←",
+            description: "Previous namespace",
+            command: Command::Action(TanicAction::FocusPrevNamespace),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Right,
+            key_label: "→",
+            description: "Next namespace",
+            command: Command::Action(TanicAction::FocusNextNamespace),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Enter,
+            key_label: "Enter",
+            description: "Open namespace",
+            command: Command::Action(TanicAction::SelectNamespace),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('H'),
+            key_label: "H",
+            description: "Namespace health",
+            command: Command::Action(TanicAction::CheckNamespaceHealth),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('M'),
+            key_label: "M",
+            description: "Write Markdown report",
+            command: Command::Action(TanicAction::WriteReport(ReportFormat::Markdown)),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('J'),
+            key_label: "J",
+            description: "Write JSON report",
+            command: Command::Action(TanicAction::WriteReport(ReportFormat::Json)),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: true,
+        },
+    ];
 }
 
 impl Widget for &NamespaceListView<'_> {
@@ -39,7 +85,7 @@ impl Widget for &NamespaceListView<'_> {
         );
 
         let TanicAppState::ViewingNamespacesList(view_state) = self.state else {
-            panic!();
+            return;
         };
 
         let mut items: Vec<Box<dyn Mappable>> = view_state
@@ -56,8 +102,23 @@ impl Widget for &NamespaceListView<'_> {
 
         let selected_idx = view_state.selected_idx;
 
+        let mut block = Block::bordered()
+            .title(Line::styled(
+                " Tanic //// Root Namespaces",
+                self.theme.title,
+            ))
+            .border_style(self.theme.base);
+        if view_state.refreshing {
+            block = block.title(Line::styled(" refreshing… ", self.theme.base).right_aligned());
+        }
+
+        if view_state.namespaces.is_empty() {
+            EmptyState::new(block, "No namespaces in this catalog", self.theme).render(area, buf);
+            return;
+        }
+
         let canvas = Canvas::default()
-            .block(Block::bordered().title(" Tanic //// Root Namespaces"))
+            .block(block)
             .x_bounds([area.x as f64, (area.x + area.width) as f64])
             .y_bounds([area.y as f64, (area.y + area.height) as f64])
             .paint(|ctx| {
@@ -69,30 +130,34 @@ impl Widget for &NamespaceListView<'_> {
                         y: item_bounds.y,
                         width: item_bounds.w,
                         height: item_bounds.h,
-                        color: Color::White,
+                        color: self.theme.tile,
                     };
 
                     ctx.draw(&rect);
 
                     let style = if Some(idx) == selected_idx {
-                        Style::new().black().bold().on_white()
+                        self.theme.tile_selected
                     } else {
-                        Style::new().white()
+                        self.theme.tile_label
                     };
 
                     let ns = &view_state.namespaces[idx];
                     let name = ns.name.clone();
                     let plural_suffix = if ns.table_count == 1 { "" } else { "s" };
-                    let name = format!(
+                    let mut name = format!(
                         "{} {} ({} table{})",
-                        NERD_FONT_ICON_TABLE_FOLDER, name, ns.table_count, plural_suffix
+                        self.theme.icons.namespace, name, ns.table_count, plural_suffix
                     );
+                    // cached namespaces that the catalog hasn't confirmed yet
+                    if ns.stale {
+                        name.push_str(" · stale");
+                    }
 
-                    let name_len = name.len();
                     let text = Line::styled(name, style);
+                    let name_width = text.width();
 
                     ctx.print(
-                        item_bounds.x + (item_bounds.w * 0.5) - (name_len as f64 * 0.5),
+                        item_bounds.x + (item_bounds.w * 0.5) - (name_width as f64 * 0.5),
                         item_bounds.y + (item_bounds.h * 0.5),
                         text,
                     );
@@ -102,3 +167,58 @@ impl Widget for &NamespaceListView<'_> {
         canvas.render(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{namespaces_state, render, theme, SIZES};
+
+    #[test]
+    fn renders_namespaces_sized_by_table_count() {
+        let state = namespaces_state(&[("sales", 8), ("marketing", 3), ("hr", 1)], Some(1));
+        let theme = theme();
+
+        for &size in SIZES {
+            let view = NamespaceListView::new(&state, &theme);
+            insta::assert_snapshot!(
+                format!("namespaces_{}x{}", size.0, size.1),
+                render(&view, size)
+            );
+        }
+    }
+
+    #[test]
+    fn renders_a_single_namespace() {
+        let state = namespaces_state(&[("default", 1)], Some(0));
+        let theme = theme();
+
+        for &size in SIZES {
+            let view = NamespaceListView::new(&state, &theme);
+            insta::assert_snapshot!(
+                format!("single_namespace_{}x{}", size.0, size.1),
+                render(&view, size)
+            );
+        }
+    }
+
+    #[test]
+    fn renders_an_empty_namespace_list() {
+        let state = namespaces_state(&[], None);
+        let theme = theme();
+
+        let view = NamespaceListView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+
+    #[test]
+    fn renders_refreshing_title() {
+        let mut state = namespaces_state(&[("sales", 8), ("hr", 1)], Some(0));
+        if let TanicAppState::ViewingNamespacesList(view_state) = &mut state {
+            view_state.refreshing = true;
+        }
+        let theme = theme();
+
+        let view = NamespaceListView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+}
@@ -0,0 +1,355 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Padding, Paragraph};
+
+use tanic_svc::scan_plan::{FileOutcome, ManifestPlan, PlannedFile, ScanPlan};
+use tanic_svc::state::ViewingScanPlanState;
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::{format_bytes, Theme};
+use crate::ui_components::empty_state::EmptyState;
+
+const BRANCH: &str = "├─ ";
+const LAST_BRANCH: &str = "└─ ";
+const TRUNK: &str = "│  ";
+const NO_TRUNK: &str = "   ";
+
+/// The files that a filtered scan of a table reads, as a tree
+pub(crate) struct ScanPlanView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> ScanPlanView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Scroll up",
+            command: Command::Action(TanicAction::ScrollScanPlanUp),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Scroll down",
+            command: Command::Action(TanicAction::ScrollScanPlanDown),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: false,
+        },
+    ];
+
+    fn block(&self, plan: &ScanPlan) -> Block<'static> {
+        let request = &plan.request;
+
+        let point_in_time = match (&request.as_of, plan.snapshot_id) {
+            (Some(as_of), Some(id)) => format!(" as of {as_of}, snapshot {id} "),
+            (None, Some(id)) => format!(" snapshot {id} "),
+            (_, None) => " no snapshots ".to_string(),
+        };
+        let filter = match &request.filter {
+            Some(filter) => format!("where {filter} "),
+            None => "unfiltered ".to_string(),
+        };
+
+        let mut block = Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} ", request.table.label()),
+                self.theme.title,
+            ))
+            .title(Line::styled(point_in_time, self.theme.base).right_aligned())
+            .title_bottom(Line::from(vec![
+                Span::styled(" Scan plan ", self.theme.title),
+                Span::styled(filter, self.theme.diff),
+            ]))
+            .border_style(self.theme.base)
+            .padding(Padding::horizontal(1));
+        if plan.estimated {
+            block = block.title_bottom(
+                Line::styled(" estimated: the table has delete files ", self.theme.diff)
+                    .right_aligned(),
+            );
+        }
+
+        block
+    }
+
+    /// The totals at the top of the plan
+    fn summary_lines(&self, plan: &ScanPlan) -> Vec<Line<'static>> {
+        let (position_deletes, equality_deletes) = (plan.position_deletes, plan.equality_deletes);
+        let selected_deletes = position_deletes.selected + equality_deletes.selected;
+        let selected_files = plan.data_files.selected + selected_deletes;
+
+        vec![
+            self.node(
+                BRANCH,
+                format!(
+                    "Data files: {} of {} selected",
+                    plan.data_files.selected, plan.data_files.total
+                ),
+            ),
+            self.node(
+                &format!("{TRUNK}{BRANCH}"),
+                format!(
+                    "{} pruned by partition",
+                    plan.data_files.pruned_by_partition
+                ),
+            ),
+            self.node(
+                &format!("{TRUNK}{LAST_BRANCH}"),
+                format!(
+                    "{} pruned by column metrics",
+                    plan.data_files.pruned_by_metrics
+                ),
+            ),
+            self.node(
+                BRANCH,
+                format!(
+                    "Delete files: {selected_deletes} of {} selected",
+                    position_deletes.total + equality_deletes.total
+                ),
+            ),
+            self.node(
+                &format!("{TRUNK}{BRANCH}"),
+                format!(
+                    "{} of {} position deletes",
+                    position_deletes.selected, position_deletes.total
+                ),
+            ),
+            self.node(
+                &format!("{TRUNK}{LAST_BRANCH}"),
+                format!(
+                    "{} of {} equality deletes",
+                    equality_deletes.selected, equality_deletes.total
+                ),
+            ),
+            self.node(
+                BRANCH,
+                format!(
+                    "Bytes to read: {} in {}, {} records",
+                    format_bytes(plan.bytes_to_read()),
+                    files(selected_files),
+                    plan.data_files.selected_records
+                ),
+            ),
+            self.node(
+                LAST_BRANCH,
+                format!(
+                    "Manifests: {} of {} match",
+                    plan.matching_manifests(),
+                    plan.manifests.len()
+                ),
+            ),
+        ]
+    }
+
+    /// Each manifest, and the files listed under it
+    fn manifest_lines(&self, plan: &ScanPlan) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+
+        for (idx, manifest) in plan.manifests.iter().enumerate() {
+            let last_manifest = idx + 1 == plan.manifests.len();
+            let (branch, trunk) = if last_manifest {
+                (LAST_BRANCH, NO_TRUNK)
+            } else {
+                (BRANCH, TRUNK)
+            };
+            lines.push(self.manifest_line(&format!("{NO_TRUNK}{branch}"), manifest));
+
+            let unlisted = manifest.files.total - manifest.listed.len();
+            for (idx, file) in manifest.listed.iter().enumerate() {
+                let last_file = unlisted == 0 && idx + 1 == manifest.listed.len();
+                let branch = if last_file { LAST_BRANCH } else { BRANCH };
+                lines.push(self.file_line(&format!("{NO_TRUNK}{trunk}{branch}"), file));
+            }
+            if unlisted > 0 {
+                lines.push(self.node(
+                    &format!("{NO_TRUNK}{trunk}{LAST_BRANCH}"),
+                    format!("… {} more", files(unlisted)),
+                ));
+            }
+        }
+
+        lines
+    }
+
+    fn node(&self, prefix: &str, text: String) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(prefix.to_string(), self.theme.log),
+            Span::styled(text, self.theme.base),
+        ])
+    }
+
+    fn manifest_line(&self, prefix: &str, manifest: &ManifestPlan) -> Line<'static> {
+        let style = if manifest.files.selected > 0 {
+            self.theme.title
+        } else {
+            self.theme.log
+        };
+        let kind = if manifest.deletes { "deletes" } else { "data" };
+
+        Line::from(vec![
+            Span::styled(prefix.to_string(), self.theme.log),
+            Span::styled(file_name(&manifest.path).to_string(), style),
+            Span::styled(
+                format!(
+                    "  {kind} · {} of {} files selected",
+                    manifest.files.selected, manifest.files.total
+                ),
+                self.theme.base,
+            ),
+        ])
+    }
+
+    fn file_line(&self, prefix: &str, file: &PlannedFile) -> Line<'static> {
+        let (outcome, style) = match file.outcome {
+            FileOutcome::Selected => ("selected", self.theme.title),
+            FileOutcome::PrunedByPartition => ("pruned by partition", self.theme.log),
+            FileOutcome::PrunedByMetrics => ("pruned by metrics", self.theme.log),
+        };
+        let partition = if file.partition.is_empty() {
+            String::new()
+        } else {
+            format!(" · {}", file.partition)
+        };
+
+        Line::from(vec![
+            Span::styled(prefix.to_string(), self.theme.log),
+            Span::styled(file_name(&file.path).to_string(), style),
+            Span::styled(
+                format!(
+                    "  {outcome}{partition} · {} records · {}",
+                    file.records,
+                    format_bytes(file.size_bytes)
+                ),
+                self.theme.base,
+            ),
+        ])
+    }
+}
+
+fn files(count: usize) -> String {
+    match count {
+        1 => "1 file".to_string(),
+        count => format!("{count} files"),
+    }
+}
+
+/// The last part of a file's path
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+impl Widget for &ScanPlanView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingScanPlan(ViewingScanPlanState { plan, scroll }) = self.state
+        else {
+            return;
+        };
+        let block = self.block(plan);
+
+        if plan.snapshot_id.is_none() {
+            EmptyState::new(block, "This table has no snapshots to scan", self.theme)
+                .render(area, buf);
+            return;
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let summary = self.summary_lines(plan);
+        let [summary_area, manifests_area] = Layout::vertical([
+            Constraint::Length(summary.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        Paragraph::new(summary).render(summary_area, buf);
+        Paragraph::new(self.manifest_lines(plan))
+            .scroll((*scroll as u16, 0))
+            .render(manifests_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme};
+    use tanic_svc::preview::PreviewRequest;
+    use tanic_svc::scan_plan::{FileContent, FileCounts};
+
+    fn file(name: &str, day: &str, outcome: FileOutcome) -> PlannedFile {
+        PlannedFile {
+            path: format!("s3://warehouse/sales/orders/data/{name}.parquet"),
+            content: FileContent::Data,
+            partition: format!("ts_day={day}"),
+            records: 1000,
+            size_bytes: 2048,
+            outcome,
+        }
+    }
+
+    fn plan_state() -> TanicAppState {
+        let counts = |selected, by_partition, by_metrics| FileCounts {
+            total: selected + by_partition + by_metrics,
+            selected,
+            pruned_by_partition: by_partition,
+            pruned_by_metrics: by_metrics,
+            selected_bytes: 2048 * selected as u64,
+            selected_records: 1000 * selected as u64,
+        };
+
+        TanicAppState::ViewingScanPlan(ViewingScanPlanState {
+            plan: ScanPlan {
+                request: PreviewRequest {
+                    filter: Some("ts >= '2024-01-02' AND total > 5".parse().unwrap()),
+                    ..PreviewRequest::current(orders_table())
+                },
+                snapshot_id: Some(42),
+                estimated: false,
+                data_files: counts(1, 1, 1),
+                position_deletes: FileCounts::default(),
+                equality_deletes: FileCounts::default(),
+                manifests: vec![
+                    ManifestPlan {
+                        path: "s3://warehouse/sales/orders/metadata/m0.avro".to_string(),
+                        deletes: false,
+                        files: counts(1, 1, 1),
+                        listed: vec![
+                            file("a", "2024-01-01", FileOutcome::PrunedByPartition),
+                            file("b", "2024-01-02", FileOutcome::Selected),
+                            file("c", "2024-01-03", FileOutcome::PrunedByMetrics),
+                        ],
+                    },
+                    ManifestPlan {
+                        path: "s3://warehouse/sales/orders/metadata/m1.avro".to_string(),
+                        deletes: false,
+                        files: counts(0, 0, 0),
+                        listed: vec![],
+                    },
+                ],
+            },
+            scroll: 0,
+        })
+    }
+
+    #[test]
+    fn renders_the_plan_as_a_tree() {
+        let state = plan_state();
+        let theme = theme();
+
+        let view = ScanPlanView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (90, 16)));
+    }
+}
@@ -0,0 +1,39 @@
+use ratatui::prelude::*;
+use ratatui::widgets::Tabs;
+use tanic_svc::TanicTabsState;
+
+use crate::theme::Theme;
+
+/// One-line bar listing the open connections, with the active one highlighted
+pub(crate) struct TabBar<'a> {
+    tabs_state: &'a TanicTabsState,
+    theme: &'a Theme,
+}
+
+impl<'a> TabBar<'a> {
+    pub(crate) fn new(tabs_state: &'a TanicTabsState, theme: &'a Theme) -> Self {
+        Self { tabs_state, theme }
+    }
+}
+
+impl Widget for &TabBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let titles = self.tabs_state.tabs.iter().enumerate().map(|(idx, tab)| {
+            let name = tab
+                .connection
+                .as_ref()
+                .map(|conn_details| conn_details.name.as_str())
+                .unwrap_or("(not connected)");
+
+            Line::from(format!(" {}: {} ", idx + 1, name))
+        });
+
+        Tabs::new(titles)
+            .select(self.tabs_state.active_idx)
+            .style(self.theme.base)
+            .highlight_style(self.theme.tile_selected)
+            .padding("", "")
+            .divider("|")
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,160 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Cell, Row, Table, TableState};
+
+use tanic_svc::comparison::{DiffEntry, TableRef};
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::empty_state::EmptyState;
+
+pub(crate) struct TableComparisonView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> TableComparisonView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Previous row",
+            command: Command::Action(TanicAction::FocusPrevComparisonRow),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Next row",
+            command: Command::Action(TanicAction::FocusNextComparisonRow),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('d'),
+            key_label: "d",
+            description: "Show only differences",
+            command: Command::Action(TanicAction::ToggleComparisonDifferencesOnly),
+            in_footer: true,
+        },
+    ];
+
+    fn column_header(&self, table_ref: &TableRef, found: bool) -> Cell<'static> {
+        let label = if found {
+            table_ref.label()
+        } else {
+            format!("{} (not found)", table_ref.label())
+        };
+
+        Cell::from(Span::styled(label, self.theme.title))
+    }
+
+    fn entry_row(&self, entry: &DiffEntry) -> Row<'static> {
+        let value_style = if entry.is_different() {
+            self.theme.diff
+        } else {
+            self.theme.base
+        };
+        let value = |value: &Option<String>| {
+            Cell::from(Span::styled(
+                value.clone().unwrap_or_else(|| "-".to_string()),
+                value_style,
+            ))
+        };
+
+        Row::new(vec![
+            Cell::from(Span::styled(format!("  {}", entry.key), self.theme.base)),
+            value(&entry.left),
+            value(&entry.right),
+        ])
+    }
+}
+
+impl Widget for &TableComparisonView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingTableComparison(view_state) = self.state else {
+            return;
+        };
+        let comparison = &view_state.comparison;
+
+        let mut rows = vec![];
+        let mut selected_row = None;
+        let mut entry_idx = 0;
+
+        for section in &comparison.sections {
+            let entries = section
+                .entries
+                .iter()
+                .filter(|entry| !view_state.differences_only || entry.is_different())
+                .collect::<Vec<_>>();
+            if entries.is_empty() {
+                continue;
+            }
+
+            rows.push(Row::new(vec![Cell::from(Span::styled(
+                section.title.clone(),
+                self.theme.title,
+            ))]));
+
+            for entry in entries {
+                if entry_idx == view_state.selected_idx {
+                    selected_row = Some(rows.len());
+                }
+                rows.push(self.entry_row(entry));
+                entry_idx += 1;
+            }
+        }
+
+        let header = Row::new(vec![
+            Cell::from(Span::styled("Field", self.theme.title)),
+            self.column_header(&comparison.left, comparison.left_found),
+            self.column_header(&comparison.right, comparison.right_found),
+        ]);
+
+        let mut block = Block::bordered()
+            .title(Line::styled(
+                " Tanic //// Table Comparison ",
+                self.theme.title,
+            ))
+            .title_bottom(
+                Line::styled(
+                    format!(" {} differences ", comparison.difference_count()),
+                    self.theme.base,
+                )
+                .right_aligned(),
+            )
+            .border_style(self.theme.base);
+        if view_state.differences_only {
+            block = block.title_bottom(Line::styled(" differences only ", self.theme.base));
+        }
+
+        if rows.is_empty() {
+            let message = if view_state.differences_only {
+                "No differences between these tables"
+            } else {
+                "Neither table could be loaded"
+            };
+            EmptyState::new(block, message, self.theme).render(area, buf);
+            return;
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Percentage(35),
+                Constraint::Percentage(35),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(self.theme.tile_selected);
+
+        let mut table_state = TableState::default().with_selected(selected_row);
+        StatefulWidget::render(table, area, buf, &mut table_state);
+    }
+}
@@ -1,31 +1,175 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::KeyCode;
 use ratatui::prelude::*;
 use ratatui::widgets::canvas::{Canvas, Rectangle};
 use ratatui::widgets::Block;
 use treemap::{MapItem, Mappable, Rect as TreeMapRect, TreemapLayout};
 
+use tanic_core::message::{Severity, TableDeets};
+use tanic_svc::comparison::TableRef;
+use tanic_svc::health::score;
+use tanic_svc::report::ReportFormat;
 use tanic_svc::{TanicAction, TanicAppState};
 
-// find more at https://www.nerdfonts.com/cheat-sheet
-const NERD_FONT_ICON_TABLE: &str = "\u{ebb7}"; // 
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::{metric_value, Theme};
+use crate::ui_components::empty_state::EmptyState;
+use crate::ui_components::maintenance_dialog::MaintenanceKind;
 
 pub(crate) struct TableListView<'a> {
     state: &'a TanicAppState,
+    comparison_mark: Option<&'a TableRef>,
+    theme: &'a Theme,
 }
 
 impl<'a> TableListView<'a> {
-    pub(crate) fn new(state: &'a TanicAppState) -> Self {
-        Self { state }
+    pub(crate) fn new(
+        state: &'a TanicAppState,
+        comparison_mark: Option<&'a TableRef>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            state,
+            comparison_mark,
+            theme,
+        }
     }
 
-    pub(crate) fn handle_key_event(&self, key_event: KeyEvent) -> Option<TanicAction> {
-        match key_event.code {
-            KeyCode::Left => Some(TanicAction::FocusPrevTable),
-            KeyCode::Right => Some(TanicAction::FocusNextTable),
-            KeyCode::Enter => Some(TanicAction::SelectTable),
-            KeyCode::Esc => Some(TanicAction::LeaveNamespace),
-            _ => None,
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Left,
+            key_label: "←",
+            description: "Previous table",
+            command: Command::Action(TanicAction::FocusPrevTable),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Right,
+            key_label: "→",
+            description: "Next table",
+            command: Command::Action(TanicAction::FocusNextTable),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Enter,
+            key_label: "Enter",
+            description: "Open table",
+            command: Command::Action(TanicAction::SelectTable),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('m'),
+            key_label: "m",
+            description: "Mark table for comparison",
+            command: Command::Action(TanicAction::MarkTableForComparison),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('c'),
+            key_label: "c",
+            description: "Compare with marked table",
+            command: Command::Action(TanicAction::CompareWithMarkedTable),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('p'),
+            key_label: "p",
+            description: "Profile columns",
+            command: Command::Action(TanicAction::ProfileSelectedTable),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('d'),
+            key_label: "d",
+            description: "Delete files",
+            command: Command::Action(TanicAction::InspectSelectedTableDeletes),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('h'),
+            key_label: "h",
+            description: "Table health",
+            command: Command::Action(TanicAction::CheckSelectedTableHealth),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('H'),
+            key_label: "H",
+            description: "Namespace health",
+            command: Command::Action(TanicAction::CheckNamespaceHealth),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('x'),
+            key_label: "x",
+            description: "Export table",
+            command: Command::OpenExportForm,
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('E'),
+            key_label: "E",
+            description: "Expire snapshots",
+            command: Command::OpenMaintenanceForm(MaintenanceKind::ExpireSnapshots),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('D'),
+            key_label: "D",
+            description: "Remove old metadata",
+            command: Command::OpenMaintenanceForm(MaintenanceKind::RemoveOldMetadata),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('O'),
+            key_label: "O",
+            description: "Delete orphan files",
+            command: Command::OpenMaintenanceForm(MaintenanceKind::DeleteOrphanFiles),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('M'),
+            key_label: "M",
+            description: "Write Markdown report",
+            command: Command::Action(TanicAction::WriteReport(ReportFormat::Markdown)),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('J'),
+            key_label: "J",
+            description: "Write JSON report",
+            command: Command::Action(TanicAction::WriteReport(ReportFormat::Json)),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: true,
+        },
+    ];
+
+    /// A table's health score, if the health checks found anything, in the
+    /// warning style if any of the findings are warnings
+    fn health_badge(&self, table: &TableDeets, tile_style: Style) -> Option<Line<'static>> {
+        let findings = &table.summary.as_ref()?.findings;
+        if findings.is_empty() {
+            return None;
         }
+
+        let warned = findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Warning);
+        let style = if warned && tile_style != self.theme.tile_selected {
+            self.theme.diff
+        } else {
+            tile_style
+        };
+
+        Some(Line::styled(
+            format!("{} {}/100", self.theme.icons.warning, score(findings)),
+            style,
+        ))
     }
 }
 
@@ -40,7 +184,7 @@ impl Widget for &TableListView<'_> {
         );
 
         let TanicAppState::ViewingTablesList(view_state) = self.state else {
-            panic!();
+            return;
         };
 
         let mut items: Vec<Box<dyn Mappable>> = view_state
@@ -56,48 +200,204 @@ impl Widget for &TableListView<'_> {
         layout.layout_items(&mut items, bounds);
 
         let selected_idx = view_state.selected_idx;
+        let metric_range = self.theme.metric_range(&view_state.tables);
+
+        let mut block = Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} Namespace ", view_state.namespace.name),
+                self.theme.title,
+            ))
+            .border_style(self.theme.base);
+        if view_state.refreshing {
+            block = block.title(Line::styled(" refreshing… ", self.theme.base).right_aligned());
+        }
+        if let Some(mark) = self.comparison_mark {
+            block = block.title_bottom(Line::styled(
+                format!(" marked: {} ", mark.label()),
+                self.theme.base,
+            ));
+        }
+        if let Some(legend) = self.theme.metric_legend(metric_range) {
+            block = block.title_bottom(legend.right_aligned());
+        }
+
+        if view_state.tables.is_empty() {
+            EmptyState::new(block, "No tables in this namespace", self.theme).render(area, buf);
+            return;
+        }
 
         let canvas = Canvas::default()
-            .block(Block::bordered().title(format!(
-                " Tanic //// {} Namespace ",
-                view_state.namespace.name
-            )))
+            .block(block)
             .x_bounds([area.x as f64, (area.x + area.width) as f64])
             .y_bounds([area.y as f64, (area.y + area.height) as f64])
             .paint(|ctx| {
                 for (idx, item) in items.iter().enumerate() {
                     let item_bounds = item.bounds();
+                    let table = &view_state.tables[idx];
+
+                    let tile_color = self
+                        .theme
+                        .metric_color(metric_value(self.theme.tile_metric, table), metric_range);
 
                     let rect = Rectangle {
                         x: item_bounds.x,
                         y: item_bounds.y,
                         width: item_bounds.w,
                         height: item_bounds.h,
-                        color: Color::White,
+                        color: tile_color,
                     };
 
                     ctx.draw(&rect);
 
                     let style = if Some(idx) == selected_idx {
-                        Style::new().black().bold().on_white()
+                        self.theme.tile_selected
+                    } else if tile_color != self.theme.tile {
+                        self.theme.tile_label.fg(tile_color)
                     } else {
-                        Style::new().white()
+                        self.theme.tile_label
                     };
 
-                    let name = view_state.tables[idx].name.clone();
-                    let name = format!("{} {}", NERD_FONT_ICON_TABLE, name);
+                    let name = table.name.clone();
+                    let mut name = format!("{} {}", self.theme.icons.table, name);
+                    // cached tables that the catalog hasn't confirmed yet
+                    if table.stale {
+                        name.push_str(" · stale");
+                    }
 
-                    let name_len = name.len();
                     let text = Line::styled(name, style);
+                    let name_width = text.width();
 
                     ctx.print(
-                        item_bounds.x + (item_bounds.w * 0.5) - (name_len as f64 * 0.5),
+                        item_bounds.x + (item_bounds.w * 0.5) - (name_width as f64 * 0.5),
                         item_bounds.y + (item_bounds.h * 0.5),
                         text,
                     );
+
+                    // badge tables that the health checks found problems with,
+                    // on the line below their name where the tile is tall enough
+                    if let Some(badge) = self.health_badge(table, style) {
+                        if item_bounds.h >= 3.0 {
+                            let badge_width = badge.width();
+                            ctx.print(
+                                item_bounds.x + (item_bounds.w * 0.5) - (badge_width as f64 * 0.5),
+                                item_bounds.y + (item_bounds.h * 0.5) - 1.0,
+                                badge,
+                            );
+                        }
+                    }
                 }
             });
 
         canvas.render(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{conn_details, render, tables_state, theme, SIZES};
+    use tanic_core::config::TileMetric;
+    use tanic_core::message::{HealthCheck, HealthFinding};
+
+    #[test]
+    fn renders_tables_sized_by_row_count() {
+        let state = tables_state(
+            &[("orders", 5000), ("customers", 1200), ("refunds", 50)],
+            Some(0),
+        );
+        let theme = theme();
+
+        for &size in SIZES {
+            let view = TableListView::new(&state, None, &theme);
+            insta::assert_snapshot!(format!("tables_{}x{}", size.0, size.1), render(&view, size));
+        }
+    }
+
+    #[test]
+    fn renders_an_empty_table_list() {
+        let state = tables_state(&[], None);
+        let theme = theme();
+
+        let view = TableListView::new(&state, None, &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+
+    #[test]
+    fn renders_comparison_mark() {
+        let state = tables_state(&[("orders", 5000), ("customers", 1200)], Some(1));
+        let mark = TableRef {
+            connection: conn_details("staging"),
+            namespace: vec!["ns".to_string()],
+            name: "orders".to_string(),
+        };
+        let theme = theme();
+
+        let view = TableListView::new(&state, Some(&mark), &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+
+    #[test]
+    fn renders_metric_legend() {
+        let state = tables_state(&[("orders", 5000), ("customers", 1200)], Some(0));
+        let theme = Theme {
+            tile_metric: TileMetric::Size,
+            ..theme()
+        };
+
+        for &size in SIZES {
+            let view = TableListView::new(&state, None, &theme);
+            insta::assert_snapshot!(
+                format!("metric_legend_{}x{}", size.0, size.1),
+                render(&view, size)
+            );
+        }
+    }
+
+    #[test]
+    fn badges_tables_with_health_findings() {
+        let mut state = tables_state(&[("orders", 5000), ("customers", 1200)], Some(1));
+        if let TanicAppState::ViewingTablesList(view_state) = &mut state {
+            let summary = view_state.tables[0].summary.as_mut().unwrap();
+            summary.findings = vec![
+                HealthFinding {
+                    check: HealthCheck::Snapshots,
+                    severity: Severity::Warning,
+                    message: "250 snapshots retained, more than 100".to_string(),
+                },
+                HealthFinding {
+                    check: HealthCheck::SortOrder,
+                    severity: Severity::Info,
+                    message: "no sort order declared".to_string(),
+                },
+            ];
+        }
+        let theme = theme();
+
+        let view = TableListView::new(&state, None, &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+
+    #[test]
+    fn marks_stale_tables() {
+        let mut state = tables_state(&[("orders", 5000), ("customers", 3000)], Some(0));
+        if let TanicAppState::ViewingTablesList(view_state) = &mut state {
+            view_state.tables[1].stale = true;
+        }
+        let theme = theme();
+
+        let view = TableListView::new(&state, None, &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+
+    #[test]
+    fn renders_refreshing_title() {
+        let mut state = tables_state(&[("orders", 5000)], Some(0));
+        if let TanicAppState::ViewingTablesList(view_state) = &mut state {
+            view_state.refreshing = true;
+        }
+        let theme = theme();
+
+        let view = TableListView::new(&state, None, &theme);
+        insta::assert_snapshot!(render(&view, (80, 24)));
+    }
+}
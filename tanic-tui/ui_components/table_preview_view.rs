@@ -0,0 +1,416 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Cell, Row, Table, TableState};
+
+use tanic_svc::preview::PREVIEW_ROW_LIMIT;
+use tanic_svc::state::{TablePreviewMode, ViewingTablePreviewState};
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::empty_state::EmptyState;
+use crate::ui_components::maintenance_dialog::MaintenanceKind;
+
+/// Widest that a data column is drawn
+const MAX_COLUMN_WIDTH: usize = 32;
+
+pub(crate) struct TablePreviewView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> TablePreviewView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Previous row",
+            command: Command::Action(TanicAction::ScrollPreviewUp),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Next row",
+            command: Command::Action(TanicAction::ScrollPreviewDown),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('s'),
+            key_label: "s",
+            description: "Data/schema",
+            command: Command::Action(TanicAction::ToggleTableSchema),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('f'),
+            key_label: "f",
+            description: "Filter",
+            command: Command::OpenFilterForm,
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('F'),
+            key_label: "F",
+            description: "Clear filter",
+            command: Command::Action(TanicAction::FilterPreview {
+                columns: Vec::new(),
+                filter: None,
+            }),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('e'),
+            key_label: "e",
+            description: "Explain scan",
+            command: Command::Action(TanicAction::ExplainScan),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('p'),
+            key_label: "p",
+            description: "Profile columns",
+            command: Command::Action(TanicAction::ProfileSelectedTable),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('d'),
+            key_label: "d",
+            description: "Delete files",
+            command: Command::Action(TanicAction::InspectSelectedTableDeletes),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('h'),
+            key_label: "h",
+            description: "Table health",
+            command: Command::Action(TanicAction::CheckSelectedTableHealth),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('t'),
+            key_label: "t",
+            description: "Time travel",
+            command: Command::OpenTimeTravelForm,
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('T'),
+            key_label: "T",
+            description: "Back to the present",
+            command: Command::Action(TanicAction::TimeTravel(None)),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('x'),
+            key_label: "x",
+            description: "Export table",
+            command: Command::OpenExportForm,
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('E'),
+            key_label: "E",
+            description: "Expire snapshots",
+            command: Command::OpenMaintenanceForm(MaintenanceKind::ExpireSnapshots),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('D'),
+            key_label: "D",
+            description: "Remove old metadata",
+            command: Command::OpenMaintenanceForm(MaintenanceKind::RemoveOldMetadata),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('O'),
+            key_label: "O",
+            description: "Delete orphan files",
+            command: Command::OpenMaintenanceForm(MaintenanceKind::DeleteOrphanFiles),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: false,
+        },
+    ];
+
+    fn block(&self, view_state: &ViewingTablePreviewState) -> Block<'static> {
+        let preview = &view_state.preview;
+
+        // the point in time stands out when it isn't the present
+        let point_in_time_style = if preview.request.as_of.is_some() {
+            self.theme.diff
+        } else {
+            self.theme.base
+        };
+
+        let summary = match view_state.mode {
+            TablePreviewMode::Data => {
+                let rows = match preview.rows.len() {
+                    PREVIEW_ROW_LIMIT => format!("first {PREVIEW_ROW_LIMIT} rows"),
+                    1 => "1 row".to_string(),
+                    count => format!("{count} rows"),
+                };
+                match &preview.files {
+                    Some(files) => format!(
+                        " {rows} · {} of {} files scanned, {} pruned ",
+                        files.scanned,
+                        files.total,
+                        files.pruned()
+                    ),
+                    None => format!(" {rows} "),
+                }
+            }
+            TablePreviewMode::Schema => format!(
+                " schema {}, {} fields ",
+                preview.schema_id,
+                preview.schema.len()
+            ),
+        };
+
+        // the columns and filter that the data was narrowed down to
+        let request = &preview.request;
+        let mut narrowed_to = vec![];
+        if !request.columns.is_empty() {
+            narrowed_to.push(request.columns.join(", "));
+        }
+        if let Some(filter) = &request.filter {
+            narrowed_to.push(format!("where {filter}"));
+        }
+        let narrowed_to = if narrowed_to.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", narrowed_to.join(" · "))
+        };
+
+        Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} ", preview.request.table.label()),
+                self.theme.title,
+            ))
+            .title(
+                Line::styled(
+                    format!(" {} ", preview.point_in_time()),
+                    point_in_time_style,
+                )
+                .right_aligned(),
+            )
+            .title_bottom(Line::from(vec![
+                Span::styled(
+                    match view_state.mode {
+                        TablePreviewMode::Data => " Data ",
+                        TablePreviewMode::Schema => " Schema ",
+                    },
+                    self.theme.title,
+                ),
+                Span::styled(narrowed_to, self.theme.diff),
+            ]))
+            .title_bottom(Line::styled(summary, self.theme.base).right_aligned())
+            .border_style(self.theme.base)
+    }
+
+    fn render_data(&self, view_state: &ViewingTablePreviewState, area: Rect, buf: &mut Buffer) {
+        let preview = &view_state.preview;
+        let block = self.block(view_state);
+
+        if preview.rows.is_empty() {
+            let message = match (&preview.rows_error, &preview.snapshot) {
+                (Some(error), _) => format!("The rows could not be read: {error}"),
+                (None, None) => "This table has no snapshots".to_string(),
+                (None, Some(_)) if preview.request.filter.is_some() => {
+                    "No rows match the filter".to_string()
+                }
+                (None, Some(_)) => "No rows in this snapshot".to_string(),
+            };
+            EmptyState::new(block, &message, self.theme).render(area, buf);
+            return;
+        }
+
+        let widths = preview
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                let widest_value = preview
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(idx))
+                    .map(|value| value.chars().count())
+                    .max()
+                    .unwrap_or_default();
+                let width = widest_value.max(column.chars().count());
+                Constraint::Length(width.min(MAX_COLUMN_WIDTH) as u16)
+            })
+            .collect::<Vec<_>>();
+
+        let header = Row::new(
+            preview
+                .columns
+                .iter()
+                .map(|column| Cell::from(Span::styled(column.clone(), self.theme.title))),
+        );
+        let rows = preview.rows.iter().map(|row| {
+            Row::new(
+                row.iter()
+                    .map(|value| Cell::from(Span::styled(value.clone(), self.theme.base))),
+            )
+        });
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .row_highlight_style(self.theme.tile_selected);
+
+        let mut table_state = TableState::default().with_selected(Some(view_state.scroll));
+        StatefulWidget::render(table, area, buf, &mut table_state);
+    }
+
+    fn render_schema(&self, view_state: &ViewingTablePreviewState, area: Rect, buf: &mut Buffer) {
+        let header = Row::new(
+            ["ID", "Column", "Type", "Required", "Doc"]
+                .map(|title| Cell::from(Span::styled(title, self.theme.title))),
+        );
+        let rows = view_state.preview.schema.iter().map(|field| {
+            Row::new(
+                [
+                    field.id.to_string(),
+                    field.name.clone(),
+                    field.field_type.clone(),
+                    if field.required { "yes" } else { "no" }.to_string(),
+                    field.doc.clone().unwrap_or_default(),
+                ]
+                .map(|value| Cell::from(Span::styled(value, self.theme.base))),
+            )
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Percentage(30),
+                Constraint::Percentage(25),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(self.block(view_state))
+        .row_highlight_style(self.theme.tile_selected);
+
+        let mut table_state = TableState::default().with_selected(Some(view_state.scroll));
+        StatefulWidget::render(table, area, buf, &mut table_state);
+    }
+}
+
+impl Widget for &TablePreviewView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingTablePreview(view_state) = self.state else {
+            return;
+        };
+
+        match view_state.mode {
+            TablePreviewMode::Data => self.render_data(view_state, area, buf),
+            TablePreviewMode::Schema => self.render_schema(view_state, area, buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme};
+    use tanic_svc::export::SnapshotSelector;
+    use tanic_svc::preview::{PreviewRequest, PreviewSnapshot, ScannedFiles, TablePreview};
+    use tanic_svc::report::SchemaField;
+
+    fn preview_state(mode: TablePreviewMode, as_of: Option<SnapshotSelector>) -> TanicAppState {
+        let field = |id, name: &str, field_type: &str, doc: Option<&str>| SchemaField {
+            id,
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+            required: id == 1,
+            doc: doc.map(str::to_string),
+        };
+
+        TanicAppState::ViewingTablePreview(ViewingTablePreviewState {
+            preview: TablePreview {
+                request: PreviewRequest {
+                    as_of,
+                    ..PreviewRequest::current(orders_table())
+                },
+                snapshot: Some(PreviewSnapshot {
+                    id: 42,
+                    committed_at: "2025-01-31 23:00:00 UTC".to_string(),
+                }),
+                schema_id: 1,
+                schema: vec![
+                    field(1, "id", "long", None),
+                    field(2, "customer", "string", Some("who placed the order")),
+                    field(3, "total", "decimal(10, 2)", None),
+                ],
+                columns: vec![
+                    "id".to_string(),
+                    "customer".to_string(),
+                    "total".to_string(),
+                ],
+                rows: vec![
+                    vec!["1".to_string(), "Ada".to_string(), "10.00".to_string()],
+                    vec!["2".to_string(), String::new(), "2.50".to_string()],
+                ],
+                rows_error: None,
+                files: None,
+            },
+            mode,
+            scroll: 0,
+        })
+    }
+
+    #[test]
+    fn renders_the_current_data() {
+        let state = preview_state(TablePreviewMode::Data, None);
+        let theme = theme();
+
+        let view = TablePreviewView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (80, 8)));
+    }
+
+    #[test]
+    fn renders_filtered_data_with_the_files_pruned() {
+        let mut state = preview_state(TablePreviewMode::Data, None);
+        if let TanicAppState::ViewingTablePreview(view_state) = &mut state {
+            let preview = &mut view_state.preview;
+            preview.request.columns = vec!["id".to_string(), "total".to_string()];
+            preview.request.filter = Some("total > 5".parse().unwrap());
+            preview.columns = preview.request.columns.clone();
+            preview.rows = vec![vec!["1".to_string(), "10.00".to_string()]];
+            preview.files = Some(ScannedFiles {
+                scanned: 3,
+                total: 12,
+            });
+        }
+        let theme = theme();
+
+        let view = TablePreviewView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (80, 6)));
+    }
+
+    #[test]
+    fn renders_the_schema_as_of_a_branch() {
+        let state = preview_state(
+            TablePreviewMode::Schema,
+            Some(SnapshotSelector::Ref("audit".to_string())),
+        );
+        let theme = theme();
+
+        let view = TablePreviewView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (80, 8)));
+    }
+}
@@ -0,0 +1,364 @@
+use crossterm::event::KeyCode;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+
+use tanic_svc::profile::{ColumnProfile, TableProfile};
+use tanic_svc::state::ViewingTableProfileState;
+use tanic_svc::{TanicAction, TanicAppState};
+
+use crate::keybindings::{Command, KeyBinding};
+use crate::theme::Theme;
+use crate::ui_components::empty_state::EmptyState;
+
+/// Shown in place of a count or bound that no file has metrics for
+const NO_STATS: &str = "–";
+
+/// Per-column statistics aggregated from a table's file metrics
+pub(crate) struct TableProfileView<'a> {
+    state: &'a TanicAppState,
+    theme: &'a Theme,
+}
+
+impl<'a> TableProfileView<'a> {
+    pub(crate) fn new(state: &'a TanicAppState, theme: &'a Theme) -> Self {
+        Self { state, theme }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Up,
+            key_label: "↑",
+            description: "Previous column",
+            command: Command::Action(TanicAction::FocusPrevProfileColumn),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Down,
+            key_label: "↓",
+            description: "Next column",
+            command: Command::Action(TanicAction::FocusNextProfileColumn),
+            in_footer: false,
+        },
+        KeyBinding {
+            key: KeyCode::Char('h'),
+            key_label: "h",
+            description: "Sample histogram",
+            command: Command::Action(TanicAction::SampleHistogram),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Char('r'),
+            key_label: "r",
+            description: "Refresh",
+            command: Command::Action(TanicAction::Refresh),
+            in_footer: false,
+        },
+    ];
+
+    fn block(&self, profile: &TableProfile) -> Block<'static> {
+        let snapshot = match profile.snapshot_id {
+            Some(id) => format!(
+                " snapshot {id} · {} data files · {} records ",
+                profile.data_files, profile.records
+            ),
+            None => " no snapshots ".to_string(),
+        };
+
+        let without_stats = profile.columns_without_stats().count();
+        let (stats_coverage, stats_style) = if without_stats == 0 {
+            (" every column has stats ".to_string(), self.theme.base)
+        } else {
+            (
+                format!(
+                    " {without_stats} of {} columns without stats ",
+                    profile.columns.len()
+                ),
+                self.theme.diff,
+            )
+        };
+
+        Block::bordered()
+            .title(Line::styled(
+                format!(" Tanic //// {} ", profile.request.table.label()),
+                self.theme.title,
+            ))
+            .title(Line::styled(snapshot, self.theme.base).right_aligned())
+            .title_bottom(Line::styled(" Column profile ", self.theme.title))
+            .title_bottom(Line::styled(stats_coverage, stats_style).right_aligned())
+            .border_style(self.theme.base)
+    }
+
+    fn row(&self, column: &ColumnProfile, data_files: usize) -> Row<'static> {
+        let counted = |value: u64| {
+            if column.files_with_counts > 0 {
+                value.to_string()
+            } else {
+                NO_STATS.to_string()
+            }
+        };
+        let null_ratio = column
+            .null_ratio()
+            .map(|ratio| format!("{:.1}%", ratio * 100.0))
+            .unwrap_or_else(|| NO_STATS.to_string());
+        let files = if column.has_stats() {
+            Span::styled(
+                format!(
+                    "{}/{data_files}",
+                    column.files_with_counts.max(column.files_with_bounds)
+                ),
+                self.theme.base,
+            )
+        } else {
+            Span::styled("no stats", self.theme.diff)
+        };
+
+        Row::new(vec![
+            Cell::from(Span::styled(column.name.clone(), self.theme.title)),
+            Cell::from(Span::styled(column.field_type.clone(), self.theme.base)),
+            Cell::from(Span::styled(counted(column.values), self.theme.base)),
+            Cell::from(Span::styled(counted(column.nulls), self.theme.base)),
+            Cell::from(Span::styled(null_ratio, self.theme.base)),
+            Cell::from(Span::styled(
+                column
+                    .nans
+                    .map(|nans| nans.to_string())
+                    .unwrap_or_else(|| NO_STATS.to_string()),
+                self.theme.base,
+            )),
+            Cell::from(Span::styled(
+                column.min.clone().unwrap_or_else(|| NO_STATS.to_string()),
+                self.theme.base,
+            )),
+            Cell::from(Span::styled(
+                column.max.clone().unwrap_or_else(|| NO_STATS.to_string()),
+                self.theme.base,
+            )),
+            Cell::from(files),
+        ])
+    }
+
+    fn render_columns(&self, view_state: &ViewingTableProfileState, area: Rect, buf: &mut Buffer) {
+        let profile = &view_state.profile;
+
+        let header = Row::new(
+            [
+                "Column", "Type", "Values", "Nulls", "Null %", "NaNs", "Min", "Max", "Files",
+            ]
+            .map(|title| Cell::from(Span::styled(title, self.theme.title))),
+        );
+        let rows = profile
+            .columns
+            .iter()
+            .map(|column| self.row(column, profile.data_files));
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(6),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .block(self.block(profile))
+        .row_highlight_style(self.theme.tile_selected);
+
+        let mut table_state = TableState::default().with_selected(Some(view_state.selected_idx));
+        StatefulWidget::render(table, area, buf, &mut table_state);
+    }
+
+    /// The histogram sampled for the profile, or why it couldn't be
+    fn histogram_lines(&self, profile: &TableProfile, width: u16) -> Option<Vec<Line<'static>>> {
+        if let Some(error) = &profile.histogram_error {
+            return Some(vec![Line::styled(
+                format!("The histogram could not be sampled: {error}"),
+                self.theme.error,
+            )]);
+        }
+        let histogram = profile.histogram.as_ref()?;
+
+        if histogram.buckets.is_empty() {
+            let message = if histogram.sampled_rows == 0 {
+                "No rows to sample"
+            } else {
+                "Every sampled value is null"
+            };
+            return Some(vec![Line::styled(message, self.theme.base)]);
+        }
+
+        let label_width = histogram
+            .buckets
+            .iter()
+            .map(|bucket| bucket.label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let most = histogram
+            .buckets
+            .iter()
+            .map(|bucket| bucket.count)
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        let count_width = most.to_string().len();
+        let bar_width = (width as usize).saturating_sub(label_width + count_width + 4);
+
+        Some(
+            histogram
+                .buckets
+                .iter()
+                .map(|bucket| {
+                    let bar = bucket.count * bar_width / most;
+                    Line::from(vec![
+                        Span::styled(format!("{:label_width$}  ", bucket.label), self.theme.base),
+                        Span::styled(format!("{:>count_width$} ", bucket.count), self.theme.base),
+                        Span::styled("█".repeat(bar), self.theme.key_hint),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    fn histogram_block(&self, profile: &TableProfile) -> Block<'static> {
+        let title = match (&profile.histogram, &profile.request.histogram) {
+            (Some(histogram), _) => format!(
+                " Histogram of {}, sampled from {} rows, {} null ",
+                histogram.column, histogram.sampled_rows, histogram.nulls
+            ),
+            (None, Some(column)) => format!(" Histogram of {column} "),
+            (None, None) => " Histogram ".to_string(),
+        };
+
+        Block::bordered()
+            .title(Line::styled(title, self.theme.title))
+            .border_style(self.theme.base)
+    }
+}
+
+impl Widget for &TableProfileView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let TanicAppState::ViewingTableProfile(view_state) = self.state else {
+            return;
+        };
+        let profile = &view_state.profile;
+
+        if profile.snapshot_id.is_none() {
+            EmptyState::new(
+                self.block(profile),
+                "This table has no snapshots to profile",
+                self.theme,
+            )
+            .render(area, buf);
+            return;
+        }
+
+        let histogram_block = self.histogram_block(profile);
+        let Some(histogram) = self.histogram_lines(profile, histogram_block.inner(area).width)
+        else {
+            self.render_columns(view_state, area, buf);
+            return;
+        };
+
+        let [columns_area, histogram_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(histogram.len() as u16 + 2),
+        ])
+        .areas(area);
+
+        self.render_columns(view_state, columns_area, buf);
+        Paragraph::new(histogram)
+            .block(histogram_block)
+            .render(histogram_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{orders_table, render, theme};
+    use tanic_svc::profile::{Histogram, HistogramBucket, ProfileRequest};
+
+    fn column(
+        name: &str,
+        field_type: &str,
+        nulls: u64,
+        bounds: Option<(&str, &str)>,
+    ) -> ColumnProfile {
+        ColumnProfile {
+            field_id: 0,
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+            files_with_counts: 3,
+            files_with_bounds: if bounds.is_some() { 3 } else { 0 },
+            values: 300,
+            nulls,
+            nans: None,
+            min: bounds.map(|(min, _)| min.to_string()),
+            max: bounds.map(|(_, max)| max.to_string()),
+        }
+    }
+
+    fn profile_state(histogram: Option<Histogram>) -> TanicAppState {
+        let notes = ColumnProfile {
+            files_with_counts: 0,
+            values: 0,
+            ..column("notes", "string", 0, None)
+        };
+
+        TanicAppState::ViewingTableProfile(ViewingTableProfileState {
+            profile: TableProfile {
+                request: ProfileRequest {
+                    table: orders_table(),
+                    histogram: histogram.as_ref().map(|histogram| histogram.column.clone()),
+                },
+                snapshot_id: Some(42),
+                data_files: 3,
+                records: 300,
+                columns: vec![
+                    column("id", "long", 0, Some(("1", "300"))),
+                    column("country", "string", 30, Some(("DE", "US"))),
+                    ColumnProfile {
+                        nans: Some(2),
+                        ..column("total", "double", 3, Some(("0.5", "99.95")))
+                    },
+                    notes,
+                ],
+                histogram,
+                histogram_error: None,
+            },
+            selected_idx: 1,
+        })
+    }
+
+    #[test]
+    fn renders_the_column_profile() {
+        let state = profile_state(None);
+        let theme = theme();
+
+        let view = TableProfileView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (100, 9)));
+    }
+
+    #[test]
+    fn renders_a_sampled_histogram() {
+        let bucket = |label: &str, count| HistogramBucket {
+            label: label.to_string(),
+            count,
+        };
+        let state = profile_state(Some(Histogram {
+            column: "country".to_string(),
+            sampled_rows: 300,
+            nulls: 30,
+            buckets: vec![bucket("GB", 160), bucket("US", 80), bucket("DE", 30)],
+        }));
+        let theme = theme();
+
+        let view = TableProfileView::new(&state, &theme);
+        insta::assert_snapshot!(render(&view, (100, 14)));
+    }
+}
@@ -0,0 +1,209 @@
+//! Forms of text fields, typed into one field at a time, shown in a popup
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Clear, Padding, Paragraph};
+
+use crate::theme::Theme;
+
+/// The key bindings of a form of text fields: `$submit` describes what
+/// Enter does, and `$command` wraps the form's `FormCommand`s
+macro_rules! text_form_key_bindings {
+    ($command:path, $submit:literal) => {
+        &[
+            $crate::keybindings::KeyBinding {
+                key: ::crossterm::event::KeyCode::Enter,
+                key_label: "Enter",
+                description: $submit,
+                command: $command($crate::keybindings::FormCommand::Submit),
+                in_footer: true,
+            },
+            $crate::keybindings::KeyBinding {
+                key: ::crossterm::event::KeyCode::Esc,
+                key_label: "Esc",
+                description: "Cancel",
+                command: $command($crate::keybindings::FormCommand::Close),
+                in_footer: true,
+            },
+            $crate::keybindings::KeyBinding {
+                key: ::crossterm::event::KeyCode::Tab,
+                key_label: "Tab",
+                description: "Next field",
+                command: $command($crate::keybindings::FormCommand::NextField),
+                in_footer: true,
+            },
+            $crate::keybindings::KeyBinding {
+                key: ::crossterm::event::KeyCode::Down,
+                key_label: "↓",
+                description: "Next field",
+                command: $command($crate::keybindings::FormCommand::NextField),
+                in_footer: false,
+            },
+            $crate::keybindings::KeyBinding {
+                key: ::crossterm::event::KeyCode::BackTab,
+                key_label: "Shift+Tab",
+                description: "Previous field",
+                command: $command($crate::keybindings::FormCommand::PrevField),
+                in_footer: false,
+            },
+            $crate::keybindings::KeyBinding {
+                key: ::crossterm::event::KeyCode::Up,
+                key_label: "↑",
+                description: "Previous field",
+                command: $command($crate::keybindings::FormCommand::PrevField),
+                in_footer: false,
+            },
+        ]
+    };
+}
+pub(crate) use text_form_key_bindings;
+
+/// Label and hint of a field
+pub(crate) type Field = (&'static str, &'static str);
+
+/// The text of each of a form's fields, and which one is being typed into
+#[derive(Clone, Debug)]
+pub(crate) struct TextFields {
+    fields: &'static [Field],
+
+    /// the text of each field, in `fields` order
+    values: Vec<String>,
+    focused: usize,
+
+    /// why the form could not be submitted
+    pub(crate) error: Option<String>,
+}
+
+impl TextFields {
+    /// Blank fields, with the first one focused
+    pub(crate) fn new(fields: &'static [Field]) -> Self {
+        Self {
+            fields,
+            values: vec![String::new(); fields.len()],
+            focused: 0,
+            error: None,
+        }
+    }
+
+    pub(crate) fn value(&self, idx: usize) -> &str {
+        &self.values[idx]
+    }
+
+    pub(crate) fn set_value(&mut self, idx: usize, value: impl Into<String>) {
+        self.values[idx] = value.into();
+    }
+
+    pub(crate) fn focus(&mut self, idx: usize) {
+        self.focused = idx;
+    }
+
+    pub(crate) fn next_field(&mut self) {
+        self.focused = (self.focused + 1) % self.values.len();
+    }
+
+    pub(crate) fn prev_field(&mut self) {
+        self.focused = (self.focused + self.values.len() - 1) % self.values.len();
+    }
+
+    /// Type into the focused field
+    pub(crate) fn edit(&mut self, key_event: KeyEvent) {
+        let value = &mut self.values[self.focused];
+
+        match key_event.code {
+            KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                value.push(c)
+            }
+            KeyCode::Backspace => {
+                value.pop();
+            }
+            _ => return,
+        }
+        self.error = None;
+    }
+
+    /// The outcome of submitting the form. If it failed, the reason is
+    /// shown in the form.
+    pub(crate) fn submit<T>(&mut self, outcome: Result<T, String>) -> Option<T> {
+        match outcome {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+
+    /// A line for each field and its hint, with the focused field marked
+    pub(crate) fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let label_width = self
+            .fields
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = vec![];
+        for (idx, ((label, hint), value)) in self.fields.iter().zip(&self.values).enumerate() {
+            let focused = idx == self.focused;
+            let (marker, value_style) = if focused {
+                ("> ", theme.title)
+            } else {
+                ("  ", theme.base)
+            };
+            let cursor = if focused { "_" } else { "" };
+
+            lines.push(Line::from(vec![
+                Span::styled(marker, theme.key_hint),
+                Span::styled(format!("{label:label_width$}  "), theme.base),
+                Span::styled(format!("{value}{cursor}"), value_style),
+            ]));
+            lines.push(Line::styled(
+                format!("{:indent$}{hint}", "", indent = label_width + 4),
+                theme.log,
+            ));
+        }
+
+        lines
+    }
+
+    /// The reason the form could not be submitted, if any, to show under
+    /// its fields
+    pub(crate) fn error_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        match &self.error {
+            Some(error) => vec![Line::default(), Line::styled(error.clone(), theme.error)],
+            None => vec![],
+        }
+    }
+}
+
+/// Render a form's lines in a popup of the given width, centred in `area`
+pub(crate) fn render_popup(
+    title: String,
+    lines: Vec<Line>,
+    width: u16,
+    theme: &Theme,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    // borders and padding
+    let width = width.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+
+    let [popup] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(popup);
+
+    let block = Block::bordered()
+        .title(Line::styled(title, theme.title).centered())
+        .border_set(border::THICK)
+        .border_style(theme.base)
+        .padding(Padding::horizontal(1));
+
+    Clear.render(popup, buf);
+    Paragraph::new(lines).block(block).render(popup, buf);
+}
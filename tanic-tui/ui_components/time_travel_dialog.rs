@@ -0,0 +1,150 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Clear, Padding, Paragraph};
+
+use tanic_svc::export::SnapshotSelector;
+
+use crate::keybindings::{Command, FormCommand, KeyBinding};
+use crate::theme::Theme;
+
+const HINTS: [&str; 3] = [
+    "snapshot ID, branch or tag, e.g. 7034892178402364, main",
+    "or a UTC time, e.g. 2025-01-31 or 2025-01-31 23:00",
+    "or blank for the current snapshot",
+];
+
+/// The point in time to read the table in view as of
+#[derive(Clone, Debug)]
+pub(crate) struct TimeTravelForm {
+    /// the table, for the dialog's title
+    table_name: String,
+
+    value: String,
+}
+
+impl TimeTravelForm {
+    /// Start from the point in time that the table is currently read as of
+    pub(crate) fn new(table_name: String, as_of: Option<&SnapshotSelector>) -> Self {
+        Self {
+            table_name,
+            value: as_of.map(ToString::to_string).unwrap_or_default(),
+        }
+    }
+
+    pub(crate) const KEY_BINDINGS: &'static [KeyBinding] = &[
+        KeyBinding {
+            key: KeyCode::Enter,
+            key_label: "Enter",
+            description: "Time travel",
+            command: Command::TimeTravelForm(FormCommand::Submit),
+            in_footer: true,
+        },
+        KeyBinding {
+            key: KeyCode::Esc,
+            key_label: "Esc",
+            description: "Cancel",
+            command: Command::TimeTravelForm(FormCommand::Close),
+            in_footer: true,
+        },
+    ];
+
+    pub(crate) fn edit(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.value.push(c)
+            }
+            KeyCode::Backspace => {
+                self.value.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// The point in time to read the table as of, or `None` for now
+    pub(crate) fn submit(&self) -> Option<SnapshotSelector> {
+        self.value.parse().ok()
+    }
+}
+
+/// Popup for choosing a point in time
+pub(crate) struct TimeTravelDialog<'a> {
+    form: &'a TimeTravelForm,
+    theme: &'a Theme,
+}
+
+impl<'a> TimeTravelDialog<'a> {
+    pub(crate) fn new(form: &'a TimeTravelForm, theme: &'a Theme) -> Self {
+        Self { form, theme }
+    }
+}
+
+impl Widget for &TimeTravelDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", self.theme.key_hint),
+            Span::styled("As of  ", self.theme.base),
+            Span::styled(format!("{}_", self.form.value), self.theme.title),
+        ])];
+        lines.extend(
+            HINTS
+                .iter()
+                .map(|hint| Line::styled(format!("         {hint}"), self.theme.log)),
+        );
+
+        // borders and padding
+        let width = 68.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+
+        let [popup] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        let block = Block::bordered()
+            .title(
+                Line::styled(
+                    format!(" Time travel {} ", self.form.table_name),
+                    self.theme.title,
+                )
+                .centered(),
+            )
+            .border_set(border::THICK)
+            .border_style(self.theme.base)
+            .padding(Padding::horizontal(1));
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines).block(block).render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{render, theme, type_text};
+
+    #[test]
+    fn submits_a_point_in_time() {
+        let mut form = TimeTravelForm::new("orders".to_string(), None);
+        assert_eq!(form.submit(), None);
+
+        type_text(&mut form, "2025-01-31");
+        assert_eq!(
+            form.submit(),
+            Some(SnapshotSelector::AsOf(1_738_281_600_000))
+        );
+    }
+
+    #[test]
+    fn starts_from_the_current_point_in_time() {
+        let mut form = TimeTravelForm::new("orders".to_string(), Some(&SnapshotSelector::Id(42)));
+        form.edit(KeyEvent::from(KeyCode::Backspace));
+        type_text(&mut form, "3");
+
+        assert_eq!(form.submit(), Some(SnapshotSelector::Id(43)));
+        insta::assert_snapshot!(render(&TimeTravelDialog::new(&form, &theme()), (80, 12)));
+    }
+}
@@ -0,0 +1,224 @@
+//! UI-only state
+//!
+//! State that only affects presentation, and so is kept by the TUI rather
+//! than being sent through the app state.
+
+use tanic_svc::{TanicAction, TanicAppState, TanicTabsState};
+use tui_logger::{LevelFilter, TuiWidgetState};
+
+use crate::keybindings::{Command, FormCommand};
+use crate::ui_components::confirm_dialog::ConfirmForm;
+use crate::ui_components::export_dialog::ExportForm;
+use crate::ui_components::filter_dialog::FilterForm;
+use crate::ui_components::maintenance_dialog::MaintenanceForm;
+use crate::ui_components::time_travel_dialog::TimeTravelForm;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum LogPanelMode {
+    #[default]
+    Normal,
+    Hidden,
+    Maximised,
+}
+
+pub(crate) struct UiState {
+    /// whether the key binding help overlay is open
+    pub(crate) show_help: bool,
+
+    pub(crate) log_panel_mode: LogPanelMode,
+
+    /// kept across frames so that level filters and scroll position persist
+    pub(crate) log_widget_state: TuiWidgetState,
+
+    /// animation frame counter, advanced while anything is animated
+    pub(crate) tick: usize,
+
+    /// the export form, while it is open
+    pub(crate) export_form: Option<ExportForm>,
+
+    /// the time travel form, while it is open
+    pub(crate) time_travel_form: Option<TimeTravelForm>,
+
+    /// the filter form, while it is open
+    pub(crate) filter_form: Option<FilterForm>,
+
+    /// the maintenance form, while it is open
+    pub(crate) maintenance_form: Option<MaintenanceForm>,
+
+    /// the confirmation of a maintenance plan, while it is open
+    pub(crate) confirm_form: Option<ConfirmForm>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            show_help: false,
+            log_panel_mode: LogPanelMode::default(),
+            log_widget_state: TuiWidgetState::new()
+                .set_default_display_level(LevelFilter::Info)
+                .set_level_for_target("tanic_svc", LevelFilter::Debug),
+            tick: 0,
+            export_form: None,
+            time_travel_form: None,
+            filter_form: None,
+            maintenance_form: None,
+            confirm_form: None,
+        }
+    }
+}
+
+impl UiState {
+    /// Apply a UI-only command, returning any action that it results in.
+    /// Commands that dispatch actions themselves are ignored.
+    pub(crate) fn handle_command(
+        &mut self,
+        command: Command,
+        tabs_state: &TanicTabsState,
+    ) -> Option<TanicAction> {
+        match command {
+            Command::Action(_) => {}
+
+            Command::ToggleHelp => self.show_help = !self.show_help,
+
+            Command::ToggleLogPanel => {
+                self.log_panel_mode = match self.log_panel_mode {
+                    LogPanelMode::Hidden => LogPanelMode::Normal,
+                    LogPanelMode::Normal | LogPanelMode::Maximised => LogPanelMode::Hidden,
+                }
+            }
+
+            Command::ToggleLogPanelMaximised => {
+                self.log_panel_mode = match self.log_panel_mode {
+                    LogPanelMode::Maximised => LogPanelMode::Normal,
+                    LogPanelMode::Normal | LogPanelMode::Hidden => LogPanelMode::Maximised,
+                }
+            }
+
+            Command::LogPanel(event) => self.log_widget_state.transition(event),
+
+            Command::OpenExportForm => {
+                // a preview is exported as it is shown
+                let state = tabs_state.active_tab().map(|tab| &tab.state);
+                self.export_form = match state {
+                    Some(TanicAppState::ViewingTablePreview(view_state)) => {
+                        Some(ExportForm::for_preview(&view_state.preview.request))
+                    }
+                    _ => tabs_state.selected_table().map(ExportForm::new),
+                };
+            }
+
+            Command::ExportForm(command) => {
+                let form = self.export_form.as_mut()?;
+
+                match command {
+                    FormCommand::NextField => form.next_field(),
+                    FormCommand::PrevField => form.prev_field(),
+                    FormCommand::Edit(key_event) => form.edit(key_event),
+                    FormCommand::Submit => {
+                        let request = form.submit()?;
+                        self.export_form = None;
+                        return Some(TanicAction::ExportTable(Box::new(request)));
+                    }
+                    FormCommand::Close => self.export_form = None,
+                }
+            }
+
+            Command::OpenTimeTravelForm => {
+                let state = tabs_state.active_tab().map(|tab| &tab.state);
+                if let Some(TanicAppState::ViewingTablePreview(view_state)) = state {
+                    let request = &view_state.preview.request;
+                    self.time_travel_form = Some(TimeTravelForm::new(
+                        request.table.name.clone(),
+                        request.as_of.as_ref(),
+                    ));
+                }
+            }
+
+            Command::TimeTravelForm(command) => {
+                let form = self.time_travel_form.as_mut()?;
+
+                match command {
+                    FormCommand::Edit(key_event) => form.edit(key_event),
+                    FormCommand::Submit => {
+                        let as_of = form.submit();
+                        self.time_travel_form = None;
+                        return Some(TanicAction::TimeTravel(as_of));
+                    }
+                    FormCommand::Close => self.time_travel_form = None,
+                    FormCommand::NextField | FormCommand::PrevField => {}
+                }
+            }
+
+            Command::OpenFilterForm => {
+                let state = tabs_state.active_tab().map(|tab| &tab.state);
+                if let Some(TanicAppState::ViewingTablePreview(view_state)) = state {
+                    self.filter_form = Some(FilterForm::new(&view_state.preview));
+                }
+            }
+
+            Command::FilterForm(command) => {
+                let form = self.filter_form.as_mut()?;
+
+                match command {
+                    FormCommand::NextField => form.next_field(),
+                    FormCommand::PrevField => form.prev_field(),
+                    FormCommand::Edit(key_event) => form.edit(key_event),
+                    FormCommand::Submit => {
+                        let action = form.submit()?;
+                        self.filter_form = None;
+                        return Some(action);
+                    }
+                    FormCommand::Close => self.filter_form = None,
+                }
+            }
+
+            Command::OpenMaintenanceForm(kind) => {
+                self.maintenance_form = tabs_state
+                    .selected_table()
+                    .map(|table| MaintenanceForm::new(table, kind));
+            }
+
+            Command::MaintenanceForm(command) => {
+                let form = self.maintenance_form.as_mut()?;
+
+                match command {
+                    FormCommand::NextField => form.next_field(),
+                    FormCommand::PrevField => form.prev_field(),
+                    FormCommand::Edit(key_event) => form.edit(key_event),
+                    FormCommand::Submit => {
+                        let request = form.submit()?;
+                        self.maintenance_form = None;
+                        return Some(TanicAction::PlanMaintenance(Box::new(request)));
+                    }
+                    FormCommand::Close => self.maintenance_form = None,
+                }
+            }
+
+            Command::OpenConfirmForm => {
+                let state = tabs_state.active_tab().map(|tab| &tab.state);
+                if let Some(TanicAppState::ViewingMaintenancePlan(view_state)) = state {
+                    if view_state.is_applicable() {
+                        self.confirm_form = Some(ConfirmForm::new(&view_state.plan));
+                    }
+                }
+            }
+
+            Command::ConfirmForm(command) => {
+                let form = self.confirm_form.as_mut()?;
+
+                match command {
+                    FormCommand::Edit(key_event) => form.edit(key_event),
+                    FormCommand::Submit => {
+                        let confirmation = form.submit()?;
+                        self.confirm_form = None;
+                        return Some(TanicAction::ApplyMaintenance(confirmation));
+                    }
+                    FormCommand::Close => self.confirm_form = None,
+                    FormCommand::NextField | FormCommand::PrevField => {}
+                }
+            }
+        }
+
+        None
+    }
+}
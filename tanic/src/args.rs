@@ -1,9 +1,91 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use http::Uri;
+use std::path::PathBuf;
+use tanic_core::config::IconSet;
+use tanic_svc::export::{ExportFormat, SnapshotSelector};
+use tanic_svc::filter::RowFilter;
+use tanic_svc::report::ReportFormat;
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 pub struct Args {
-    /// URI of an Iceberg Catalog to connect to
-    pub catalogue_uri: Option<Uri>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// URIs of Iceberg Catalogs to connect to, each in its own tab
+    pub catalogue_uris: Vec<Uri>,
+
+    /// Icon set to use: nerd-font, unicode or ascii. Overrides the config file
+    #[arg(long)]
+    pub icons: Option<IconSet>,
+
+    /// Record every key event and action to a file, to reproduce the session with --replay
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay a recorded session without a catalog or terminal, printing the frames drawn
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Export a table's data to a local CSV, JSON Lines or Parquet file
+    Export(ExportArgs),
+
+    /// Write a metadata report on a table, or on every table in a namespace
+    Report(ReportArgs),
+}
+
+#[derive(clap::Args)]
+pub struct ExportArgs {
+    /// URI of the Iceberg Catalog that the table is in
+    pub catalogue_uri: Uri,
+
+    /// The table to export, as <namespace>.<table>
+    pub table: String,
+
+    /// File to write to
+    pub output: PathBuf,
+
+    /// File format: csv, ndjson or parquet. Defaults to the output file's extension
+    #[arg(long)]
+    pub format: Option<ExportFormat>,
+
+    /// Columns to export, separated by commas. Defaults to every column
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// Only export rows matching this filter, such as "country = 'GB' AND total > 100"
+    #[arg(long)]
+    pub filter: Option<RowFilter>,
+
+    /// Maximum number of rows to export
+    #[arg(long)]
+    pub limit: Option<u64>,
+
+    /// Snapshot ID, branch or tag to read from. Defaults to the current snapshot
+    #[arg(long)]
+    pub snapshot: Option<SnapshotSelector>,
+}
+
+#[derive(clap::Args)]
+pub struct ReportArgs {
+    /// URI of the Iceberg Catalog that the table or namespace is in
+    pub catalogue_uri: Uri,
+
+    /// The table to report on, as <namespace>.<table>, or the namespace with --namespace
+    pub target: String,
+
+    /// Report on every table in the namespace given as the target
+    #[arg(long)]
+    pub namespace: bool,
+
+    /// File to write to. Defaults to standard output
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Report format: markdown or json. Defaults to the output file's extension, or markdown
+    #[arg(long)]
+    pub format: Option<ReportFormat>,
 }
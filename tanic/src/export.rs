@@ -0,0 +1,66 @@
+//! Table Export
+//!
+//! `tanic export` writes a table's data to a local file without starting
+//! the UI, for sharing samples with people who don't have catalog access.
+
+use iceberg::TableIdent;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use std::io::Write;
+
+use tanic_core::config::ConnectionDetails;
+use tanic_core::TanicConfig;
+use tanic_svc::export::{self, ExportFormat, ExportOptions};
+use tanic_svc::iceberg_context::{CatalogConnector, RestCatalogConnector};
+
+use crate::args::ExportArgs;
+
+pub(crate) async fn run(args: ExportArgs, config: &TanicConfig) -> Result<()> {
+    let format = match args.format {
+        Some(format) => format,
+        None => ExportFormat::from_path(&args.output).ok_or_else(|| {
+            miette!(
+                "can't tell the format of {} from its extension; use --format",
+                args.output.display()
+            )
+        })?,
+    };
+
+    let table_ident = TableIdent::from_strs(args.table.split('.'))
+        .into_diagnostic()
+        .wrap_err_with(|| format!("'{}' is not a <namespace>.<table> name", args.table))?;
+
+    let catalog = RestCatalogConnector.connect(&ConnectionDetails::new_anon(args.catalogue_uri));
+    let table = tokio::time::timeout(config.requests.timeout(), catalog.load_table(&table_ident))
+        .await
+        .map_err(|_| miette!("timed out loading {}", args.table))?
+        .into_diagnostic()
+        .wrap_err_with(|| format!("could not load {}", args.table))?;
+
+    let options = ExportOptions {
+        format,
+        columns: args.columns,
+        filter: args.filter,
+        limit: args.limit,
+        snapshot: args.snapshot,
+    };
+    let rows_expected = export::expected_rows(&table, &options);
+
+    let rows_written = export::export_table(&table, &args.output, &options, |rows_written| {
+        let mut stderr = std::io::stderr();
+        let _ = match rows_expected {
+            Some(expected) => write!(stderr, "\rexported {rows_written}/{expected} rows"),
+            None => write!(stderr, "\rexported {rows_written} rows"),
+        };
+    })
+    .await
+    .into_diagnostic()
+    .wrap_err_with(|| format!("could not export {}", args.table))?;
+
+    eprintln!(
+        "\rexported {rows_written} rows of {} to {}",
+        args.table,
+        args.output.display()
+    );
+
+    Ok(())
+}
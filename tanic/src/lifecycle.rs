@@ -1 +1,261 @@
+//! Process Lifecycle
+//!
+//! Tanic runs as three tasks: the app state, the UI and the Iceberg context.
+//! Whichever way the process ends - the user quitting, a task failing or
+//! panicking, or a termination signal - the other tasks are asked to stop,
+//! given a moment to do so, and the terminal is put back the way it was
+//! found before any error is printed. Maintenance being applied gets longer
+//! to finish, since stopping partway would leave a table half cleaned up.
 
+use miette::{miette, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::watch::Receiver as WatchReceiver;
+use tokio::task::{Id as TaskId, JoinError, JoinSet};
+
+use tanic_core::error::panic_message;
+use tanic_svc::maintenance::MaintenancePlan;
+use tanic_svc::{TanicAction, TanicAppState, TanicTabsState};
+
+/// How long tasks get to stop on their own before they are aborted
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How much longer tasks get to stop while maintenance is being applied
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Log panics, and report them straight away unless the TUI is running.
+///
+/// The app survives panics in request workers, which fail their requests,
+/// so a panic mustn't restore the terminal while the TUI is still drawing
+/// to it. A panic in one of the app's tasks is reported instead once the
+/// terminal has been restored on shutdown.
+pub(crate) fn install_panic_hook() {
+    miette::set_panic_hook();
+    let report_panic = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!(%info, "panicked");
+
+        // a report would be lost in the alternate screen
+        if !crossterm::terminal::is_raw_mode_enabled().unwrap_or(false) {
+            report_panic(info);
+        }
+    }));
+}
+
+/// The app's long-running tasks
+#[derive(Default)]
+pub(crate) struct Tasks {
+    tasks: JoinSet<tanic_core::Result<()>>,
+    names: HashMap<TaskId, &'static str>,
+}
+
+impl Tasks {
+    pub(crate) fn spawn(
+        &mut self,
+        name: &'static str,
+        task: impl Future<Output = tanic_core::Result<()>> + Send + 'static,
+    ) {
+        let handle = self.tasks.spawn(task);
+        self.names.insert(handle.id(), name);
+    }
+
+    /// Run until a task ends or a termination signal arrives, then shut
+    /// down the rest. Fails with the first task's failure, if any.
+    pub(crate) async fn run(
+        mut self,
+        action_tx: UnboundedSender<TanicAction>,
+        state_rx: WatchReceiver<TanicTabsState>,
+    ) -> Result<()> {
+        let mut failure = tokio::select! {
+            Some(joined) = self.tasks.join_next_with_id() => self.failure(joined),
+            signal = termination_signal() => {
+                tracing::info!(signal, "shutting down");
+                None
+            }
+        };
+
+        // the app state stops on exit, once any maintenance being applied
+        // has finished, which stops the UI, and the Iceberg context stops
+        // once the app state's commands stop
+        let _ = action_tx.send(TanicAction::Exit);
+
+        let mut stopped = self.join_within(SHUTDOWN_TIMEOUT, &mut failure).await;
+        let applying = !being_applied(&state_rx.borrow()).is_empty();
+        if !stopped && applying {
+            tracing::info!("waiting for maintenance being applied to finish");
+            stopped = self.join_within(DRAIN_TIMEOUT, &mut failure).await;
+        }
+
+        if !stopped {
+            for plan in being_applied(&state_rx.borrow()) {
+                tracing::error!(
+                    table = plan.request.table.label(),
+                    operation = %plan.request.operation,
+                    "interrupted maintenance being applied"
+                );
+                failure = failure.or_else(|| {
+                    Some(miette!(
+                        "interrupted applying maintenance to {}: {}. Run a dry run \
+                         to see what is left to remove.",
+                        plan.request.table.label(),
+                        plan.request.operation
+                    ))
+                });
+            }
+            tracing::warn!(
+                remaining = self.tasks.len(),
+                "tasks did not stop in time; aborting them"
+            );
+            self.tasks.shutdown().await;
+        }
+
+        // an aborted or panicked UI doesn't get to restore the terminal itself
+        ratatui::restore();
+
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Wait for every task to stop, keeping the first failure. Returns
+    /// whether they all stopped in time.
+    async fn join_within(
+        &mut self,
+        timeout: Duration,
+        failure: &mut Option<miette::Report>,
+    ) -> bool {
+        let deadline = tokio::time::sleep(timeout);
+        tokio::pin!(deadline);
+
+        loop {
+            tokio::select! {
+                joined = self.tasks.join_next_with_id() => match joined {
+                    Some(joined) => {
+                        let task_failure = self.failure(joined);
+                        *failure = failure.take().or(task_failure);
+                    }
+                    None => return true,
+                },
+                () = &mut deadline => return false,
+            }
+        }
+    }
+
+    fn failure(
+        &self,
+        joined: std::result::Result<(TaskId, tanic_core::Result<()>), JoinError>,
+    ) -> Option<miette::Report> {
+        match joined {
+            Ok((id, Ok(()))) => {
+                tracing::info!(task = self.name(id), "task stopped");
+                None
+            }
+            Ok((id, Err(err))) => {
+                let name = self.name(id);
+                tracing::error!(task = name, %err, "task failed");
+                Some(miette!("the {name} task failed: {err}"))
+            }
+            Err(err) if err.is_panic() => {
+                let name = self.name(err.id());
+                let payload = err.into_panic();
+                Some(miette!(
+                    "the {name} task panicked: {}",
+                    panic_message(&*payload)
+                ))
+            }
+            Err(_) => None,
+        }
+    }
+
+    fn name(&self, id: TaskId) -> &'static str {
+        self.names.get(&id).copied().unwrap_or("unknown")
+    }
+}
+
+/// The maintenance plans that tabs are waiting to finish applying
+fn being_applied(state: &TanicTabsState) -> Vec<MaintenancePlan> {
+    state
+        .tabs
+        .iter()
+        .filter_map(|tab| match &tab.state {
+            TanicAppState::ApplyingMaintenance(plan) => Some(plan.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Wait for a signal asking the process to stop, and name it. Never
+/// returns if signals can't be listened for.
+#[cfg(unix)]
+async fn termination_signal() -> &'static str {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let signals = [
+        (SignalKind::terminate(), "SIGTERM"),
+        (SignalKind::hangup(), "SIGHUP"),
+        (SignalKind::interrupt(), "SIGINT"),
+    ];
+
+    let mut listeners = Vec::new();
+    for (kind, name) in signals {
+        match signal(kind) {
+            Ok(listener) => listeners.push((listener, name)),
+            Err(err) => tracing::warn!(%err, signal = name, "could not listen for signal"),
+        }
+    }
+    if listeners.is_empty() {
+        return std::future::pending().await;
+    }
+
+    let received = listeners.iter_mut().map(|(listener, name)| {
+        Box::pin(async move {
+            listener.recv().await;
+            *name
+        })
+    });
+    let (name, ..) = futures::future::select_all(received).await;
+    name
+}
+
+/// Wait for a signal asking the process to stop, and name it. Never
+/// returns if signals can't be listened for.
+#[cfg(not(unix))]
+async fn termination_signal() -> &'static str {
+    if let Err(err) = tokio::signal::ctrl_c().await {
+        tracing::warn!(%err, "could not listen for Ctrl-C");
+        return std::future::pending().await;
+    }
+    "Ctrl-C"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn a_caught_worker_panic_leaves_raw_mode_enabled() {
+        // in the order main sets them up
+        install_panic_hook();
+        // raw mode needs a terminal, which test runs don't always have
+        let Ok(_terminal) = tanic_tui::init_terminal() else {
+            eprintln!("skipped: no terminal to put in raw mode");
+            return;
+        };
+
+        // as request workers do
+        let caught = futures::executor::block_on(
+            AssertUnwindSafe(async { panic!("the request failed") }).catch_unwind(),
+        );
+        let raw_mode = crossterm::terminal::is_raw_mode_enabled().unwrap();
+        ratatui::restore();
+
+        assert!(caught.is_err());
+        assert!(raw_mode);
+    }
+}
@@ -1,4 +1,7 @@
+use tanic_core::config::LoggingConfig;
 use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::*;
@@ -18,9 +21,61 @@ pub(crate) fn init() {
     .expect("Unable to set global subscriber");
 }
 
-pub(crate) fn init_tui_logger() {
+/// Send logs to the TUI's log panel and, if configured, to rotating log files.
+///
+/// The returned guard flushes the log file when dropped, and so must be held
+/// until the app exits.
+pub(crate) fn init_tui_logger(config: &LoggingConfig) -> Option<WorkerGuard> {
+    let mut file_error = None;
+    let (file_layer, guard) = match file_writer(config) {
+        Ok(Some((writer, guard))) => {
+            let layer = tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(
+                    EnvFilter::builder()
+                        .with_env_var("TANIC_LOG")
+                        .with_default_directive(LevelFilter::INFO.into())
+                        .from_env_lossy(),
+                );
+            (Some(layer), Some(guard))
+        }
+        Ok(None) => (None, None),
+        Err(err) => {
+            file_error = Some(err);
+            (None, None)
+        }
+    };
+
     tracing_subscriber::registry()
         .with(tui_logger::tracing_subscriber_layer())
+        .with(file_layer)
         .init();
     tui_logger::init_logger(tui_logger::LevelFilter::Trace).expect("Could not initialize logger");
+
+    if let Some(err) = file_error {
+        tracing::warn!(%err, "could not open log file, logging to the log panel only");
+    }
+
+    guard
+}
+
+fn file_writer(config: &LoggingConfig) -> Result<Option<(NonBlocking, WorkerGuard)>, String> {
+    if !config.to_file {
+        return Ok(None);
+    }
+
+    let log_dir = config
+        .log_dir()
+        .ok_or_else(|| "could not determine the data directory".to_string())?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("tanic")
+        .filename_suffix("log")
+        .max_log_files(config.max_files)
+        .build(log_dir)
+        .map_err(|err| err.to_string())?;
+
+    Ok(Some(tracing_appender::non_blocking(appender)))
 }
@@ -1,7 +1,10 @@
 use clap::Parser;
+use crossterm::event::EventStream;
 use miette::{IntoDiagnostic, Result};
 
-use crate::args::Args;
+use crate::args::{Args, Command};
+use crate::lifecycle::Tasks;
+use crate::session::Recorder;
 use tanic_core::config::ConnectionDetails;
 use tanic_core::TanicConfig;
 use tanic_svc::iceberg_context::IcebergContextManager;
@@ -9,39 +12,76 @@ use tanic_svc::{AppStateManager, TanicAction};
 use tanic_tui::TanicTui;
 
 mod args;
+mod export;
 mod lifecycle;
 mod logging;
+mod report;
+mod session;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    logging::init_tui_logger();
+    lifecycle::install_panic_hook();
 
     let args = Args::try_parse().into_diagnostic()?;
-    let config = TanicConfig::load().into_diagnostic()?;
+    let mut config = TanicConfig::load().into_diagnostic()?;
+    if let Some(icons) = args.icons {
+        config.theme.icons = icons;
+    }
+
+    match args.command {
+        Some(Command::Export(export_args)) => return export::run(export_args, &config).await,
+        Some(Command::Report(report_args)) => return report::run(report_args, &config).await,
+        None => {}
+    }
+
+    let _log_file_guard = logging::init_tui_logger(&config.logging);
     tracing::info!(?config, "loaded config");
     // let config = Arc::new(RwLock::new(config));
 
-    let (app_state, action_tx, state_rx) = AppStateManager::new(config);
-    let tanic_tui = TanicTui::new(action_tx.clone());
-    let iceberg_ctx_mgr = IcebergContextManager::new(action_tx.clone());
+    if let Some(path) = &args.replay {
+        return session::replay(path, config).await;
+    }
+
+    let recorder = args
+        .record
+        .as_deref()
+        .map(|path| Recorder::create(path, &config.theme))
+        .transpose()?;
+
+    let (app_state, action_tx, state_rx, command_rx) = AppStateManager::new(config.clone());
+
+    // when recording, actions are recorded on their way to the app state
+    let (ui_action_tx, action_tx) = match &recorder {
+        Some(recorder) => (
+            recorder.tap_ui_actions(action_tx.clone()),
+            recorder.tap_actions(action_tx),
+        ),
+        None => (action_tx.clone(), action_tx),
+    };
 
-    let svc_task = tokio::spawn(async move { app_state.event_loop().await });
+    let tanic_tui = TanicTui::new(ui_action_tx, &config);
+    let iceberg_ctx_mgr = IcebergContextManager::new(action_tx.clone(), &config);
+
+    let mut tasks = Tasks::default();
+    tasks.spawn("app state", app_state.event_loop());
     let ui_state_rx = state_rx.clone();
-    let ui_task = tokio::spawn(async move { tanic_tui.event_loop(ui_state_rx).await });
-    let iceberg_task_state_rx = state_rx.clone();
-    let iceberg_task =
-        tokio::spawn(async move { iceberg_ctx_mgr.event_loop(iceberg_task_state_rx).await });
+    tasks.spawn("UI", async move {
+        match recorder {
+            Some(recorder) => {
+                let events = recorder.record_terminal_events(EventStream::new());
+                tanic_tui.event_loop_with(events, ui_state_rx).await
+            }
+            None => tanic_tui.event_loop(ui_state_rx).await,
+        }
+    });
+    tasks.spawn("Iceberg context", iceberg_ctx_mgr.event_loop(command_rx));
 
-    if let Some(ref uri) = args.catalogue_uri {
+    for uri in &args.catalogue_uris {
         let connection = ConnectionDetails::new_anon(uri.clone());
 
-        let message = TanicAction::ConnectTo(connection);
+        let message = TanicAction::OpenTab(connection);
         action_tx.send(message).into_diagnostic()?;
     }
 
-    tokio::select! {
-        _ = ui_task => Ok(()),
-        _ = svc_task => Ok(()),
-        _ = iceberg_task => Ok(()),
-    }
+    tasks.run(action_tx, state_rx).await
 }
@@ -0,0 +1,75 @@
+//! Metadata Reports
+//!
+//! `tanic report` writes a report on a table's or a namespace's metadata,
+//! for attaching to data-contract reviews and tickets.
+
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+
+use tanic_core::config::ConnectionDetails;
+use tanic_core::TanicConfig;
+use tanic_svc::comparison::TableRef;
+use tanic_svc::iceberg_context::{IcebergContext, RestCatalogConnector};
+use tanic_svc::report::{ReportFormat, ReportTarget};
+
+use crate::args::ReportArgs;
+
+pub(crate) async fn run(args: ReportArgs, config: &TanicConfig) -> Result<()> {
+    let format = args
+        .format
+        .or_else(|| args.output.as_deref().and_then(ReportFormat::from_path))
+        .unwrap_or(ReportFormat::Markdown);
+
+    let connection = ConnectionDetails::new_anon(args.catalogue_uri);
+    let parts = args
+        .target
+        .split('.')
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    let target = if args.namespace {
+        ReportTarget::Namespace {
+            connection: connection.clone(),
+            namespace: parts,
+        }
+    } else {
+        let [namespace @ .., name] = parts.as_slice() else {
+            unreachable!("splitting always yields at least one part");
+        };
+        if namespace.is_empty() {
+            return Err(miette!(
+                "'{}' is not a <namespace>.<table> name; use --namespace to report on a namespace",
+                args.target
+            ));
+        }
+
+        ReportTarget::Table(TableRef {
+            connection: connection.clone(),
+            namespace: namespace.to_vec(),
+            name: name.clone(),
+        })
+    };
+
+    let iceberg_ctx = IcebergContext::connect_to(
+        &connection,
+        &RestCatalogConnector,
+        config.requests.timeout(),
+    );
+    let report = iceberg_ctx
+        .report(&target)
+        .await
+        .into_diagnostic()
+        .wrap_err_with(|| format!("could not report on {}", args.target))?;
+
+    match &args.output {
+        Some(path) => {
+            report
+                .write(path, format)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("could not write {}", path.display()))?;
+            eprintln!("wrote a report on {} to {}", args.target, path.display());
+        }
+        None => print!("{}", report.render(format).into_diagnostic()?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,346 @@
+//! Session Recording
+//!
+//! `--record` writes every terminal event and action to a file, one JSON
+//! object per line, along with when it happened. `--replay` feeds a recorded
+//! session back through the app, without a catalog or a terminal, and prints
+//! the frames that were drawn. Catalog responses are part of the recording,
+//! so a replay draws what the recorded session drew.
+
+use crossterm::event::Event;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use ratatui::Terminal;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::Instant;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+
+use tanic_core::config::ThemeConfig;
+use tanic_core::TanicConfig;
+use tanic_svc::{AppStateManager, TanicAction};
+use tanic_tui::headless::HeadlessBackend;
+use tanic_tui::TanicTui;
+
+/// How long a replay waits after its last event, for the UI to catch up
+const REPLAY_SETTLE_TIME: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionEntry {
+    /// time since the session started
+    elapsed_ms: u64,
+    event: SessionEvent,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum SessionEvent {
+    /// the first entry of every session
+    Started {
+        width: u16,
+        height: u16,
+        theme: ThemeConfig,
+    },
+
+    /// a terminal event, such as a key press, read by the UI
+    Terminal(Event),
+
+    /// an action from outside the UI, such as a catalog response
+    Action(TanicAction),
+
+    /// an action that the UI dispatched in response to a terminal event.
+    /// Not replayed, since replaying the terminal event dispatches it again.
+    UiAction(TanicAction),
+}
+
+/// Writes a session to a file as it happens
+#[derive(Clone)]
+pub(crate) struct Recorder {
+    started: Instant,
+    file: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path, theme: &ThemeConfig) -> Result<Self> {
+        let file = File::create(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("could not create recording {}", path.display()))?;
+        let (width, height) = crossterm::terminal::size().into_diagnostic()?;
+
+        Ok(Self::start(file, width, height, theme))
+    }
+
+    fn start(file: File, width: u16, height: u16, theme: &ThemeConfig) -> Self {
+        let recorder = Self {
+            started: Instant::now(),
+            file: Arc::new(Mutex::new(BufWriter::new(file))),
+        };
+        recorder.record(SessionEvent::Started {
+            width,
+            height,
+            theme: theme.clone(),
+        });
+
+        recorder
+    }
+
+    fn record(&self, event: SessionEvent) {
+        let entry = SessionEntry {
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            event,
+        };
+
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        let written = serde_json::to_writer(&mut *file, &entry)
+            .into_diagnostic()
+            .and_then(|()| writeln!(file).into_diagnostic())
+            .and_then(|()| file.flush().into_diagnostic());
+
+        if let Err(err) = written {
+            tracing::warn!(%err, "could not record session event");
+        }
+    }
+
+    /// Record terminal events as the UI reads them
+    pub(crate) fn record_terminal_events(
+        &self,
+        events: impl Stream<Item = std::io::Result<Event>> + Unpin,
+    ) -> impl Stream<Item = std::io::Result<Event>> + Unpin {
+        let recorder = self.clone();
+
+        events.map(move |event| {
+            if let Ok(event) = &event {
+                recorder.record(SessionEvent::Terminal(event.clone()));
+            }
+            event
+        })
+    }
+
+    /// A sender that records actions from outside the UI on their way to the app state
+    pub(crate) fn tap_actions(
+        &self,
+        action_tx: UnboundedSender<TanicAction>,
+    ) -> UnboundedSender<TanicAction> {
+        self.tap(action_tx, SessionEvent::Action)
+    }
+
+    /// A sender that records the UI's actions on their way to the app state
+    pub(crate) fn tap_ui_actions(
+        &self,
+        action_tx: UnboundedSender<TanicAction>,
+    ) -> UnboundedSender<TanicAction> {
+        self.tap(action_tx, SessionEvent::UiAction)
+    }
+
+    fn tap(
+        &self,
+        action_tx: UnboundedSender<TanicAction>,
+        to_event: fn(TanicAction) -> SessionEvent,
+    ) -> UnboundedSender<TanicAction> {
+        let (tap_tx, mut tap_rx) = tokio::sync::mpsc::unbounded_channel::<TanicAction>();
+        let recorder = self.clone();
+
+        tokio::spawn(async move {
+            while let Some(action) = tap_rx.recv().await {
+                recorder.record(to_event(action.clone()));
+                if action_tx.send(action).is_err() {
+                    break;
+                }
+            }
+        });
+
+        tap_tx
+    }
+}
+
+/// Replay a recorded session, printing the frame that was on screen before
+/// each terminal event, and the final frame
+pub(crate) async fn replay(path: &Path, mut config: TanicConfig) -> Result<()> {
+    let mut entries = read_session(path)?.into_iter();
+
+    let Some(SessionEntry {
+        event:
+            SessionEvent::Started {
+                width,
+                height,
+                theme,
+            },
+        ..
+    }) = entries.next()
+    else {
+        return Err(miette!("{} is not a recorded session", path.display()));
+    };
+    config.theme = theme;
+
+    let (app_state, action_tx, state_rx, mut command_rx) = AppStateManager::new(config.clone());
+    let tanic_tui = TanicTui::new(action_tx.clone(), &config);
+
+    let backend = HeadlessBackend::new(width, height);
+    let mut terminal = Terminal::new(backend.clone()).into_diagnostic()?;
+    let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let svc_task = tokio::spawn(app_state.event_loop());
+    let ui_task = tokio::spawn(async move {
+        tanic_tui
+            .run(
+                &mut terminal,
+                UnboundedReceiverStream::new(event_rx),
+                state_rx,
+            )
+            .await
+    });
+
+    // there is no catalog: the recorded responses stand in for it
+    tokio::spawn(async move { while command_rx.recv().await.is_some() {} });
+
+    let started = Instant::now();
+    for entry in entries {
+        tokio::time::sleep_until(started + Duration::from_millis(entry.elapsed_ms)).await;
+
+        match entry.event {
+            SessionEvent::Terminal(event) => {
+                if let Event::Resize(width, height) = event {
+                    backend.resize(width, height);
+                }
+
+                print_frame(
+                    &format!(
+                        "before {} at {:.3}s",
+                        describe(&event),
+                        entry.elapsed_ms as f64 / 1000.0
+                    ),
+                    &backend.screen(),
+                );
+                let _ = event_tx.send(Ok(event));
+            }
+            SessionEvent::Action(action) => {
+                let _ = action_tx.send(action);
+            }
+            SessionEvent::Started { .. } | SessionEvent::UiAction(_) => {}
+        }
+    }
+
+    tokio::time::sleep(REPLAY_SETTLE_TIME).await;
+    print_frame("final frame", &backend.screen());
+
+    // the session may have ended without quitting, such as by crashing
+    let _ = action_tx.send(TanicAction::Exit);
+    svc_task.await.into_diagnostic()?.into_diagnostic()?;
+    ui_task.await.into_diagnostic()?.into_diagnostic()?;
+
+    Ok(())
+}
+
+fn read_session(path: &Path) -> Result<Vec<SessionEntry>> {
+    let file = File::open(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("could not open recording {}", path.display()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|(idx, line)| {
+            let line = line.into_diagnostic()?;
+            serde_json::from_str(&line)
+                .into_diagnostic()
+                .wrap_err_with(|| {
+                    format!("invalid entry on line {} of {}", idx + 1, path.display())
+                })
+        })
+        .collect()
+}
+
+fn describe(event: &Event) -> String {
+    match event {
+        Event::Key(key) => format!("key {:?}", key.code),
+        Event::Resize(width, height) => format!("resize to {width}x{height}"),
+        other => format!("{other:?}"),
+    }
+}
+
+fn print_frame(title: &str, screen: &str) {
+    println!("── {title} ──");
+    println!("{screen}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Uri;
+    use tanic_core::config::ConnectionDetails;
+    use tanic_core::message::{NamespaceDeets, TableDeets};
+
+    /// The published state after each action, as the app state manager
+    /// reduces them
+    async fn states_after(actions: impl IntoIterator<Item = TanicAction>) -> Vec<String> {
+        let (app_state, action_tx, mut state_rx, _command_rx) =
+            AppStateManager::new(TanicConfig::default());
+        let svc_task = tokio::spawn(app_state.event_loop());
+
+        let mut states = vec![];
+        for action in actions {
+            action_tx.send(action).unwrap();
+            state_rx.changed().await.unwrap();
+            states.push(format!("{:?}", *state_rx.borrow_and_update()));
+        }
+
+        action_tx.send(TanicAction::Exit).unwrap();
+        svc_task.await.unwrap().unwrap();
+        states
+    }
+
+    #[tokio::test]
+    async fn a_replayed_session_reaches_the_recorded_states() {
+        let namespace = NamespaceDeets {
+            parts: vec!["sales".to_string()],
+            name: "sales".to_string(),
+            table_count: 1,
+            stale: true,
+        };
+        let table = TableDeets {
+            namespace: namespace.parts.clone(),
+            name: "orders".to_string(),
+            row_count: 1,
+            summary: None,
+            stale: true,
+        };
+        let actions = vec![
+            TanicAction::ConnectTo(ConnectionDetails::new_anon(Uri::from_static(
+                "http://localhost:8181",
+            ))),
+            TanicAction::RetrievedNamespaceList(vec![namespace.clone()]),
+            TanicAction::SelectNamespace,
+            TanicAction::RetrievedTableList(namespace, vec![table]),
+        ];
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let recorder = Recorder::start(
+            File::create(&path).unwrap(),
+            80,
+            24,
+            &ThemeConfig::default(),
+        );
+        for action in &actions {
+            recorder.record(SessionEvent::Action(action.clone()));
+        }
+        drop(recorder);
+
+        let replayed = read_session(&path)
+            .unwrap()
+            .into_iter()
+            .filter_map(|entry| match entry.event {
+                SessionEvent::Action(action) => Some(action),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let recorded = states_after(actions).await;
+        assert!(recorded.last().unwrap().contains("stale: true"));
+        assert_eq!(states_after(replayed).await, recorded);
+    }
+}